@@ -0,0 +1,62 @@
+// Builds the shared-memory (wasm threads) artifact into
+// ../mandelbrot/pkg-threads, run by `npm run build` and `npm run dev`. In
+// this build the crate's `parallel` feature renders each tile across a pool
+// of web workers sharing one linear memory, and every pool thread reads the
+// same cached reference orbit instead of computing its own. worker.js loads
+// it only on cross-origin-isolated pages (COOP/COEP headers, required for
+// SharedArrayBuffer) and performs the init_render_threads /
+// run_render_thread handshake (mandelbrot/src/threads.rs); other pages keep
+// the single-threaded pkg/pkg-relaxed builds.
+//
+// The web target (rather than the bundler default) exposes the compiled
+// module, so helper workers can instantiate it on the controlling worker's
+// memory (js/renderThread.js).
+//
+// Shared memory needs atomics, and std must be rebuilt with them, which is
+// nightly-only (-Z build-std).
+//
+// wasm-opt is skipped: wasm-pack's only wasm-opt hook is the crate's
+// release metadata list, shared with the pkg/pkg-relaxed builds. That list
+// does not pass --enable-threads, so wasm-opt would reject this module's
+// atomics, and enabling threads there would let it emit atomics into the
+// single-threaded builds as well.
+
+const { spawnSync } = require("child_process");
+const path = require("path");
+
+const crateDir = path.resolve(__dirname, "..", "mandelbrot");
+
+const result = spawnSync(
+  "wasm-pack",
+  [
+    "build",
+    "--release",
+    "--no-opt",
+    "--target",
+    "web",
+    "--out-dir",
+    "pkg-threads",
+    "--",
+    "--features",
+    "parallel",
+    "-Z",
+    "build-std=panic_abort,std",
+  ],
+  {
+    cwd: crateDir,
+    stdio: "inherit",
+    env: {
+      ...process.env,
+      RUSTUP_TOOLCHAIN: "nightly",
+      // A RUSTFLAGS env var replaces .cargo/config.toml's rustflags
+      // entirely, so it must re-include +simd128.
+      RUSTFLAGS:
+        "-C target-feature=+simd128,+atomics,+bulk-memory,+mutable-globals",
+    },
+  },
+);
+
+if (result.status !== 0) {
+  console.error("wasm threads build failed");
+  process.exit(result.status ?? 1);
+}
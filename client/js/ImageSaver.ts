@@ -7,7 +7,7 @@ import {
   isFixedPaletteMethod,
   MandelbrotConfig,
 } from "./config";
-import { fittedCdfForValues, TileIterationRange } from "./TileCache";
+import type { TileIterationRange } from "./TileCache";
 import { embedTextChunks } from "./pngMetadata";
 import { buildZip, encodeNpyFloat32 } from "./dataExport";
 import {
@@ -243,7 +243,7 @@ class ImageSaver {
       ),
     );
 
-    const cdf = this.exportCdf(
+    const cdf = await this.exportCdf(
       responses,
       columns,
       totalWidth,
@@ -287,18 +287,18 @@ class ImageSaver {
   }
 
   /** The equalization table fit to the whole export's own escape values over
-   * the config's palette window (see fittedCdfForValues), or null (linear)
-   * when the export has nothing to equalize to. The columns are stitched into
+   * the config's palette window (see RegionRenderer.fitPaletteCdf), or null
+   * (linear) when the export has nothing to equalize to. The columns are stitched into
    * one buffer first so the neighbor-capped, center-weighted histogram scan
    * sees the export as the single image it is. */
-  private exportCdf(
+  private async exportCdf(
     responses: MandelbrotResponse[],
     columns: ColumnLayout[],
     totalWidth: number,
     totalHeight: number,
     config: MandelbrotConfig,
     strength: number,
-  ): Float32Array | null {
+  ): Promise<Float32Array | null> {
     const columnValues = responses.map((response) => response.values);
     if (columnValues.some((values) => values === null)) {
       return null;
@@ -318,7 +318,7 @@ class ImageSaver {
             };
     }
 
-    return fittedCdfForValues(
+    return this.map.regionRenderer.fitPaletteCdf(
       this.stitchColumnValues(
         columns,
         columnValues as Float32Array[],
@@ -64,7 +64,14 @@ const REBASED_LEAFLET_ZOOM = 12;
 // core at 100% while rendering, starving the rest of the system (video decode
 // in other tabs stutters, fans spin up). Reserving a core keeps the OS
 // scheduler able to service other work at a negligible cost to render latency.
+//
+// Cross-origin-isolated pages run a single pool worker instead: it loads the
+// shared-memory build and splits each tile across that many render threads
+// of its own (see worker.js).
 function renderPoolSize(): number {
+  if (crossOriginIsolated) {
+    return 1;
+  }
   const cores = navigator.hardwareConcurrency || 4;
   return Math.max(1, cores - 1);
 }
@@ -1,6 +1,6 @@
 import type MandelbrotMap from "./MandelbrotMap";
 import type { RenderFrame } from "./RegionRenderer";
-import { fittedCdfForRender, fittedRangeForRender } from "./TileCache";
+import { fittedRangeForRender } from "./TileCache";
 import { renderSettingsFingerprint, standaloneColoring } from "./config";
 import type { ColoringOptions, TileRect } from "./protocol";
 import { FULL_SET_ZOOM } from "./magnification";
@@ -197,7 +197,7 @@ class MinimapView {
       if (standardMode && response.values) {
         const range = fittedRangeForRender(response, size, size);
         if (range) {
-          const cdf = fittedCdfForRender(
+          const cdf = await this.map.regionRenderer.fitPaletteCdfToRender(
             response,
             size,
             size,
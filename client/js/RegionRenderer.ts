@@ -1,5 +1,6 @@
 import type MandelbrotMap from "./MandelbrotMap";
 import type { TilePosition } from "./MandelbrotMap";
+import type { TileIterationRange } from "./TileCache";
 import { coloringOptions } from "./config";
 import {
   CalculateRequest,
@@ -8,6 +9,8 @@ import {
   DistanceEstimateResponse,
   JuliaRequest,
   MandelbrotResponse,
+  PaletteCdfRequest,
+  PaletteCdfResponse,
   PeriodRequest,
   PeriodResponse,
   RecolorRequest,
@@ -260,6 +263,63 @@ class RegionRenderer {
     )) as RecolorResponse;
   }
 
+  /** The histogram-equalization table (see `ColoringOptions.paletteCdf`) fit
+   * to a standalone render's escape values (row-major `height x width`,
+   * `Infinity` for interior pixels) over the palette `window`, blended toward
+   * linear by `strength` (0..1) — for consumers with a private palette fit:
+   * the image export, animation frames, the Navigator thumbnails. `range` is
+   * the values' escaped-pixel iteration range. Fit in a worker by the crate's
+   * `EscapeHistogram`, the same center-weighted, neighbor-capped scan as the
+   * map's on-screen fit. Null (linear) at strength 0, when `range` is null
+   * (all interior), or when the window holds no escaped mass. */
+  async fitPaletteCdf(
+    values: Float32Array,
+    width: number,
+    height: number,
+    range: TileIterationRange | null,
+    window: TileIterationRange,
+    strength: number,
+  ): Promise<Float32Array | null> {
+    if (range === null || strength <= 0) {
+      return null;
+    }
+    const request: PaletteCdfRequest = {
+      type: "paletteCdf",
+      payload: { values, width, height, range, window, strength },
+    };
+
+    return (await this.map.pool.queue((workerTask) =>
+      workerTask(request),
+    )) as PaletteCdfResponse;
+  }
+
+  /** `fitPaletteCdf` over a render response's own escape values and range;
+   * null when the response carries none. */
+  async fitPaletteCdfToRender(
+    response: MandelbrotResponse,
+    width: number,
+    height: number,
+    window: TileIterationRange,
+    strength: number,
+  ): Promise<Float32Array | null> {
+    if (
+      !response.values ||
+      response.minIter === null ||
+      response.maxIter === null
+    ) {
+      return null;
+    }
+
+    return this.fitPaletteCdf(
+      response.values,
+      width,
+      height,
+      { min: response.minIter, max: response.maxIter },
+      window,
+      strength,
+    );
+  }
+
   /** Renders the region and returns only its per-pixel smoothed escape values
    * (row-major, `imageHeight * imageWidth` floats; `Infinity` for interior
    * pixels), skipping the colorized image. Used by the raw-data export, which
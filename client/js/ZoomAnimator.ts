@@ -1,6 +1,6 @@
 import { saveAs } from "file-saver";
 import type MandelbrotMap from "./MandelbrotMap";
-import { fittedCdfForRender, fittedRangeForRender } from "./TileCache";
+import { fittedRangeForRender } from "./TileCache";
 import { AnimationSpec, buildFrameRects, frameCount } from "./animationFrames";
 import { coloringOptions, MandelbrotConfig } from "./config";
 import type { TileRect } from "./protocol";
@@ -328,10 +328,10 @@ class ZoomAnimator {
       };
       // Histogram coloring: equalize against this frame's own escape-value
       // distribution over that window, blended toward linear by `strength`
-      // (see fittedCdfForRender / buildPaletteCdf). Null (linear, no table) at
+      // (see RegionRenderer.fitPaletteCdf). Null (linear, no table) at
       // strength 0 or when the frame has no escaped mass in the window.
       const cdf = equalize
-        ? fittedCdfForRender(
+        ? await renderer.fitPaletteCdfToRender(
             response,
             spec.width,
             spec.height,
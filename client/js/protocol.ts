@@ -137,6 +137,24 @@ export type RecolorPayload = {
   coloring: ColoringOptions;
 };
 export type RecolorRequest = { type: "recolor"; payload: RecolorPayload };
+// Fits a histogram-equalization table to one standalone render's escape
+// values with the crate's `EscapeHistogram` (see
+// RegionRenderer.fitPaletteCdf).
+export type PaletteCdfPayload = {
+  values: Float32Array;
+  width: number;
+  height: number;
+  // Escaped-pixel iteration range of the values: the histogram's domain.
+  range: { min: number; max: number };
+  // Palette window the table spans.
+  window: { min: number; max: number };
+  // Blend toward the linear mapping, 0..1.
+  strength: number;
+};
+export type PaletteCdfRequest = {
+  type: "paletteCdf";
+  payload: PaletteCdfPayload;
+};
 export type OptimisePayload = { buffer: ArrayBuffer };
 export type OptimiseRequest = { type: "optimise"; payload: OptimisePayload };
 // Tier-up warmup for the deep general-exponent (multibrot) perturbation
@@ -162,6 +180,7 @@ export type WorkerRequest =
   | PeriodRequest
   | OptimiseRequest
   | RecolorRequest
+  | PaletteCdfRequest
   | WarmupGeneralRequest
   | WarmupGeneralDirectRequest
   | WarmupDeepRequest
@@ -183,6 +202,8 @@ export type MandelbrotResponse = {
 };
 export type OptimiseResponse = ArrayBuffer;
 export type RecolorResponse = Uint8Array;
+// The equalization table, or null for the linear mapping.
+export type PaletteCdfResponse = Float32Array | null;
 // Exterior distance estimate in complex-plane units, or negative when the
 // point is inside the set (no exterior distance).
 export type DistanceEstimateResponse = number;
@@ -194,6 +215,7 @@ export type WorkerResponse =
   | DistanceEstimateResponse
   | PeriodResponse
   | OptimiseResponse
-  | RecolorResponse;
+  | RecolorResponse
+  | PaletteCdfResponse;
 
 export type TaskThread = FunctionThread<[WorkerRequest], WorkerResponse>;
@@ -0,0 +1,11 @@
+// One render pool thread of the shared-memory build (see startRenderThreads
+// in worker.js): instantiates the module on the memory the controlling
+// worker posts, reports that it is ready, and runs rayon's worker loop for
+// the life of the page.
+import initThreads, { run_render_thread } from "../../mandelbrot/pkg-threads";
+
+self.onmessage = async ({ data: { module, memory } }) => {
+  await initThreads({ module_or_path: module, memory });
+  self.postMessage("ready");
+  run_render_thread();
+};
@@ -2,7 +2,7 @@ import * as L from "leaflet";
 import throttle from "lodash/throttle";
 import type MandelbrotMap from "../MandelbrotMap";
 import MinimapView, { thumbnailRenderSize } from "../MinimapView";
-import { fittedCdfForRender, fittedRangeForRender } from "../TileCache";
+import { fittedRangeForRender } from "../TileCache";
 import { renderSettingsFingerprint, standaloneColoring } from "../config";
 import type { ColoringOptions } from "../protocol";
 import type { NavigatorMode } from "../state/uiState";
@@ -274,7 +274,7 @@ class NavigatorEngine {
       let image = response.image;
       const range = fittedRangeForRender(response, size, size);
       if (range && response.values) {
-        const cdf = fittedCdfForRender(
+        const cdf = await this.map.regionRenderer.fitPaletteCdfToRender(
           response,
           size,
           size,
@@ -29,10 +29,47 @@ const supportsRelaxedSimd = WebAssembly.validate(
   ]),
 );
 
-(supportsRelaxedSimd
-  ? import("../../mandelbrot/pkg-relaxed")
-  : import("../../mandelbrot/pkg")
-)
+// Starts `count` helper workers on the shared-memory build's module and
+// memory, waits until each has instantiated it, then builds the render pool
+// they join. Helpers must be running before init_render_threads blocks this
+// worker: a browser may not start a nested worker while its parent is
+// blocked.
+async function startRenderThreads(threads, count) {
+  const module = threads.render_thread_module();
+  const memory = threads.render_thread_memory();
+  await Promise.all(
+    Array.from({ length: count }, () => {
+      const helper = new Worker(new URL("./renderThread.js", import.meta.url));
+      return new Promise((resolve, reject) => {
+        helper.onmessage = resolve;
+        helper.onerror = reject;
+        helper.postMessage({ module, memory });
+      });
+    }),
+  );
+  threads.init_render_threads(count);
+}
+
+// Cross-origin-isolated pages can share memory between workers: there the
+// page runs this worker alone (renderPoolSize in MandelbrotMap.ts) on the
+// shared-memory build (build-threads-wasm.js), which renders each tile
+// across helper threads reading one reference orbit cache. It takes the
+// one-core-free thread count the page would otherwise have given its pool.
+// Elsewhere each pool worker loads its own single-threaded build.
+async function loadWasm() {
+  if (self.crossOriginIsolated) {
+    const threads = await import("../../mandelbrot/pkg-threads");
+    await threads.default();
+    const cores = navigator.hardwareConcurrency || 4;
+    await startRenderThreads(threads, Math.max(1, cores - 1));
+    return threads;
+  }
+  return supportsRelaxedSimd
+    ? import("../../mandelbrot/pkg-relaxed")
+    : import("../../mandelbrot/pkg");
+}
+
+loadWasm()
   .then(async (wasm) => {
     wasm.init();
 
@@ -219,6 +256,29 @@ const supportsRelaxedSimd = WebAssembly.validate(
     const recolorTile = (params) =>
       wasm.recolor_tile(params.values, params.coloring);
 
+    // Histogram-equalization table fit to one standalone render's escape
+    // values by the crate's `EscapeHistogram`; null means the linear
+    // mapping. The wasm side takes integer window bounds, and derived
+    // animation windows can be fractional.
+    const paletteCdf = (params) => {
+      const histogram = new wasm.EscapeHistogram(
+        params.range.min,
+        params.range.max,
+      );
+      try {
+        histogram.add_values(params.values, params.width, params.height);
+        return (
+          histogram.palette_cdf(
+            Math.round(params.window.min),
+            Math.round(params.window.max),
+            params.strength,
+          ) ?? null
+        );
+      } finally {
+        histogram.free();
+      }
+    };
+
     // Exterior distance estimate for a single point (issue #42): a dedicated
     // scalar loop in wasm that tracks the orbit derivative, far cheaper than a
     // one-pixel render. Returns complex-plane units, negative when the point
@@ -254,6 +314,8 @@ const supportsRelaxedSimd = WebAssembly.validate(
           return period(request.payload);
         case "recolor":
           return recolorTile(request.payload);
+        case "paletteCdf":
+          return paletteCdf(request.payload);
         case "optimise":
           return await optimiseImage(request.payload);
         case "warmupGeneral":
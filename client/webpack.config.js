@@ -151,8 +151,10 @@ const workerConfig = {
       },
       {
         test: /\.wasm$/,
-        // Both wasm-pack outputs (pkg/ and pkg-relaxed/) are wasm modules
-        // compiled by asyncWebAssembly, not file assets.
+        // The bundler-target wasm-pack outputs (pkg/ and pkg-relaxed/) are
+        // wasm modules compiled by asyncWebAssembly, not file assets. The
+        // web-target pkg-threads/ build fetches its wasm by URL, so it stays
+        // an asset.
         exclude: /pkg(-relaxed)?\/.*\.wasm$/,
         type: "asset/resource",
         generator: {
@@ -1,9 +1,13 @@
 //! Renders a preview grid of every color scheme the app exposes, plus
-//! per-palette lightness stats, to diagnose low-contrast palettes.
+//! per-palette perceptual metrics (see `mandelbrot::palette_metrics`), to
+//! diagnose low-contrast and CVD-unfriendly palettes.
 //!
 //! Usage: cargo run --release --example palette_preview -- <output_dir>
 
-use mandelbrot::{get_mandelbrot_tile_precise, recolor_values, ColoringOptions, ValidColorSpace};
+use mandelbrot::{
+    get_mandelbrot_tile_precise, palette_metrics, recolor_values, ColorVisionDeficiency,
+    ColoringOptions, ValidColorSpace,
+};
 use rayon::prelude::*;
 
 const TILE: usize = 256;
@@ -58,46 +62,21 @@ const SCHEMES: &[&str] = &[
     "sinebow",
 ];
 
-/// Samples a palette as the app applies it (default orientation, no
-/// transforms): 256 RGBA pixels across the full gradient.
-fn palette_colors(name: &str) -> Vec<u8> {
-    let values: Vec<f32> = (0..256).map(|i| i as f32).collect();
-    recolor_values(
-        &values,
-        &ColoringOptions {
-            palette: name.to_string(),
-            reverse_colors: false,
-            shift_hue_amount: 0.0,
-            saturate_amount: 0.0,
-            lighten_amount: 0.0,
-            color_space: 0, // Hsl
-            palette_min_iter: 0,
-            palette_max_iter: 255,
-            color_density: 1,
-            distance_estimate: false,
-            atom_domain: false,
-            palette_cdf: None,
-            palette_offset: 0.0,
-        },
-    )
+/// A palette as the app applies it (default orientation, no transforms),
+/// windowed over values 0..=255.
+fn palette_coloring(name: &str) -> ColoringOptions {
+    ColoringOptions {
+        palette: name.to_string(),
+        palette_max_iter: 255,
+        ..Default::default()
+    }
 }
 
-// CIE L* (0-100) of an sRGB color.
-fn lightness(r: u8, g: u8, b: u8) -> f64 {
-    fn lin(u: u8) -> f64 {
-        let v = u as f64 / 255.0;
-        if v <= 0.04045 {
-            v / 12.92
-        } else {
-            ((v + 0.055) / 1.055).powf(2.4)
-        }
-    }
-    let y = 0.2126 * lin(r) + 0.7152 * lin(g) + 0.0722 * lin(b);
-    if y > 0.008856 {
-        116.0 * y.cbrt() - 16.0
-    } else {
-        903.3 * y
-    }
+/// Samples a palette as the app applies it: 256 RGBA pixels across the full
+/// gradient.
+fn palette_colors(name: &str) -> Vec<u8> {
+    let values: Vec<f32> = (0..256).map(|i| i as f32).collect();
+    recolor_values(&values, &palette_coloring(name))
 }
 
 fn main() {
@@ -114,27 +93,28 @@ fn main() {
     let strips: Vec<Vec<u8>> = SCHEMES.iter().map(|name| palette_colors(name)).collect();
 
     println!(
-        "{:<20} {:>7} {:>7} {:>7} {:>9}",
-        "scheme", "L*min", "L*max", "range", "meanStep"
+        "{:<20} {:>7} {:>7} {:>7} {:>6} {:>9} {:>9} {:>9} {:>9}",
+        "scheme", "L*min", "L*max", "range", "mono", "meanDE", "variation", "deutanDE", "tritanDE"
     );
     let mut stats: Vec<(&str, f64)> = Vec::new();
-    for (name, strip) in SCHEMES.iter().zip(&strips) {
-        let ls: Vec<f64> = strip
-            .chunks_exact(4)
-            .map(|px| lightness(px[0], px[1], px[2]))
-            .collect();
-        let min = ls.iter().cloned().fold(f64::INFINITY, f64::min);
-        let max = ls.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
-        let mean_step: f64 = ls.windows(2).map(|w| (w[1] - w[0]).abs()).sum::<f64>() / 255.0;
+    for name in SCHEMES {
+        let coloring = palette_coloring(name);
+        let metrics = palette_metrics(&coloring, None);
+        let deutan = palette_metrics(&coloring, Some(ColorVisionDeficiency::Deuteranopia));
+        let tritan = palette_metrics(&coloring, Some(ColorVisionDeficiency::Tritanopia));
         println!(
-            "{:<20} {:>7.1} {:>7.1} {:>7.1} {:>9.3}",
+            "{:<20} {:>7.1} {:>7.1} {:>7.1} {:>6.2} {:>9.3} {:>9.3} {:>9.3} {:>9.3}",
             name,
-            min,
-            max,
-            max - min,
-            mean_step
+            metrics.lightness_min,
+            metrics.lightness_max,
+            metrics.lightness_range,
+            metrics.lightness_monotonicity,
+            metrics.mean_step,
+            metrics.step_variation,
+            deutan.mean_step,
+            tritan.mean_step
         );
-        stats.push((name, max - min));
+        stats.push((name, metrics.lightness_range));
     }
     stats.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
     println!("\nSorted by L* range (lowest contrast first):");
@@ -0,0 +1,241 @@
+//! Color-vision-deficiency (CVD) tooling: simulating how dichromats see a
+//! rendered image, scoring palettes by perceptual contrast, and remapping
+//! palettes so the differences a dichromat would lose survive.
+//!
+//! Simulation uses the Machado, Oliveira & Fernandes (2009) full-severity
+//! matrices in linear RGB. The CVD-safe remap daltonizes each palette entry
+//! (Fidaner et al.): the part of a color the deficiency hides is shifted
+//! onto channels it keeps. Remapped palettes are compiled once and cached
+//! like re-blended ones (see `ColoringOptions::cvd_remap`), so renders and
+//! recolors at every output depth pick the remap up.
+
+use palette::color_difference::Ciede2000;
+use palette::white_point::D65;
+use palette::{FromColor, Lab, Srgb};
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    for_each_precise_color, linear_to_srgb, srgb_to_linear, ColoringOptions, PreciseColor,
+    NUM_COLOR_CHANNELS,
+};
+
+#[cfg(test)]
+#[path = "accessibility_test.rs"]
+mod accessibility_test;
+
+/// A dichromatic color vision deficiency. Field values mirror the client's
+/// camelCase payload.
+#[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ColorVisionDeficiency {
+    /// No functioning long-wavelength (red) cones.
+    Protanopia,
+    /// No functioning medium-wavelength (green) cones.
+    Deuteranopia,
+    /// No functioning short-wavelength (blue) cones.
+    Tritanopia,
+}
+
+impl ColorVisionDeficiency {
+    /// Machado et al.'s severity-1 simulation matrix, acting on linear RGB.
+    fn simulation_matrix(self) -> [[f64; 3]; 3] {
+        match self {
+            ColorVisionDeficiency::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            ColorVisionDeficiency::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            ColorVisionDeficiency::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+
+    /// Where the daltonization error (what the deficiency hides) goes: the
+    /// red-green deficiencies shift it into green and blue, tritanopia into
+    /// red and green.
+    fn error_shift_matrix(self) -> [[f64; 3]; 3] {
+        match self {
+            ColorVisionDeficiency::Protanopia | ColorVisionDeficiency::Deuteranopia => {
+                [[0.0, 0.0, 0.0], [0.7, 1.0, 0.0], [0.7, 0.0, 1.0]]
+            }
+            ColorVisionDeficiency::Tritanopia => {
+                [[1.0, 0.0, 0.7], [0.0, 1.0, 0.7], [0.0, 0.0, 0.0]]
+            }
+        }
+    }
+
+    /// How an sRGB-encoded color looks to a dichromat, as sRGB-encoded
+    /// channels clamped to the gamut.
+    pub fn simulate(self, color: PreciseColor) -> PreciseColor {
+        let simulated = apply_matrix(self.simulation_matrix(), color.map(srgb_to_linear));
+        simulated.map(|channel| linear_to_srgb(channel.clamp(0.0, 1.0)))
+    }
+
+    /// `color` daltonized for this deficiency, sRGB-encoded and clamped to
+    /// the gamut. Greys, which every deficiency sees unchanged, pass through.
+    pub fn daltonize(self, color: PreciseColor) -> PreciseColor {
+        let linear = color.map(srgb_to_linear);
+        let simulated = apply_matrix(self.simulation_matrix(), linear);
+        let error = [0, 1, 2].map(|channel| linear[channel] - simulated[channel]);
+        let shift = apply_matrix(self.error_shift_matrix(), error);
+        [0, 1, 2].map(|channel| linear_to_srgb((linear[channel] + shift[channel]).clamp(0.0, 1.0)))
+    }
+}
+
+fn apply_matrix(matrix: [[f64; 3]; 3], color: [f64; 3]) -> [f64; 3] {
+    matrix.map(|row| row[0] * color[0] + row[1] * color[1] + row[2] * color[2])
+}
+
+/// Simulates `deficiency` on 8-bit RGBA pixels in place, leaving alpha.
+pub fn simulate_color_vision_deficiency_rgba8(image: &mut [u8], deficiency: ColorVisionDeficiency) {
+    for pixel in image.chunks_exact_mut(NUM_COLOR_CHANNELS) {
+        let color = [0, 1, 2].map(|index| f64::from(pixel[index]) / 255.0);
+        for (channel, value) in pixel.iter_mut().zip(deficiency.simulate(color)) {
+            *channel = (value * 255.0).round() as u8;
+        }
+    }
+}
+
+/// Returns a copy of a rendered 8-bit RGBA `image` as a dichromat would see
+/// it. `deficiency` is a `ColorVisionDeficiency` payload (`"protanopia"`,
+/// `"deuteranopia"` or `"tritanopia"`).
+#[wasm_bindgen]
+pub fn simulate_color_vision_deficiency(
+    image: &[u8],
+    deficiency: JsValue,
+) -> Result<Vec<u8>, JsValue> {
+    let deficiency: ColorVisionDeficiency =
+        serde_wasm_bindgen::from_value(deficiency).map_err(JsValue::from)?;
+    let mut simulated = image.to_vec();
+    simulate_color_vision_deficiency_rgba8(&mut simulated, deficiency);
+    Ok(simulated)
+}
+
+/// Number of colors `palette_metrics` samples across the palette window.
+const PALETTE_METRIC_SAMPLES: usize = 256;
+
+/// Perceptual contrast metrics of a palette as a coloring applies it,
+/// sampled at `PALETTE_METRIC_SAMPLES` evenly spaced values across its
+/// palette window.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct PaletteMetrics {
+    /// Darkest CIE L* (0-100) in the palette.
+    pub lightness_min: f64,
+    /// Lightest CIE L* in the palette.
+    pub lightness_max: f64,
+    /// `lightness_max - lightness_min`: low values read as flat, and are
+    /// the first to lose structure for viewers who rely on lightness alone.
+    pub lightness_range: f64,
+    /// How consistently L* moves in one direction, in [0, 1]: the net L*
+    /// change over the total L* travelled. 1 is a monotone ramp; diverging
+    /// and cyclic palettes score near 0.
+    pub lightness_monotonicity: f64,
+    /// Mean CIEDE2000 difference between neighboring samples.
+    pub mean_step: f64,
+    /// Smallest CIEDE2000 difference between neighboring samples: where the
+    /// palette is hardest to tell apart.
+    pub min_step: f64,
+    /// Coefficient of variation (standard deviation over mean) of the
+    /// CIEDE2000 steps. 0 is perceptually uniform; larger values mean some
+    /// stretches of the palette change much faster than others.
+    pub step_variation: f64,
+}
+
+/// Scores the palette `options` applies, optionally as seen with
+/// `deficiency` (simulated after the coloring, including any
+/// `ColoringOptions::cvd_remap`).
+pub fn palette_metrics(
+    options: &ColoringOptions,
+    deficiency: Option<ColorVisionDeficiency>,
+) -> PaletteMetrics {
+    let (min, max) = options.palette_thresholds();
+    let values: Vec<f32> = (0..PALETTE_METRIC_SAMPLES)
+        .map(|index| {
+            let fraction = index as f64 / (PALETTE_METRIC_SAMPLES - 1) as f64;
+            (min + (max - min) * fraction) as f32
+        })
+        .collect();
+    let mut colors = vec![[0.0; 3]; values.len()];
+    for_each_precise_color(&values, options, |pixel_index, color| {
+        colors[pixel_index] = match deficiency {
+            Some(deficiency) => deficiency.simulate(color),
+            None => color,
+        };
+    });
+
+    let labs: Vec<Lab<D65, f64>> = colors
+        .iter()
+        .map(|&[r, g, b]| Lab::from_color(Srgb::new(r, g, b)))
+        .collect();
+    let lightness_min = labs.iter().map(|lab| lab.l).fold(f64::INFINITY, f64::min);
+    let lightness_max = labs
+        .iter()
+        .map(|lab| lab.l)
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    let lightness_steps: Vec<f64> = labs.windows(2).map(|pair| pair[1].l - pair[0].l).collect();
+    let travelled: f64 = lightness_steps.iter().map(|step| step.abs()).sum();
+    let lightness_monotonicity = if travelled > 0.0 {
+        lightness_steps.iter().sum::<f64>().abs() / travelled
+    } else {
+        0.0
+    };
+
+    let steps: Vec<f64> = labs
+        .windows(2)
+        .map(|pair| pair[0].difference(pair[1]))
+        .collect();
+    let mean_step = steps.iter().sum::<f64>() / steps.len() as f64;
+    let min_step = steps.iter().copied().fold(f64::INFINITY, f64::min);
+    let variance = steps
+        .iter()
+        .map(|step| (step - mean_step).powi(2))
+        .sum::<f64>()
+        / steps.len() as f64;
+    let step_variation = if mean_step > 0.0 {
+        variance.sqrt() / mean_step
+    } else {
+        0.0
+    };
+
+    PaletteMetrics {
+        lightness_min,
+        lightness_max,
+        lightness_range: lightness_max - lightness_min,
+        lightness_monotonicity,
+        mean_step,
+        min_step,
+        step_variation,
+    }
+}
+
+/// A coloring to score and the deficiency, if any, to score it under, as
+/// `analyze_palette` takes them. Field names mirror the client's camelCase
+/// payload.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteAnalysisOptions {
+    pub coloring: ColoringOptions,
+    /// Defaults to `None`: normal color vision.
+    #[serde(default)]
+    pub deficiency: Option<ColorVisionDeficiency>,
+}
+
+/// Reports `PaletteMetrics` for a `PaletteAnalysisOptions` payload, for
+/// ranking palettes (and their CVD-safe remaps) by accessibility.
+#[wasm_bindgen]
+pub fn analyze_palette(options: JsValue) -> Result<PaletteMetrics, JsValue> {
+    let options: PaletteAnalysisOptions =
+        serde_wasm_bindgen::from_value(options).map_err(JsValue::from)?;
+    Ok(palette_metrics(&options.coloring, options.deficiency))
+}
@@ -0,0 +1,119 @@
+use super::*;
+use crate::recolor_values;
+
+fn coloring(palette: &str) -> ColoringOptions {
+    ColoringOptions {
+        palette: palette.to_string(),
+        palette_max_iter: 255,
+        ..Default::default()
+    }
+}
+
+const DEFICIENCIES: [ColorVisionDeficiency; 3] = [
+    ColorVisionDeficiency::Protanopia,
+    ColorVisionDeficiency::Deuteranopia,
+    ColorVisionDeficiency::Tritanopia,
+];
+
+fn difference(a: PreciseColor, b: PreciseColor) -> f64 {
+    let lab = |[r, g, b]: PreciseColor| Lab::<D65, f64>::from_color(Srgb::new(r, g, b));
+    lab(a).difference(lab(b))
+}
+
+#[test]
+fn greys_look_the_same_to_everyone() {
+    for deficiency in DEFICIENCIES {
+        for grey in [0.0, 0.25, 0.5, 1.0] {
+            let color = [grey; 3];
+            assert!(difference(deficiency.simulate(color), color) < 0.01);
+            assert!(difference(deficiency.daltonize(color), color) < 0.01);
+        }
+    }
+}
+
+#[test]
+fn red_green_deficiencies_confuse_red_and_green() {
+    let (red, green) = ([0.8, 0.2, 0.1], [0.3, 0.6, 0.1]);
+    let normal = difference(red, green);
+    for deficiency in [
+        ColorVisionDeficiency::Protanopia,
+        ColorVisionDeficiency::Deuteranopia,
+    ] {
+        let simulated = difference(deficiency.simulate(red), deficiency.simulate(green));
+        assert!(simulated < normal / 2.0, "{deficiency:?}: {simulated}");
+
+        // Daltonizing first keeps more of the difference visible.
+        let remapped = difference(
+            deficiency.simulate(deficiency.daltonize(red)),
+            deficiency.simulate(deficiency.daltonize(green)),
+        );
+        assert!(remapped > simulated, "{deficiency:?}: {remapped}");
+    }
+}
+
+#[test]
+fn simulation_leaves_alpha() {
+    let mut image = vec![255, 0, 0, 17, 128, 128, 128, 255];
+    simulate_color_vision_deficiency_rgba8(&mut image, ColorVisionDeficiency::Protanopia);
+    assert_eq!(image[3], 17);
+    assert_ne!(image[..3], [255, 0, 0]);
+    assert_eq!(image[4..], [128, 128, 128, 255]);
+}
+
+#[test]
+fn metrics_separate_ramps_from_cyclic_palettes() {
+    let greys = palette_metrics(&coloring("greys"), None);
+    assert!(greys.lightness_range > 90.0, "{greys:?}");
+    assert!(greys.lightness_monotonicity > 0.99, "{greys:?}");
+    assert!(greys.mean_step > greys.min_step, "{greys:?}");
+
+    let rainbow = palette_metrics(&coloring("rainbow"), None);
+    assert!(rainbow.lightness_monotonicity < 0.5, "{rainbow:?}");
+
+    // Viridis was designed for perceptually even steps; jet was not.
+    let viridis = palette_metrics(&coloring("viridis"), None);
+    let jet = palette_metrics(&coloring("jet"), None);
+    assert!(
+        viridis.step_variation < jet.step_variation,
+        "{viridis:?} vs {jet:?}"
+    );
+
+    // Greys carry their structure in lightness, which no deficiency takes.
+    let simulated = palette_metrics(
+        &coloring("greys"),
+        Some(ColorVisionDeficiency::Deuteranopia),
+    );
+    assert!((simulated.lightness_range - greys.lightness_range).abs() < 1.0);
+}
+
+#[test]
+fn cvd_remap_recolors_through_a_daltonized_palette() {
+    let values: Vec<f32> = (0..=255).map(|value| value as f32).collect();
+    let remapped = ColoringOptions {
+        cvd_remap: Some(ColorVisionDeficiency::Deuteranopia),
+        ..coloring("redYellowGreen")
+    };
+    assert_ne!(
+        recolor_values(&values, &remapped),
+        recolor_values(&values, &coloring("redYellowGreen"))
+    );
+
+    // The remap helps the viewer it targets tell the palette apart.
+    let deficiency = Some(ColorVisionDeficiency::Deuteranopia);
+    let before = palette_metrics(&coloring("redYellowGreen"), deficiency);
+    let after = palette_metrics(&remapped, deficiency);
+    assert!(
+        after.mean_step > before.mean_step,
+        "{before:?} vs {after:?}"
+    );
+
+    // Greys have nothing to remap.
+    let greys = ColoringOptions {
+        cvd_remap: Some(ColorVisionDeficiency::Protanopia),
+        ..coloring("greys")
+    };
+    assert_eq!(
+        recolor_values(&values, &greys),
+        recolor_values(&values, &coloring("greys"))
+    );
+}
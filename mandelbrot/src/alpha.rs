@@ -0,0 +1,95 @@
+//! Alpha output.
+//!
+//! Tiles are opaque by default. `ColoringOptions` can instead make interior
+//! pixels transparent (`transparent_interior`), derive alpha from each
+//! pixel's value (`alpha_ramp` — the escape ramp, or the distance-estimate
+//! brightness in that coloring method), and premultiply the color channels
+//! by the alpha (`premultiplied_alpha`), so exports composite over other
+//! artwork and the client can layer tiles over its own background. Alpha
+//! comes from the base coloring's values, after layers and dithering; the
+//! linear-RGB output has no alpha channel and stays as is.
+
+use serde::Deserialize;
+
+use crate::{ColoringOptions, NUM_COLOR_CHANNELS};
+
+#[cfg(test)]
+#[path = "alpha_test.rs"]
+mod alpha_test;
+
+/// How a pixel's value maps to its alpha. Field values mirror the client's
+/// camelCase payload.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum AlphaRamp {
+    /// Alpha rises from 0 at the start of the palette window to 1 at its
+    /// end: late-escaping pixels, or pixels far from the boundary in the
+    /// distance-estimate method, are the most opaque.
+    Value,
+    /// The reverse of `Value`: early-escaping pixels, or the boundary in
+    /// the distance-estimate method, are the most opaque.
+    InverseValue,
+}
+
+/// Whether `options` asks for anything but opaque output.
+pub(crate) fn has_alpha(options: &ColoringOptions) -> bool {
+    options.transparent_interior || options.alpha_ramp.is_some()
+}
+
+/// The alpha in [0, 1] of a pixel with cached `value`. Interior (non-finite)
+/// pixels are transparent with `transparent_interior` and opaque otherwise,
+/// whatever the ramp.
+pub(crate) fn pixel_alpha(value: f32, options: &ColoringOptions) -> f64 {
+    let value = options.palette_value(value);
+    if !value.is_finite() {
+        return if options.transparent_interior {
+            0.0
+        } else {
+            1.0
+        };
+    }
+    let Some(ramp) = options.alpha_ramp else {
+        return 1.0;
+    };
+    let (min, max) = options.palette_thresholds();
+    let position = ((value - min) / (max - min)).clamp(0.0, 1.0);
+    match ramp {
+        AlphaRamp::Value => position,
+        AlphaRamp::InverseValue => 1.0 - position,
+    }
+}
+
+/// Applies `options`' alpha to 8-bit RGBA `image` in place, one pixel per
+/// entry of `values`, premultiplying the color channels if asked. Leaves
+/// the image opaque when `options` asks for no alpha, and untouched when
+/// `values` does not hold one value per pixel.
+pub(crate) fn apply_alpha_rgba8(image: &mut [u8], values: &[f32], options: &ColoringOptions) {
+    if !has_alpha(options) || image.len() != values.len() * NUM_COLOR_CHANNELS {
+        return;
+    }
+    for (pixel, &value) in image.chunks_exact_mut(NUM_COLOR_CHANNELS).zip(values) {
+        let alpha = (pixel_alpha(value, options) * 255.0).round() as u8;
+        pixel[3] = alpha;
+        if options.premultiplied_alpha {
+            for channel in &mut pixel[..3] {
+                *channel = ((u32::from(*channel) * u32::from(alpha) + 127) / 255) as u8;
+            }
+        }
+    }
+}
+
+/// `apply_alpha_rgba8` for 16-bit-per-channel RGBA.
+pub(crate) fn apply_alpha_rgba16(image: &mut [u16], values: &[f32], options: &ColoringOptions) {
+    if !has_alpha(options) || image.len() != values.len() * NUM_COLOR_CHANNELS {
+        return;
+    }
+    for (pixel, &value) in image.chunks_exact_mut(NUM_COLOR_CHANNELS).zip(values) {
+        let alpha = (pixel_alpha(value, options) * 65535.0).round() as u16;
+        pixel[3] = alpha;
+        if options.premultiplied_alpha {
+            for channel in &mut pixel[..3] {
+                *channel = ((u64::from(*channel) * u64::from(alpha) + 32767) / 65535) as u16;
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use super::*;
+use crate::test_fixtures::full_set_tile;
+use crate::{recolor_values, recolor_values_rgba16, OutputFormat, TileRenderOptions};
+
+fn alphas(image: &[u8]) -> Vec<u8> {
+    image
+        .chunks_exact(NUM_COLOR_CHANNELS)
+        .map(|pixel| pixel[3])
+        .collect()
+}
+
+const VALUES: [f32; 4] = [0.0, 25.0, 100.0, f32::INFINITY];
+
+#[test]
+fn tiles_stay_opaque_by_default() {
+    assert_eq!(
+        alphas(&recolor_values(&VALUES, &ColoringOptions::default())),
+        [255; 4]
+    );
+    // Premultiplying opaque pixels changes nothing.
+    let premultiplied = ColoringOptions {
+        premultiplied_alpha: true,
+        ..ColoringOptions::default()
+    };
+    assert_eq!(
+        recolor_values(&VALUES, &premultiplied),
+        recolor_values(&VALUES, &ColoringOptions::default())
+    );
+}
+
+#[test]
+fn interior_pixels_can_be_transparent() {
+    let options = ColoringOptions {
+        transparent_interior: true,
+        ..ColoringOptions::default()
+    };
+    assert_eq!(
+        alphas(&recolor_values(&VALUES, &options)),
+        [255, 255, 255, 0]
+    );
+
+    let wide = recolor_values_rgba16(&VALUES, &options);
+    let wide_alphas: Vec<u16> = wide.chunks_exact(4).map(|pixel| pixel[3]).collect();
+    assert_eq!(wide_alphas, [u16::MAX, u16::MAX, u16::MAX, 0]);
+}
+
+#[test]
+fn ramps_follow_the_palette_window() {
+    let ramp = |alpha_ramp| ColoringOptions {
+        alpha_ramp: Some(alpha_ramp),
+        ..ColoringOptions::default()
+    };
+    assert_eq!(
+        alphas(&recolor_values(&VALUES, &ramp(AlphaRamp::Value))),
+        [0, 64, 255, 255]
+    );
+    assert_eq!(
+        alphas(&recolor_values(&VALUES, &ramp(AlphaRamp::InverseValue))),
+        [255, 191, 0, 255]
+    );
+
+    // In the distance-estimate method the ramp runs over the brightness:
+    // the inverse ramp keeps the boundary and fades the far exterior.
+    let boundary = ColoringOptions {
+        distance_estimate: true,
+        ..ramp(AlphaRamp::InverseValue)
+    };
+    assert_eq!(
+        alphas(&recolor_values(&[0.0, 0.5, 1.0], &boundary)),
+        [255, 128, 0]
+    );
+}
+
+#[test]
+fn premultiplying_scales_the_colors_by_alpha() {
+    let straight = ColoringOptions {
+        alpha_ramp: Some(AlphaRamp::Value),
+        ..ColoringOptions::default()
+    };
+    let premultiplied = ColoringOptions {
+        premultiplied_alpha: true,
+        ..straight.clone()
+    };
+    let straight = recolor_values(&VALUES, &straight);
+    let premultiplied = recolor_values(&VALUES, &premultiplied);
+    for (straight, premultiplied) in straight
+        .chunks_exact(NUM_COLOR_CHANNELS)
+        .zip(premultiplied.chunks_exact(NUM_COLOR_CHANNELS))
+    {
+        let alpha = f64::from(straight[3]) / 255.0;
+        assert_eq!(premultiplied[3], straight[3]);
+        for channel in 0..3 {
+            let expected = (f64::from(straight[channel]) * alpha).round();
+            assert_eq!(f64::from(premultiplied[channel]), expected);
+        }
+    }
+}
+
+#[test]
+fn renders_apply_the_same_alpha_as_recolors() {
+    let options = ColoringOptions {
+        transparent_interior: true,
+        alpha_ramp: Some(AlphaRamp::InverseValue),
+        premultiplied_alpha: true,
+        ..ColoringOptions::default()
+    };
+    let rendered = TileRenderOptions {
+        coloring: options.clone(),
+        output_format: OutputFormat::Rgba16,
+        ..full_set_tile()
+    }
+    .render_layered();
+
+    assert_eq!(recolor_values(&rendered.values, &options), rendered.image);
+    assert_eq!(
+        recolor_values_rgba16(&rendered.values, &options),
+        rendered.image16
+    );
+    let alphas = alphas(&rendered.image);
+    assert!(alphas.contains(&0) && alphas.iter().any(|&alpha| alpha > 0));
+}
@@ -0,0 +1,186 @@
+//! Named per-pixel value channels.
+//!
+//! A tile's `values` hold the one quantity its coloring method needs. A
+//! render can additionally request any set of `ValueChannel`s, returned as a
+//! planar buffer (`MandelbrotTile::channels`, one plane per channel in
+//! `MandelbrotTile::channel_names` order), so the client can switch coloring
+//! between them — or stack them as layers — by recoloring instead of
+//! re-rendering. `ColoringOptions::value_channel` tells `recolor_tile` which
+//! channel a plane holds.
+//!
+//! Interior pixels are `Infinity` in every channel that has no interior
+//! value. Channels that only the direct f64 path can compute (distance
+//! estimate, atom index, period, stripe average) are `NaN` on perturbation
+//! tiles; both recolor to black like the interior.
+
+use num::complex::Complex64;
+use serde::Deserialize;
+
+use crate::{
+    atom_domain_index_at_c, atom_domain_value, distance_estimate_at_c,
+    distance_estimate_brightness, period_at_c, smoothed_escape_value, stripe_average_at_c,
+    ESCAPE_RADIUS,
+};
+
+#[cfg(test)]
+#[path = "channels_test.rs"]
+mod channels_test;
+
+/// A per-pixel quantity a render can return. Field values mirror the
+/// client's camelCase payload.
+///
+/// Only the escape-derived channels (smooth iteration, iteration, final
+/// modulus and argument) are defined on every tile. The distance estimate,
+/// atom index, period and stripe average need the direct f64 orbit, so they
+/// are `NaN` on perturbation tiles and formula tiles.
+#[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ValueChannel {
+    /// The continuous (smoothed) escape iteration count.
+    SmoothIteration,
+    /// The integer escape iteration count.
+    Iteration,
+    /// `|z|` at escape.
+    FinalModulus,
+    /// `arg z` at escape, in radians (-π, π].
+    FinalArgument,
+    /// Distance-estimate brightness in [0, 1] (see
+    /// `distance_estimate_brightness`).
+    DistanceEstimate,
+    /// The atom-domain index (see `atom_domain_index_at_c`). Defined for
+    /// every pixel, interior included.
+    AtomIndex,
+    /// The period of the attracting cycle (see `period_at_c`): interior
+    /// pixels only; `Infinity` where none was found.
+    Period,
+    /// The stripe average of the escaping orbit in [0, 1] (see
+    /// `stripe_average_at_c`).
+    StripeAverage,
+}
+
+impl ValueChannel {
+    /// The channel's name in `MandelbrotTile::channel_names`, matching its
+    /// payload spelling.
+    pub fn name(self) -> &'static str {
+        match self {
+            ValueChannel::SmoothIteration => "smoothIteration",
+            ValueChannel::Iteration => "iteration",
+            ValueChannel::FinalModulus => "finalModulus",
+            ValueChannel::FinalArgument => "finalArgument",
+            ValueChannel::DistanceEstimate => "distanceEstimate",
+            ValueChannel::AtomIndex => "atomIndex",
+            ValueChannel::Period => "period",
+            ValueChannel::StripeAverage => "stripeAverage",
+        }
+    }
+
+    /// Whether the channel colors over the fixed `0..1` palette domain (see
+    /// `palette_value`) rather than the user's iteration thresholds.
+    pub(crate) fn has_fixed_range(self) -> bool {
+        !matches!(
+            self,
+            ValueChannel::SmoothIteration | ValueChannel::Iteration | ValueChannel::FinalModulus
+        )
+    }
+
+    /// Whether the channel derives from the escape result, which every
+    /// render path produces.
+    fn is_escape_derived(self) -> bool {
+        matches!(
+            self,
+            ValueChannel::SmoothIteration
+                | ValueChannel::Iteration
+                | ValueChannel::FinalModulus
+                | ValueChannel::FinalArgument
+        )
+    }
+
+    /// Maps a cached channel value to the value the palette normalizes:
+    /// the argument becomes a fraction of a turn, and the integer indices
+    /// scatter categorically like the atom-domain mode (`atom_domain_value`).
+    /// Non-finite values pass through and render black.
+    pub(crate) fn palette_value(self, value: f32) -> f64 {
+        let value = f64::from(value);
+        if !value.is_finite() {
+            return value;
+        }
+        match self {
+            ValueChannel::FinalArgument => (value / std::f64::consts::TAU).rem_euclid(1.0),
+            ValueChannel::AtomIndex | ValueChannel::Period => atom_domain_value(value as u32),
+            _ => value,
+        }
+    }
+}
+
+/// An escape-derived channel's value from an escape result (the iteration
+/// count and the `z` it escaped with).
+pub(crate) fn escape_channel_value(
+    channel: ValueChannel,
+    (escape_iterations, z): (u32, Complex64),
+    max_iterations: u32,
+    exponent: u32,
+) -> f32 {
+    if escape_iterations >= max_iterations {
+        return f32::INFINITY;
+    }
+    match channel {
+        ValueChannel::SmoothIteration => {
+            smoothed_escape_value(escape_iterations, z, max_iterations, exponent, true) as f32
+        }
+        ValueChannel::Iteration => escape_iterations as f32,
+        ValueChannel::FinalModulus => z.norm() as f32,
+        ValueChannel::FinalArgument => z.arg() as f32,
+        _ => f32::NAN,
+    }
+}
+
+/// A channel's value at `c` on the direct f64 path. `escape` is the point's
+/// escape result, needed only by the escape-derived channels.
+pub(crate) fn direct_channel_value(
+    channel: ValueChannel,
+    c: Complex64,
+    escape: impl FnOnce() -> (u32, Complex64),
+    max_iterations: u32,
+    exponent: u32,
+    pixel_spacing: f64,
+) -> f32 {
+    let escape_radius_squared = ESCAPE_RADIUS * ESCAPE_RADIUS;
+    match channel {
+        ValueChannel::DistanceEstimate => distance_estimate_brightness(
+            distance_estimate_at_c(c, max_iterations, escape_radius_squared, exponent),
+            pixel_spacing,
+        ) as f32,
+        ValueChannel::AtomIndex => {
+            atom_domain_index_at_c(c, max_iterations, escape_radius_squared) as f32
+        }
+        ValueChannel::Period => period_at_c(c, max_iterations, escape_radius_squared)
+            .map_or(f32::INFINITY, |period| period as f32),
+        ValueChannel::StripeAverage => {
+            stripe_average_at_c(c, max_iterations, escape_radius_squared, exponent)
+                .map_or(f32::INFINITY, |average| average as f32)
+        }
+        _ => escape_channel_value(channel, escape(), max_iterations, exponent),
+    }
+}
+
+/// Fills one plane per channel (row-major, `pixel_count` values each) from
+/// per-pixel escape results, as the perturbation paths produce them.
+pub(crate) fn escape_channel_planes(
+    channels: &[ValueChannel],
+    escape_results: &[(u32, Complex64)],
+    max_iterations: u32,
+    exponent: u32,
+) -> Vec<f32> {
+    channels
+        .iter()
+        .flat_map(|&channel| {
+            escape_results.iter().map(move |&escape| {
+                if channel.is_escape_derived() {
+                    escape_channel_value(channel, escape, max_iterations, exponent)
+                } else {
+                    f32::NAN
+                }
+            })
+        })
+        .collect()
+}
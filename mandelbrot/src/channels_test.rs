@@ -0,0 +1,156 @@
+use super::*;
+use crate::test_fixtures::{self, coloring, deep_tile};
+use crate::{recolor_values, BlendMode, ColoringLayer, ColoringOptions, TileRenderOptions};
+
+/// The full-set tile, returning `channels`.
+fn full_set_tile(channels: Vec<ValueChannel>) -> TileRenderOptions {
+    TileRenderOptions {
+        channels,
+        ..test_fixtures::full_set_tile()
+    }
+}
+
+fn plane(tile: &crate::MandelbrotTile, name: &str) -> Vec<f32> {
+    let pixel_count = 24 * 20;
+    let index = tile
+        .channel_names
+        .iter()
+        .position(|channel| channel == name)
+        .unwrap_or_else(|| panic!("no {name} channel in {:?}", tile.channel_names));
+    tile.channels[index * pixel_count..(index + 1) * pixel_count].to_vec()
+}
+
+#[test]
+fn palette_values_map_the_angular_and_categorical_channels() {
+    let argument = ValueChannel::FinalArgument;
+    assert_eq!(argument.palette_value(0.0), 0.0);
+    assert!((argument.palette_value(std::f32::consts::PI) - 0.5).abs() < 1e-6);
+    assert!((argument.palette_value(-std::f32::consts::FRAC_PI_2) - 0.75).abs() < 1e-6);
+
+    assert_eq!(
+        ValueChannel::Period.palette_value(3.0),
+        atom_domain_value(3)
+    );
+    assert_eq!(ValueChannel::Iteration.palette_value(42.0), 42.0);
+    assert_eq!(
+        ValueChannel::AtomIndex.palette_value(f32::INFINITY),
+        f64::INFINITY
+    );
+    assert!(ValueChannel::DistanceEstimate.has_fixed_range());
+    assert!(!ValueChannel::SmoothIteration.has_fixed_range());
+}
+
+#[test]
+fn channels_come_back_in_request_order_without_duplicates() {
+    let rendered = full_set_tile(vec![
+        ValueChannel::Period,
+        ValueChannel::SmoothIteration,
+        ValueChannel::Period,
+        ValueChannel::Iteration,
+    ])
+    .render_layered();
+    assert_eq!(
+        rendered.channel_names,
+        ["period", "smoothIteration", "iteration"]
+    );
+    assert_eq!(rendered.channels.len(), 3 * 24 * 20);
+
+    // The smooth-iteration channel is the escape-time values.
+    assert_eq!(plane(&rendered, "smoothIteration"), rendered.values);
+    // Only interior pixels have a period; only escaping ones an iteration.
+    let periods = plane(&rendered, "period");
+    let iterations = plane(&rendered, "iteration");
+    assert!(periods.iter().any(|value| value.is_finite()));
+    assert!(iterations.iter().any(|value| value.is_finite()));
+    for (period, iteration) in periods.iter().zip(&iterations) {
+        if iteration.is_finite() {
+            assert!(period.is_infinite());
+            assert_eq!(iteration.fract(), 0.0);
+        }
+    }
+
+    let plain = full_set_tile(Vec::new()).render_layered();
+    assert!(plain.channels.is_empty() && plain.channel_names.is_empty());
+    assert_eq!(plain.image, rendered.image);
+}
+
+#[test]
+fn recoloring_a_channel_matches_the_coloring_method_render() {
+    let rendered = full_set_tile(vec![ValueChannel::DistanceEstimate]).render_layered();
+
+    let mut distance_estimate = full_set_tile(Vec::new());
+    distance_estimate.coloring.distance_estimate = true;
+    let reference = distance_estimate.render_layered();
+
+    let options = ColoringOptions {
+        value_channel: Some(ValueChannel::DistanceEstimate),
+        ..coloring("turbo")
+    };
+    assert_eq!(plane(&rendered, "distanceEstimate"), reference.values);
+    assert_eq!(
+        recolor_values(&plane(&rendered, "distanceEstimate"), &options),
+        reference.image
+    );
+}
+
+#[test]
+fn stripe_averages_cover_the_escaping_pixels() {
+    let rendered = full_set_tile(vec![ValueChannel::StripeAverage]).render_layered();
+    let stripes = plane(&rendered, "stripeAverage");
+
+    assert!(stripes
+        .iter()
+        .any(|value| value.is_finite() && *value != stripes[0]));
+    for (stripe, value) in stripes.iter().zip(&rendered.values) {
+        if value.is_finite() {
+            assert!((0.0..=1.0).contains(stripe), "{stripe}");
+        } else {
+            assert!(stripe.is_infinite());
+        }
+    }
+    assert!(ValueChannel::StripeAverage.has_fixed_range());
+}
+
+#[test]
+fn channel_layers_color_the_shared_channel() {
+    let mut layered = full_set_tile(vec![ValueChannel::FinalArgument]);
+    layered.layers = vec![ColoringLayer {
+        coloring: ColoringOptions {
+            value_channel: Some(ValueChannel::FinalArgument),
+            ..coloring("rainbow")
+        },
+        blend_mode: BlendMode::Luminosity,
+        opacity: Some(0.5),
+    }];
+    let rendered = layered.render_layered();
+
+    assert_eq!(rendered.layer_values, plane(&rendered, "finalArgument"));
+    let plain = full_set_tile(Vec::new()).render_layered();
+    assert_ne!(rendered.image, plain.image);
+
+    // A layer's channel is computed even when the tile does not return it.
+    layered.channels.clear();
+    let unrequested = layered.render_layered();
+    assert!(unrequested.channels.is_empty());
+    assert_eq!(unrequested.layer_values, rendered.layer_values);
+    assert_eq!(unrequested.image, rendered.image);
+}
+
+#[test]
+fn perturbation_tiles_leave_direct_only_channels_undefined() {
+    // Effective zoom 12 + 40 is past the direct-rendering cutoff (see
+    // `test_get_mandelbrot_tile_precise_deep_zoom`).
+    let rendered = TileRenderOptions {
+        channels: vec![
+            ValueChannel::SmoothIteration,
+            ValueChannel::DistanceEstimate,
+        ],
+        ..deep_tile()
+    }
+    .render_layered();
+
+    assert_eq!(plane(&rendered, "smoothIteration"), rendered.values);
+    assert!(plane(&rendered, "distanceEstimate")
+        .iter()
+        .all(|value| value.is_nan()));
+}
@@ -0,0 +1,353 @@
+//! Dithering for the 8-bit output.
+//!
+//! Slow gradients (low color density, wide palette windows) step through
+//! the same 8-bit color for many pixels, which shows as banding. With a
+//! `DitherPattern` set (`ColoringOptions::dither`), each pixel's unquantized
+//! color gets a threshold from a fixed pattern before it is rounded down,
+//! trading the bands for fine, even noise. The threshold depends only on
+//! the pixel's position modulo the pattern size — a power of two that
+//! divides the map's tile size — so neighboring tiles continue each other's
+//! pattern without seams.
+
+use serde::Deserialize;
+
+use crate::{PreciseColor, RgbColor, NUM_COLOR_CHANNELS};
+
+#[cfg(test)]
+#[path = "dither_test.rs"]
+mod dither_test;
+
+/// A dither threshold pattern. Field values mirror the client's camelCase
+/// payload.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum DitherPattern {
+    /// An 8x8 Bayer matrix: cheap and regular, with a faint cross-hatch.
+    Bayer,
+    /// A 64x64 blue-noise mask: no visible structure, only high-frequency
+    /// noise the eye averages away.
+    BlueNoise,
+}
+
+/// Side of the Bayer matrix, in pixels.
+const BAYER_SIZE: usize = 8;
+
+/// Side of the blue-noise mask, in pixels.
+const BLUE_NOISE_SIZE: usize = 64;
+
+impl DitherPattern {
+    /// The threshold in (0, 1) for the pixel at (`column`, `row`).
+    pub(crate) fn threshold(self, column: usize, row: usize) -> f64 {
+        match self {
+            DitherPattern::Bayer => {
+                let rank = bayer_rank(column % BAYER_SIZE, row % BAYER_SIZE);
+                (rank as f64 + 0.5) / (BAYER_SIZE * BAYER_SIZE) as f64
+            }
+            DitherPattern::BlueNoise => {
+                let index = (row % BLUE_NOISE_SIZE) * BLUE_NOISE_SIZE + column % BLUE_NOISE_SIZE;
+                (f64::from(BLUE_NOISE_RANKS[index]) + 0.5) / BLUE_NOISE_RANKS.len() as f64
+            }
+        }
+    }
+}
+
+/// The Bayer matrix entry at (`x`, `y`): the bits of `x ^ y` and `y`
+/// interleaved, least significant first.
+fn bayer_rank(x: usize, y: usize) -> usize {
+    let mut rank = 0;
+    let mut size = 1;
+    while size < BAYER_SIZE {
+        let bit = size.trailing_zeros();
+        rank = (rank << 2) | ((((x ^ y) >> bit) & 1) << 1) | ((y >> bit) & 1);
+        size <<= 1;
+    }
+    rank
+}
+
+/// Blue-noise ranks, row-major: a permutation of `0..BLUE_NOISE_SIZE²`
+/// generated by a void-and-cluster construction (Ulichney 1993) on the
+/// torus, so the mask tiles seamlessly. Pixels are ranked in the order that
+/// keeps every prefix as evenly spread as possible; `dither_test`
+/// regenerates the table from the construction and checks it matches.
+#[rustfmt::skip]
+const BLUE_NOISE_RANKS: [u16; BLUE_NOISE_SIZE * BLUE_NOISE_SIZE] = [
+    2935, 3793, 1193, 3046, 421, 3367, 169, 3102, 512, 1637, 2974, 332, 1849, 2634, 816, 1769,
+    329, 2674, 578, 1802, 1399, 2762, 2277, 182, 3654, 2342, 4004, 3076, 2662, 3389, 137, 1259,
+    2373, 815, 2997, 1431, 2601, 3289, 1263, 3450, 1550, 2587, 4054, 682, 3729, 3225, 960, 2455,
+    2789, 1167, 2999, 461, 3648, 3204, 269, 999, 1830, 401, 862, 1501, 2982, 1138, 3922, 1433,
+    843, 270, 2299, 1491, 3899, 1816, 2428, 1442, 2305, 1059, 2081, 3321, 1434, 3607, 3095, 2389,
+    4044, 895, 3534, 3010, 409, 3763, 1695, 3094, 1996, 449, 1341, 1916, 649, 1703, 3948, 2886,
+    495, 3707, 2138, 394, 4042, 716, 2003, 3862, 991, 3153, 48, 2932, 2009, 391, 1844, 1483,
+    3556, 246, 3794, 2218, 1719, 636, 2260, 3051, 3425, 2790, 3995, 2128, 265, 2427, 559, 2160,
+    1672, 3283, 2812, 647, 2581, 1013, 3655, 723, 4015, 3224, 124, 3870, 683, 1149, 151, 1518,
+    3294, 1247, 1983, 2352, 1028, 3200, 550, 964, 1525, 3343, 2822, 33, 3684, 2438, 981, 1531,
+    3312, 1807, 1131, 2758, 1607, 2309, 198, 2477, 545, 1866, 2284, 1448, 805, 2699, 3851, 3031,
+    741, 1914, 1388, 856, 2888, 1222, 3765, 1624, 49, 1335, 2492, 621, 1704, 3360, 3604, 2713,
+    4006, 1104, 1924, 3735, 87, 3248, 2075, 295, 1682, 2695, 1331, 2426, 2773, 2012, 3497, 2207,
+    494, 2871, 91, 3898, 1515, 2582, 2112, 3974, 2447, 713, 3820, 2178, 1208, 3049, 277, 2220,
+    2639, 164, 3541, 3131, 974, 3693, 3042, 1417, 3359, 3766, 1189, 3267, 3614, 2332, 1172, 100,
+    2239, 3304, 2676, 3915, 114, 3271, 2468, 813, 2064, 3550, 1054, 3752, 2897, 925, 1294, 28,
+    2489, 373, 3459, 861, 1740, 1315, 2534, 2966, 3491, 871, 504, 1755, 3703, 381, 2976, 794,
+    2640, 3651, 1737, 678, 3346, 281, 3494, 1295, 160, 2709, 1582, 884, 3395, 1848, 3581, 776,
+    4014, 1386, 701, 2409, 306, 1820, 614, 2682, 927, 384, 2829, 223, 1741, 551, 3443, 1595,
+    4091, 1088, 363, 2367, 1984, 1519, 502, 3947, 2968, 445, 3181, 1910, 197, 2238, 3110, 1978,
+    3713, 1410, 2210, 2686, 3118, 3943, 553, 1111, 1962, 3769, 2288, 3188, 935, 1575, 4001, 1865,
+    1052, 1393, 2404, 3002, 1196, 1920, 758, 2936, 3627, 1871, 3236, 302, 2573, 536, 2814, 1144,
+    1897, 2989, 2096, 3890, 1279, 3499, 2199, 3961, 1638, 2079, 2452, 3941, 1014, 2032, 2909, 2533,
+    609, 3164, 1759, 3509, 1001, 3686, 2725, 1133, 1736, 2319, 1466, 759, 2671, 3907, 1552, 673,
+    2777, 3230, 542, 1186, 328, 2271, 1598, 3319, 213, 1420, 2901, 47, 3412, 1260, 2499, 248,
+    3119, 3847, 192, 2137, 4055, 2677, 1548, 2228, 985, 522, 2308, 4074, 1319, 1640, 3775, 2329,
+    442, 3458, 3, 1690, 3238, 2817, 1043, 84, 3146, 3559, 763, 1395, 3088, 3675, 337, 972,
+    1934, 2810, 1351, 573, 3007, 201, 2186, 3457, 335, 2756, 4066, 3320, 1180, 437, 3455, 1044,
+    120, 1662, 4089, 1912, 2854, 3671, 818, 2757, 2143, 4053, 746, 1860, 2698, 2126, 630, 3580,
+    1975, 732, 3306, 993, 493, 3576, 38, 3156, 3789, 1459, 3011, 1023, 3504, 2145, 200, 3229,
+    1504, 2711, 1086, 2454, 785, 407, 1941, 2575, 1230, 521, 1793, 2689, 21, 2350, 1509, 3380,
+    3796, 75, 2424, 3950, 1608, 2532, 743, 1414, 3148, 950, 68, 2014, 2485, 1765, 2957, 2318,
+    3625, 840, 2370, 3166, 1011, 34, 1855, 3551, 451, 2545, 1223, 3645, 348, 3814, 3048, 1112,
+    2807, 1490, 2515, 1702, 2953, 1160, 2422, 1795, 358, 2632, 1965, 71, 2690, 736, 3021, 914,
+    3869, 583, 3674, 3080, 1443, 4073, 3331, 1554, 3758, 2970, 2161, 3433, 1153, 3998, 708, 2222,
+    1287, 3265, 848, 2125, 3397, 1213, 3873, 1873, 3733, 2165, 1333, 3608, 628, 3841, 322, 2026,
+    1206, 2734, 237, 1492, 3844, 2571, 1397, 3034, 1046, 1683, 3113, 2248, 988, 1425, 1751, 77,
+    2293, 3513, 317, 3788, 1995, 3376, 740, 3992, 1342, 3448, 686, 3211, 1713, 3957, 1380, 1967,
+    2508, 1750, 2217, 243, 2020, 2738, 663, 2290, 909, 253, 3896, 467, 1666, 2749, 1869, 2993,
+    446, 2636, 1832, 256, 2803, 489, 3085, 165, 2613, 565, 3006, 1601, 3202, 963, 1480, 3293,
+    3812, 1877, 3503, 695, 2063, 3288, 595, 2283, 3919, 152, 3423, 585, 2921, 2478, 3371, 3886,
+    581, 1233, 2667, 698, 1392, 232, 2750, 2101, 946, 2327, 3832, 1235, 2423, 287, 2882, 3473,
+    118, 1181, 3333, 864, 3798, 1155, 131, 3616, 1880, 2769, 1447, 2471, 941, 3243, 148, 3620,
+    1019, 4040, 1418, 3714, 1029, 1926, 2398, 1541, 1080, 3537, 2354, 284, 2720, 2093, 2541, 661,
+    1580, 472, 2472, 2988, 1224, 366, 3727, 886, 1982, 2684, 1460, 1905, 3980, 261, 830, 2076,
+    2948, 1804, 4026, 2142, 2878, 3650, 1626, 3268, 115, 2959, 1614, 393, 3600, 2133, 1109, 655,
+    2670, 4010, 1565, 2796, 2388, 1770, 3172, 2563, 1197, 3365, 742, 3081, 2111, 3835, 654, 1566,
+    1993, 2934, 619, 2546, 3197, 3525, 804, 4027, 2866, 1712, 852, 3689, 1212, 4035, 19, 3000,
+    2195, 3239, 939, 4000, 1710, 2417, 2860, 1630, 3310, 517, 3757, 915, 2206, 1340, 2765, 1549,
+    3301, 987, 207, 3186, 1076, 498, 2504, 1199, 3761, 605, 2015, 2827, 878, 3237, 1839, 3736,
+    2231, 400, 3089, 677, 309, 3501, 1455, 558, 3954, 188, 1937, 3665, 303, 1128, 2612, 2324,
+    3512, 298, 2241, 1667, 0, 1379, 2121, 399, 3284, 88, 1990, 3064, 535, 1782, 3422, 1113,
+    3912, 211, 1415, 2117, 70, 3597, 1139, 245, 2531, 1276, 2992, 42, 3547, 3145, 531, 3751,
+    404, 2570, 3610, 1569, 2349, 3925, 809, 1780, 2652, 3396, 1098, 3932, 2578, 483, 1426, 2925,
+    943, 1761, 1290, 3612, 1946, 2902, 937, 2243, 1625, 2952, 2356, 1300, 1697, 2845, 3364, 1334,
+    802, 3151, 1201, 3863, 2971, 667, 3785, 2719, 1320, 2269, 3935, 1463, 2641, 2344, 796, 2816,
+    1895, 2514, 3672, 2795, 624, 3170, 2200, 4062, 774, 3507, 2339, 1594, 2580, 1825, 1166, 2421,
+    1976, 1337, 739, 1918, 1, 2996, 2094, 3175, 235, 2281, 1510, 149, 1711, 3426, 2386, 25,
+    3357, 3854, 2544, 2183, 1070, 3996, 39, 2706, 3723, 1058, 662, 3208, 4078, 515, 1928, 180,
+    3909, 2666, 1904, 934, 2464, 3339, 1810, 1008, 2503, 727, 3385, 1035, 230, 3787, 1621, 443,
+    3162, 767, 1147, 3378, 1870, 908, 1476, 3039, 1694, 2036, 481, 1066, 4019, 717, 3398, 93,
+    3939, 2960, 3424, 2688, 3720, 1470, 403, 1343, 4024, 902, 3028, 3636, 2167, 827, 4079, 1218,
+    2000, 752, 226, 3250, 537, 1674, 3205, 2027, 386, 3390, 2502, 76, 2123, 958, 3574, 2403,
+    1725, 529, 3587, 204, 2158, 1529, 272, 2937, 3520, 480, 1819, 2800, 3220, 2078, 3468, 1266,
+    3807, 2291, 331, 1587, 2606, 3519, 457, 2728, 196, 3815, 3155, 2737, 280, 2113, 2890, 1581,
+    955, 2188, 307, 1187, 640, 3275, 2598, 3554, 670, 1921, 2473, 519, 1311, 2950, 325, 2603,
+    3083, 1533, 2847, 3760, 1360, 2407, 3637, 734, 1376, 1858, 3868, 1553, 2679, 3127, 1408, 2910,
+    1018, 3315, 1369, 2898, 4069, 590, 3694, 2073, 1436, 3866, 2205, 369, 1407, 894, 150, 2646,
+    527, 1406, 3104, 3846, 130, 2062, 3918, 1184, 2459, 920, 1396, 1903, 3285, 1278, 3823, 2599,
+    552, 3246, 1785, 4092, 2270, 1731, 1027, 2192, 2887, 3440, 1165, 3216, 3790, 1927, 1609, 3578,
+    530, 2250, 1003, 1876, 2685, 275, 1151, 3008, 2264, 2842, 806, 1198, 3749, 360, 718, 3993,
+    63, 2282, 1942, 822, 2618, 1173, 3192, 882, 159, 3087, 1137, 3553, 2440, 4005, 2998, 1728,
+    3538, 2080, 2799, 971, 2387, 1303, 658, 3108, 2153, 3434, 615, 3768, 2381, 846, 168, 1857,
+    3536, 1372, 2536, 874, 2926, 122, 3843, 487, 1517, 326, 1783, 67, 2651, 696, 2366, 1084,
+    3231, 3881, 105, 3432, 793, 3147, 1775, 4034, 144, 3529, 486, 3254, 2340, 1661, 2061, 2540,
+    1555, 3071, 3750, 350, 3435, 1799, 2303, 2782, 1732, 2474, 769, 2873, 1628, 566, 2172, 1021,
+    30, 4071, 675, 1701, 3347, 2917, 1836, 3588, 9, 1760, 2877, 314, 1658, 2963, 3667, 2227,
+    1097, 3019, 181, 3624, 1528, 3374, 2552, 1906, 3631, 2730, 3991, 2251, 1048, 3358, 3953, 178,
+    1387, 1980, 2527, 1559, 3817, 2109, 509, 2480, 990, 1586, 2630, 1930, 116, 3053, 3562, 1164,
+    3384, 633, 1101, 2449, 1465, 44, 3834, 540, 3349, 3976, 2022, 73, 3748, 1219, 3394, 2579,
+    1922, 1192, 2497, 221, 3754, 397, 2301, 1005, 1520, 3927, 2560, 1168, 3388, 541, 1482, 2727,
+    353, 3895, 2336, 592, 2054, 1238, 753, 3070, 982, 1370, 602, 2995, 1512, 411, 2732, 1735,
+    2874, 731, 3057, 431, 1240, 2815, 3596, 1347, 3278, 2147, 3910, 1120, 3704, 912, 505, 2731,
+    249, 1867, 2856, 3966, 2044, 2991, 928, 1600, 1214, 345, 1449, 3158, 2321, 1853, 338, 3140,
+    1521, 3479, 2852, 1994, 1401, 783, 4002, 2742, 516, 3217, 879, 2198, 1843, 4050, 1010, 3287,
+    761, 1948, 1597, 3244, 2846, 250, 4011, 2177, 101, 2463, 3335, 1875, 3567, 2104, 870, 2310,
+    3683, 1145, 4060, 2358, 3298, 6, 1705, 726, 2931, 240, 652, 2880, 1467, 2393, 1724, 3931,
+    2196, 3644, 1390, 797, 462, 3290, 2525, 3697, 2209, 2729, 3641, 1006, 597, 2801, 3917, 764,
+    3020, 484, 890, 3633, 3193, 2595, 1646, 3340, 1254, 2021, 233, 3605, 2752, 78, 2461, 2108,
+    3643, 1285, 2653, 893, 3724, 2439, 1421, 3418, 1707, 3828, 820, 349, 1244, 3850, 3079, 510,
+    3329, 231, 1821, 672, 2001, 1057, 2643, 3964, 1955, 3549, 2538, 1840, 209, 3428, 2987, 1321,
+    849, 2577, 106, 3480, 2294, 1175, 1752, 194, 3045, 750, 1766, 2562, 3511, 1570, 1121, 2411,
+    1977, 3972, 1691, 2268, 340, 1056, 2114, 112, 3685, 2491, 3018, 648, 1348, 3142, 1632, 488,
+    2944, 166, 3983, 426, 1156, 1814, 392, 2776, 1119, 3090, 2082, 2892, 2490, 32, 1651, 1345,
+    2074, 2585, 1506, 2774, 3461, 3742, 2252, 320, 1513, 838, 1269, 3107, 4037, 724, 2045, 362,
+    3308, 1622, 3061, 1908, 2787, 3897, 684, 3387, 1106, 4094, 412, 3253, 155, 2176, 3659, 263,
+    1357, 2696, 108, 1225, 3003, 3867, 642, 2883, 1745, 966, 1563, 3848, 2300, 812, 3929, 1082,
+    3369, 1753, 2278, 3460, 2024, 3165, 3640, 704, 2330, 218, 1462, 3688, 1016, 3295, 2821, 3962,
+    913, 3552, 3121, 156, 901, 1451, 532, 3139, 3427, 2374, 3677, 455, 2179, 2665, 1152, 3808,
+    2371, 607, 4052, 957, 1484, 260, 2168, 2669, 1952, 1464, 2432, 2010, 1277, 2915, 873, 3222,
+    612, 3764, 3342, 2437, 1818, 3487, 1309, 2233, 4083, 413, 3351, 1992, 264, 3476, 2692, 2031,
+    2526, 788, 1452, 2809, 645, 2584, 1012, 1648, 3874, 3316, 577, 1773, 2261, 719, 1936, 354,
+    2296, 588, 1188, 3987, 2443, 2894, 1878, 1143, 2770, 60, 1764, 940, 1398, 3223, 80, 1696,
+    2869, 1253, 2213, 415, 3712, 3027, 1264, 3586, 13, 2973, 938, 3700, 539, 3999, 1781, 2557,
+    2092, 1036, 1617, 523, 824, 2588, 279, 3138, 798, 2724, 1242, 2911, 1024, 1681, 428, 1394,
+    55, 3842, 3063, 291, 1301, 4018, 12, 2042, 2837, 1249, 2596, 4082, 170, 3453, 2658, 1256,
+    3680, 2964, 1987, 1656, 371, 3277, 3857, 765, 2039, 3990, 3043, 2315, 3821, 1879, 3492, 903,
+    3642, 176, 3393, 2635, 1797, 2391, 839, 1655, 3856, 646, 3327, 2739, 1603, 2369, 72, 3533,
+    3054, 351, 2775, 3681, 3179, 2049, 1435, 3584, 1891, 2359, 27, 3730, 2483, 3133, 3799, 2849,
+    3438, 1103, 2151, 3582, 1738, 2346, 3023, 3543, 330, 835, 1969, 3124, 1105, 1502, 3818, 1768,
+    104, 2521, 3421, 800, 2274, 1288, 147, 2629, 1479, 576, 1210, 247, 2820, 524, 2392, 2722,
+    1988, 1446, 3109, 1053, 593, 3485, 3189, 435, 2505, 2216, 1789, 257, 1116, 3276, 756, 1313,
+    2320, 4061, 1892, 1261, 50, 3969, 983, 2834, 579, 1639, 3456, 1422, 634, 1925, 897, 2242,
+    589, 1885, 2668, 760, 3240, 496, 1110, 1478, 2266, 3410, 1568, 441, 2868, 2397, 520, 3201,
+    992, 1423, 440, 2714, 3801, 1808, 3522, 2162, 3218, 3481, 2494, 3639, 1583, 836, 1346, 297,
+    3852, 766, 2132, 3920, 1522, 103, 1974, 2804, 973, 3500, 1291, 3937, 2067, 2884, 3816, 1676,
+    179, 3264, 877, 2276, 2962, 1589, 2444, 187, 3877, 3174, 906, 2135, 4030, 157, 1299, 3279,
+    1573, 3958, 202, 1427, 3706, 1919, 2558, 3933, 603, 2772, 3826, 2166, 3658, 825, 2043, 2781,
+    4032, 1841, 3662, 3103, 1072, 562, 2947, 896, 290, 1708, 978, 2077, 3185, 4087, 2949, 3300,
+    1671, 2576, 241, 2941, 2328, 3687, 1231, 4033, 1530, 135, 3044, 705, 2414, 416, 997, 2626,
+    685, 1468, 2703, 471, 3356, 622, 3673, 2118, 1271, 2537, 319, 2783, 3075, 2326, 3619, 2766,
+    341, 2416, 3152, 961, 2828, 129, 867, 3060, 1837, 89, 998, 1366, 276, 3372, 1236, 208,
+    2376, 697, 2115, 40, 1558, 2462, 4058, 1322, 2402, 3791, 2785, 668, 14, 1828, 2204, 1073,
+    571, 3449, 1182, 1846, 904, 2614, 659, 3115, 2150, 2707, 1868, 3579, 1508, 3391, 1957, 3660,
+    2182, 3446, 1743, 3928, 1991, 1041, 2912, 1715, 710, 3514, 1874, 1130, 1494, 470, 1749, 751,
+    1125, 3515, 1714, 2275, 4065, 2069, 3402, 1252, 3606, 2431, 3261, 2627, 1718, 2942, 3803, 1542,
+    3518, 2955, 1305, 2624, 3566, 2008, 184, 1772, 3116, 434, 1440, 3409, 1258, 2566, 374, 3810,
+    2864, 2272, 4021, 3093, 439, 3355, 1778, 293, 860, 3722, 491, 1045, 2647, 31, 2965, 1234,
+    288, 2841, 977, 167, 2479, 1437, 3420, 271, 2317, 3038, 3901, 617, 3705, 3176, 2450, 3926,
+    2086, 2916, 604, 1239, 376, 1485, 2701, 292, 1620, 782, 1999, 4029, 626, 2211, 924, 1913,
+    453, 1026, 3955, 390, 3210, 965, 2791, 3414, 795, 2134, 3979, 2338, 2980, 3629, 780, 1524,
+    1950, 133, 786, 1596, 3611, 1375, 2247, 3845, 2451, 1352, 3219, 2099, 4048, 664, 1756, 3885,
+    2517, 3590, 1328, 3182, 3776, 425, 2592, 4046, 926, 1543, 206, 2600, 2110, 922, 59, 3303,
+    1477, 183, 3809, 2528, 3532, 2985, 681, 2144, 3875, 2848, 324, 1185, 3132, 22, 2716, 3232,
+    2475, 3400, 2230, 1823, 629, 1371, 3858, 364, 2548, 1089, 1859, 268, 1002, 1721, 3228, 2660,
+    3595, 1339, 2556, 2105, 2764, 94, 2933, 1061, 3465, 1688, 224, 2870, 1298, 2353, 3173, 921,
+    1998, 601, 2280, 1791, 768, 2136, 1191, 3160, 1972, 2831, 1286, 3399, 1645, 2881, 1195, 1893,
+    2659, 936, 3136, 1947, 799, 1746, 3709, 2400, 1042, 3352, 1498, 3663, 2410, 1631, 3849, 707,
+    1416, 134, 1591, 2753, 3666, 2368, 2070, 1606, 3690, 2919, 3299, 639, 3739, 2169, 173, 1150,
+    547, 3183, 3804, 357, 1123, 3982, 730, 2005, 352, 2572, 3783, 834, 1882, 3647, 452, 1590,
+    2929, 4028, 45, 2628, 3628, 2983, 1673, 653, 74, 3781, 2184, 772, 365, 4075, 3477, 599,
+    3711, 2361, 1332, 311, 3311, 1161, 418, 3129, 64, 1901, 561, 2120, 892, 3475, 1217, 2071,
+    2885, 3900, 833, 3096, 1154, 62, 3137, 694, 1270, 127, 1540, 2484, 2855, 1304, 4003, 2383,
+    2928, 1685, 876, 3431, 1863, 2418, 3134, 1588, 3326, 651, 2190, 3114, 119, 2589, 1179, 3361,
+    853, 1412, 3125, 1055, 1500, 259, 3838, 3297, 2420, 1083, 3544, 3120, 2509, 1971, 1419, 2779,
+    408, 1670, 3984, 2838, 2253, 3859, 2030, 1411, 2564, 3981, 2744, 3067, 382, 2602, 203, 3307,
+    567, 1886, 3523, 430, 2568, 4043, 1771, 2710, 3368, 2289, 3936, 898, 1958, 396, 3362, 755,
+    2056, 65, 2313, 2972, 1461, 427, 3670, 1202, 2746, 4008, 1441, 1074, 3495, 1641, 3872, 310,
+    2412, 1850, 3452, 534, 2805, 2372, 945, 1884, 2754, 1584, 432, 1777, 1037, 258, 2255, 875,
+    3255, 2088, 15, 994, 1546, 600, 2793, 3583, 792, 1146, 1642, 3770, 1316, 1815, 4085, 2235,
+    1000, 2518, 1282, 2174, 1535, 942, 507, 3743, 1020, 1899, 497, 3508, 3123, 1684, 2680, 1472,
+    3833, 3241, 1248, 3934, 669, 2705, 2127, 2, 933, 2357, 422, 1959, 2896, 574, 2085, 2768,
+    3780, 222, 2225, 3959, 1954, 3517, 1344, 346, 4017, 729, 3004, 3836, 2708, 3291, 3891, 2939,
+    1177, 3626, 2565, 3167, 3483, 2419, 158, 1805, 3336, 336, 2256, 748, 3270, 2895, 511, 1444,
+    3056, 3774, 195, 3377, 3001, 3557, 2106, 2456, 214, 3036, 1381, 2593, 29, 1099, 3692, 485,
+    976, 2625, 321, 2029, 1034, 3317, 1650, 3819, 2956, 1747, 3178, 3734, 2394, 929, 3233, 1273,
+    733, 2903, 1126, 1627, 810, 162, 2940, 3370, 2312, 1207, 2041, 36, 1356, 687, 1579, 161,
+    1852, 712, 1361, 464, 1915, 832, 4070, 1232, 3035, 2501, 3599, 83, 2037, 1031, 2406, 3632,
+    368, 1618, 2784, 721, 1862, 251, 1326, 3273, 1623, 3865, 2171, 754, 4072, 2351, 2011, 3100,
+    1842, 3506, 1503, 3657, 2832, 375, 2331, 1338, 313, 3560, 803, 227, 1329, 4064, 79, 1677,
+    2500, 3383, 438, 3112, 2664, 3805, 2052, 1536, 482, 3602, 2609, 3354, 2360, 3635, 2152, 2615,
+    3405, 4020, 2286, 3017, 3772, 1505, 2907, 2185, 528, 1686, 1358, 2717, 3945, 1593, 3411, 828,
+    1935, 2307, 4009, 1067, 2642, 3908, 2899, 888, 575, 2786, 1051, 3252, 1497, 334, 2747, 1292,
+    142, 2263, 608, 2470, 1748, 4090, 826, 3413, 2597, 2175, 1527, 2681, 1803, 2844, 2181, 3669,
+    1883, 1355, 3861, 2298, 1243, 591, 2495, 996, 3157, 1824, 821, 1633, 1069, 444, 3141, 947,
+    379, 2797, 1689, 107, 1040, 2493, 308, 3516, 1033, 3882, 3082, 885, 465, 2561, 174, 2863,
+    1226, 3334, 18, 1495, 2154, 518, 1729, 3486, 2025, 3676, 186, 1827, 2920, 3563, 851, 3305,
+    3989, 2889, 918, 3143, 113, 1170, 3024, 1864, 660, 1117, 3878, 3286, 492, 3505, 1078, 398,
+    2700, 844, 2035, 8, 3274, 1812, 3568, 128, 2741, 3973, 274, 3737, 3009, 1938, 3930, 1471,
+    2090, 1204, 638, 3609, 2048, 3226, 706, 2654, 1966, 191, 2156, 3381, 1792, 3738, 1373, 2139,
+    3696, 679, 2457, 3214, 3716, 1251, 2348, 92, 2620, 1227, 2378, 3830, 473, 2236, 1733, 554,
+    2083, 1599, 3784, 1367, 3441, 2007, 2673, 410, 3613, 3062, 54, 2028, 872, 2430, 1532, 3207,
+    266, 3546, 2945, 1585, 4068, 857, 3026, 1453, 2140, 1169, 2433, 625, 2761, 1330, 99, 2507,
+    3708, 2954, 3366, 2375, 1404, 3960, 1776, 1302, 3728, 2930, 618, 1237, 2311, 3171, 714, 3014,
+    383, 1779, 2823, 865, 278, 3015, 3591, 984, 3150, 1577, 690, 2683, 1359, 1025, 3887, 2547,
+    1107, 242, 2436, 503, 2240, 711, 3905, 1403, 2322, 1700, 2520, 1274, 3986, 3040, 671, 3792,
+    1194, 2390, 549, 1075, 2623, 316, 2257, 3756, 544, 3467, 1693, 3314, 2214, 887, 3526, 1653,
+    757, 254, 1856, 910, 474, 2745, 126, 3111, 859, 1571, 2574, 4056, 41, 1017, 1687, 3923,
+    2384, 1171, 3840, 2002, 1612, 2678, 568, 1847, 3914, 370, 3540, 1888, 3033, 3404, 90, 2979,
+    3601, 3221, 1813, 2819, 3569, 1602, 3249, 215, 980, 3747, 459, 2818, 1605, 163, 2265, 1845,
+    3963, 1664, 3168, 2149, 3646, 1385, 2788, 1068, 1900, 3052, 51, 1409, 4093, 339, 2657, 3247,
+    2245, 4007, 2633, 3086, 3653, 1100, 3417, 2016, 2345, 282, 3498, 1909, 2833, 3561, 2616, 234,
+    1514, 3466, 433, 3280, 1132, 4076, 2180, 1349, 2853, 2102, 3199, 841, 304, 2395, 1943, 1511,
+    749, 1297, 4038, 951, 26, 1136, 2469, 2875, 1981, 3169, 777, 3442, 2068, 3710, 931, 2981,
+    85, 790, 3445, 219, 1788, 702, 3348, 405, 3903, 842, 2607, 2047, 1065, 3099, 1800, 466,
+    1015, 1314, 1592, 189, 2146, 2535, 1493, 526, 3777, 3198, 1135, 691, 1481, 458, 1960, 3135,
+    907, 2687, 2116, 725, 2551, 57, 3407, 807, 190, 2487, 1127, 4031, 1636, 3725, 564, 2792,
+    2262, 344, 2586, 2053, 3101, 3806, 1786, 644, 4025, 1539, 2611, 1142, 333, 3196, 1383, 2617,
+    2004, 2859, 1283, 2523, 3985, 2984, 2059, 2445, 1545, 2861, 3535, 611, 3753, 2396, 1384, 3879,
+    2850, 3484, 616, 3800, 1754, 762, 4036, 2811, 952, 1790, 2482, 3030, 2201, 3829, 1228, 2304,
+    4023, 125, 3069, 1709, 3732, 1456, 2401, 3078, 3802, 1516, 546, 2050, 2879, 1281, 930, 3187,
+    3893, 1657, 3403, 555, 1450, 2254, 356, 3382, 1262, 136, 2273, 3652, 1716, 2496, 500, 3577,
+    1578, 3824, 2232, 525, 1560, 1141, 82, 3679, 1211, 301, 2259, 1613, 177, 2943, 720, 2084,
+    11, 1822, 2466, 3266, 1216, 2969, 56, 2237, 1364, 417, 3956, 110, 3401, 881, 3245, 606,
+    1806, 1308, 3623, 1050, 2835, 632, 1963, 1004, 1784, 3598, 2661, 3323, 16, 2519, 3558, 2058,
+    140, 1114, 2986, 866, 3589, 2825, 1049, 2648, 2065, 3105, 594, 2900, 855, 4081, 2203, 1118,
+    3324, 343, 986, 3630, 2740, 3489, 823, 3177, 1979, 3997, 948, 3302, 1911, 3464, 1178, 3649,
+    2608, 3144, 954, 2189, 463, 3531, 1923, 3242, 3678, 2715, 2060, 1325, 1730, 2554, 255, 2867,
+    3447, 2476, 475, 2191, 296, 3206, 3978, 244, 2824, 414, 1317, 801, 3860, 1826, 420, 1438,
+    2694, 3691, 1838, 2481, 216, 1669, 3911, 747, 3493, 1635, 3883, 1323, 1968, 154, 3016, 657,
+    1902, 2559, 3077, 1796, 210, 2141, 2498, 1679, 641, 3059, 2442, 1318, 2712, 395, 2334, 1634,
+    556, 1405, 283, 3968, 1486, 2591, 1095, 299, 1619, 738, 3462, 569, 2923, 3731, 1428, 2089,
+    953, 1572, 2951, 3871, 1762, 2539, 1241, 2164, 3350, 2362, 1680, 3098, 2155, 1140, 2914, 3436,
+    650, 2306, 469, 4012, 1209, 3235, 1949, 69, 2399, 979, 262, 2621, 3272, 1537, 3528, 2733,
+    20, 3977, 1190, 631, 3282, 1296, 3902, 429, 2726, 1489, 139, 3721, 693, 4041, 968, 2913,
+    3853, 3386, 2380, 2906, 1831, 817, 3913, 2990, 2446, 1200, 3084, 2314, 1093, 380, 2408, 3938,
+    7, 3555, 666, 1163, 3318, 773, 1567, 3572, 680, 1038, 3795, 217, 2453, 689, 4088, 1699,
+    969, 3149, 1354, 2103, 2610, 560, 2958, 1429, 3718, 3058, 2097, 3621, 450, 2405, 1220, 880,
+    2208, 1457, 2435, 3745, 1953, 2918, 995, 3330, 3773, 2163, 854, 3159, 1744, 2098, 3269, 109,
+    1887, 699, 1071, 3615, 145, 3353, 2170, 508, 1890, 3740, 171, 4086, 1985, 3510, 778, 1851,
+    3213, 2619, 1961, 2377, 193, 3767, 2743, 46, 3005, 1989, 2638, 3488, 1488, 3325, 121, 2212,
+    2594, 3741, 37, 3309, 916, 3759, 2223, 1062, 2759, 586, 1678, 1148, 770, 3949, 1739, 3664,
+    3338, 476, 3032, 858, 294, 1611, 2295, 35, 1229, 1835, 3573, 2583, 286, 1445, 2644, 1158,
+    2246, 2798, 1562, 2091, 623, 2702, 1389, 3565, 944, 2802, 1496, 863, 1644, 3068, 2763, 1353,
+    1032, 436, 4047, 1377, 3029, 2066, 1094, 1833, 4022, 1365, 514, 932, 1917, 2736, 1280, 3072,
+    377, 1534, 1973, 2794, 1647, 199, 3444, 1801, 318, 4049, 2337, 3126, 2748, 2017, 236, 2891,
+    1087, 1663, 2087, 3527, 2530, 3952, 703, 3092, 2467, 347, 2946, 1134, 2292, 3904, 533, 3415,
+    3786, 389, 3215, 4067, 1245, 3065, 1726, 24, 3203, 2202, 490, 2650, 3344, 143, 548, 3811,
+    2215, 2857, 1698, 868, 3521, 563, 2513, 3212, 372, 2333, 2826, 3880, 305, 3699, 808, 1811,
+    3946, 1047, 3451, 745, 3916, 1267, 2524, 3106, 837, 1350, 3502, 58, 1473, 3259, 2516, 722,
+    2335, 4057, 117, 1324, 2836, 1129, 2013, 3638, 1475, 4016, 637, 1654, 3469, 891, 3047, 1391,
+    1758, 850, 2553, 220, 2364, 829, 3855, 2510, 1215, 3944, 1787, 3668, 1250, 2072, 2542, 1564,
+    3594, 111, 3180, 2555, 273, 1643, 3813, 787, 1551, 3437, 1081, 1616, 3154, 2194, 2522, 3296,
+    610, 2704, 2287, 406, 3022, 2023, 543, 3695, 2193, 2655, 1889, 989, 3876, 513, 1275, 3726,
+    355, 2735, 3190, 1834, 557, 3430, 315, 2645, 819, 1940, 3256, 2691, 66, 1986, 2448, 252,
+    2904, 2038, 3539, 1424, 1907, 3281, 454, 2019, 709, 2927, 205, 2323, 700, 3906, 1009, 2994,
+    771, 1951, 1157, 3864, 2107, 2808, 1289, 2244, 3634, 95, 3025, 2055, 584, 1176, 185, 1454,
+    2033, 3593, 1307, 1757, 2429, 3328, 1115, 1526, 141, 3251, 627, 2922, 2258, 1723, 3470, 1956,
+    1557, 688, 3564, 911, 2347, 3013, 1649, 1203, 3379, 402, 2226, 1063, 3779, 1556, 3545, 715,
+    3994, 1060, 3073, 538, 3755, 1091, 2872, 3656, 1547, 3454, 1064, 3257, 2851, 1809, 3416, 327,
+    2379, 3322, 1507, 656, 3375, 956, 3130, 456, 2672, 1763, 814, 2567, 4039, 3439, 2780, 3822,
+    460, 2961, 86, 4063, 923, 229, 3837, 2905, 1717, 3970, 1174, 3571, 312, 2663, 869, 2977,
+    1183, 2550, 2157, 1469, 3702, 81, 3975, 2267, 2858, 3717, 1413, 3074, 572, 2830, 1205, 2131,
+    2569, 52, 1652, 2425, 2778, 123, 1368, 2316, 359, 2605, 1720, 478, 1430, 61, 2229, 1284,
+    3782, 2755, 419, 2297, 1722, 153, 4077, 1932, 1124, 3771, 3292, 388, 1336, 1817, 949, 2279,
+    1692, 1022, 2543, 3463, 1458, 2721, 1896, 791, 2341, 448, 2511, 1931, 1499, 3341, 98, 3888,
+    3227, 225, 3825, 447, 2697, 1964, 970, 587, 1829, 228, 900, 2512, 1872, 3892, 361, 3345,
+    1363, 3194, 3701, 811, 2046, 3921, 1767, 3332, 919, 3965, 2124, 3617, 2488, 4059, 3097, 582,
+    1665, 962, 3988, 2978, 3570, 2529, 1402, 2839, 643, 2187, 1487, 2343, 2938, 4, 3128, 676,
+    3682, 3262, 2122, 692, 3091, 2285, 385, 3542, 3117, 1374, 3698, 784, 3041, 1096, 2363, 2040,
+    789, 2893, 1861, 1039, 3258, 1378, 3472, 2458, 3161, 1523, 4095, 3263, 175, 2325, 917, 2718,
+    1894, 477, 2249, 1255, 3496, 613, 2649, 239, 3012, 1327, 674, 2806, 1159, 831, 1944, 3575,
+    2549, 2129, 23, 1246, 735, 2034, 342, 3490, 3191, 146, 3940, 905, 3618, 2018, 3889, 2465,
+    1439, 238, 1221, 1854, 479, 3797, 1257, 2095, 967, 2693, 5, 2173, 4084, 506, 3592, 1660,
+    3971, 1306, 3406, 2434, 570, 2967, 285, 3839, 1122, 2751, 2130, 635, 1727, 3622, 1538, 3478,
+    728, 3967, 2840, 300, 1544, 3066, 1077, 2219, 3715, 1898, 102, 3209, 1675, 267, 2924, 1400,
+    367, 3195, 3482, 1576, 2813, 3894, 1030, 1629, 2441, 1272, 1881, 2656, 596, 1561, 1090, 423,
+    3055, 2675, 3942, 3524, 2843, 1659, 3313, 172, 3884, 1610, 3408, 2865, 1774, 1362, 2767, 378,
+    2234, 2631, 53, 1574, 4045, 2224, 1742, 779, 1970, 17, 3419, 1310, 2862, 1079, 3037, 97,
+    2460, 1615, 975, 3337, 2506, 1933, 4080, 499, 1604, 2590, 3474, 2159, 3831, 2413, 3719, 665,
+    2723, 1085, 1939, 2415, 501, 3122, 2221, 3762, 775, 3050, 3548, 289, 3363, 2876, 2197, 3471,
+    1706, 847, 2302, 43, 1092, 737, 2382, 2975, 598, 2006, 1162, 323, 744, 2486, 3429, 1007,
+    1798, 620, 3530, 2051, 889, 2760, 1265, 3585, 2604, 3778, 883, 2385, 3951, 468, 2148, 3746,
+    1293, 3184, 2119, 3827, 10, 845, 3392, 1268, 2908, 781, 1108, 387, 1432, 899, 2057, 3260,
+    1734, 3924, 212, 3603, 959, 1794, 96, 2771, 424, 2100, 1102, 1668, 2365, 1312, 138, 4013,
+    580, 1997, 3373, 1474, 2622, 1945, 4051, 1382, 2637, 3661, 2355, 3234, 3744, 1929, 132, 3163,
+];
+
+/// Quantizes an unquantized color to 8 bits with a dither `threshold` in
+/// (0, 1): the channel rounds up with probability equal to its fraction
+/// over the pattern, so flat areas average to the exact color.
+pub(crate) fn quantize(color: PreciseColor, threshold: f64) -> RgbColor {
+    color.map(|channel| (channel * 255.0 + threshold).floor().clamp(0.0, 255.0) as u8)
+}
+
+/// Dithers unquantized colors (row-major, `image_width` per row) into 8-bit
+/// RGBA.
+pub(crate) fn dithered_rgba8(
+    colors: &[PreciseColor],
+    image_width: usize,
+    pattern: DitherPattern,
+) -> Vec<u8> {
+    let mut image = vec![255; colors.len() * NUM_COLOR_CHANNELS];
+    for (pixel_index, &color) in colors.iter().enumerate() {
+        let (column, row) = (pixel_index % image_width, pixel_index / image_width);
+        let index = pixel_index * NUM_COLOR_CHANNELS;
+        image[index..index + 3].copy_from_slice(&quantize(color, pattern.threshold(column, row)));
+    }
+    image
+}
@@ -0,0 +1,276 @@
+use super::*;
+use crate::test_fixtures;
+use crate::{recolor_values, ColoringOptions, TileRenderOptions};
+
+fn coloring(dither: Option<DitherPattern>) -> ColoringOptions {
+    ColoringOptions {
+        dither,
+        ..test_fixtures::coloring("greys")
+    }
+}
+
+/// The full-set tile at 64x64, square so recolors can infer the dither
+/// rows.
+fn full_set_tile(dither: Option<DitherPattern>, supersampling: u32) -> TileRenderOptions {
+    TileRenderOptions {
+        image_width: 64,
+        image_height: 64,
+        coloring: coloring(dither),
+        supersampling,
+        ..test_fixtures::full_set_tile()
+    }
+}
+
+fn thresholds(pattern: DitherPattern, size: usize) -> Vec<f64> {
+    (0..size * size)
+        .map(|index| pattern.threshold(index % size, index / size))
+        .collect()
+}
+
+#[test]
+fn patterns_rank_every_cell_once() {
+    for (pattern, size) in [
+        (DitherPattern::Bayer, BAYER_SIZE),
+        (DitherPattern::BlueNoise, BLUE_NOISE_SIZE),
+    ] {
+        let mut ranks: Vec<usize> = thresholds(pattern, size)
+            .iter()
+            .map(|threshold| (threshold * (size * size) as f64) as usize)
+            .collect();
+        ranks.sort_unstable();
+        assert!(
+            ranks.iter().copied().eq(0..size * size),
+            "{pattern:?} is not a permutation"
+        );
+    }
+    // The classic 2x2 corner of the Bayer matrix, scaled to 8x8.
+    assert_eq!(
+        [
+            bayer_rank(0, 0),
+            bayer_rank(1, 0),
+            bayer_rank(0, 1),
+            bayer_rank(1, 1)
+        ],
+        [0, 32, 48, 16]
+    );
+}
+
+/// Standard deviation, in pixels, of the Gaussian the void-and-cluster
+/// construction measures clustering with.
+const BLUE_NOISE_SIGMA: f64 = 1.5;
+
+/// The void-and-cluster construction `BLUE_NOISE_RANKS` was generated by.
+fn void_and_cluster_ranks() -> Vec<u16> {
+    const PIXELS: usize = BLUE_NOISE_SIZE * BLUE_NOISE_SIZE;
+    let wrap = |delta: usize| delta.min(BLUE_NOISE_SIZE - delta) as f64;
+    let kernel: Vec<f64> = (0..PIXELS)
+        .map(|index| {
+            let (dx, dy) = (wrap(index % BLUE_NOISE_SIZE), wrap(index / BLUE_NOISE_SIZE));
+            (-(dx * dx + dy * dy) / (2.0 * BLUE_NOISE_SIGMA * BLUE_NOISE_SIGMA)).exp()
+        })
+        .collect();
+
+    // Each pixel's clustering energy: the kernel summed over the set pixels.
+    let mut energy = vec![0.0; PIXELS];
+    let mut set = vec![false; PIXELS];
+    let toggle = |energy: &mut [f64], set: &mut [bool], pixel: usize| {
+        set[pixel] = !set[pixel];
+        let sign = if set[pixel] { 1.0 } else { -1.0 };
+        let (x, y) = (pixel % BLUE_NOISE_SIZE, pixel / BLUE_NOISE_SIZE);
+        for (index, value) in energy.iter_mut().enumerate() {
+            let dx = (index % BLUE_NOISE_SIZE + BLUE_NOISE_SIZE - x) % BLUE_NOISE_SIZE;
+            let dy = (index / BLUE_NOISE_SIZE + BLUE_NOISE_SIZE - y) % BLUE_NOISE_SIZE;
+            *value += sign * kernel[dy * BLUE_NOISE_SIZE + dx];
+        }
+    };
+    // The tightest cluster among set pixels, or the largest void among the
+    // unset ones.
+    let extreme = |energy: &[f64], set: &[bool], want_set: bool| {
+        let candidates = (0..PIXELS).filter(|&pixel| set[pixel] == want_set);
+        if want_set {
+            candidates.max_by(|&a, &b| energy[a].total_cmp(&energy[b]))
+        } else {
+            candidates.min_by(|&a, &b| energy[a].total_cmp(&energy[b]))
+        }
+        .unwrap()
+    };
+
+    // Initial pattern: a tenth of the pixels, picked by a fixed hash, then
+    // relaxed by moving the tightest cluster into the largest void until
+    // that stops changing anything (or, as a guard, after one pass' worth of
+    // moves).
+    let initial = PIXELS / 10;
+    let mut hash: u32 = 0x9e37_79b9;
+    let mut placed = 0;
+    while placed < initial {
+        hash ^= hash << 13;
+        hash ^= hash >> 17;
+        hash ^= hash << 5;
+        let pixel = hash as usize % PIXELS;
+        if !set[pixel] {
+            toggle(&mut energy, &mut set, pixel);
+            placed += 1;
+        }
+    }
+    for _ in 0..PIXELS {
+        let cluster = extreme(&energy, &set, true);
+        toggle(&mut energy, &mut set, cluster);
+        let void = extreme(&energy, &set, false);
+        toggle(&mut energy, &mut set, void);
+        if void == cluster {
+            break;
+        }
+    }
+    let (initial_energy, initial_set) = (energy.clone(), set.clone());
+
+    // Rank the initial pixels by removing the tightest cluster repeatedly,
+    // then the rest by filling the largest void repeatedly.
+    let mut ranks = vec![0; PIXELS];
+    for rank in (0..initial).rev() {
+        let cluster = extreme(&energy, &set, true);
+        toggle(&mut energy, &mut set, cluster);
+        ranks[cluster] = rank as u16;
+    }
+    let (mut energy, mut set) = (initial_energy, initial_set);
+    for rank in initial..PIXELS {
+        let void = extreme(&energy, &set, false);
+        toggle(&mut energy, &mut set, void);
+        ranks[void] = rank as u16;
+    }
+    ranks
+}
+
+#[test]
+fn blue_noise_table_matches_its_construction() {
+    assert_eq!(void_and_cluster_ranks(), BLUE_NOISE_RANKS);
+}
+
+#[test]
+fn blue_noise_is_even_at_every_scale() {
+    // Every 8x8 block of the mask samples the threshold range evenly, which
+    // white noise would not.
+    let thresholds = thresholds(DitherPattern::BlueNoise, BLUE_NOISE_SIZE);
+    for block_row in 0..BLUE_NOISE_SIZE / 8 {
+        for block_column in 0..BLUE_NOISE_SIZE / 8 {
+            let mean = (0..64)
+                .map(|cell| {
+                    let (x, y) = (block_column * 8 + cell % 8, block_row * 8 + cell / 8);
+                    thresholds[y * BLUE_NOISE_SIZE + x]
+                })
+                .sum::<f64>()
+                / 64.0;
+            assert!((mean - 0.5).abs() < 0.06, "block mean {mean}");
+        }
+    }
+}
+
+#[test]
+fn flat_areas_average_to_the_exact_color() {
+    let color = [0.3, 100.25 / 255.0, 1.0];
+    for pattern in [DitherPattern::Bayer, DitherPattern::BlueNoise] {
+        let image = dithered_rgba8(&[color; 64 * 64], 64, pattern);
+        for channel in 0..3 {
+            let mean = image
+                .chunks_exact(NUM_COLOR_CHANNELS)
+                .map(|pixel| f64::from(pixel[channel]))
+                .sum::<f64>()
+                / (64.0 * 64.0);
+            assert!(
+                (mean - color[channel] * 255.0).abs() < 0.01,
+                "{pattern:?} channel {channel}: {mean}"
+            );
+        }
+        assert!(image
+            .chunks_exact(NUM_COLOR_CHANNELS)
+            .all(|pixel| pixel[3] == 255));
+    }
+}
+
+#[test]
+fn tiles_continue_each_others_pattern() {
+    // A 128-pixel row split into two 64-pixel tiles dithers identically.
+    let colors: Vec<PreciseColor> = (0..128 * 4)
+        .map(|index| [(index % 128) as f64 / 128.0 / 255.0 + 0.5; 3])
+        .collect();
+    for pattern in [DitherPattern::Bayer, DitherPattern::BlueNoise] {
+        let whole = dithered_rgba8(&colors, 128, pattern);
+        for half in 0..2 {
+            let tile_colors: Vec<PreciseColor> = colors
+                .chunks_exact(64)
+                .skip(half)
+                .step_by(2)
+                .flatten()
+                .copied()
+                .collect();
+            let tile = dithered_rgba8(&tile_colors, 64, pattern);
+            for row in 0..4 {
+                let whole_row = &whole[(row * 128 + half * 64) * 4..][..64 * 4];
+                assert_eq!(&tile[row * 64 * 4..][..64 * 4], whole_row);
+            }
+        }
+    }
+}
+
+#[test]
+fn renders_and_recolors_dither_alike() {
+    for pattern in [DitherPattern::Bayer, DitherPattern::BlueNoise] {
+        let rendered = full_set_tile(Some(pattern), 0).render_layered();
+        let plain = full_set_tile(None, 0).render_layered();
+        assert_eq!(rendered.values, plain.values);
+        assert_ne!(rendered.image, plain.image);
+        assert_eq!(
+            recolor_values(&rendered.values, &coloring(Some(pattern))),
+            rendered.image
+        );
+
+        // Dithering moves each channel by at most one step.
+        for (&dithered, &truncated) in rendered.image.iter().zip(&plain.image) {
+            assert!(
+                dithered.abs_diff(truncated) <= 1,
+                "{dithered} vs {truncated}"
+            );
+        }
+    }
+}
+
+#[test]
+fn anti_aliased_pixels_keep_their_resampled_color() {
+    let pattern = Some(DitherPattern::BlueNoise);
+    let rendered = full_set_tile(pattern, 2).render_layered();
+    let undithered = full_set_tile(None, 2).render_layered();
+    let recolored = recolor_values(&rendered.values, &coloring(None));
+
+    let pixels = |image: &[u8]| {
+        image
+            .chunks_exact(NUM_COLOR_CHANNELS)
+            .map(<[u8]>::to_vec)
+            .collect::<Vec<_>>()
+    };
+    let (rendered, undithered, recolored) = (
+        pixels(&rendered.image),
+        pixels(&undithered.image),
+        pixels(&recolored),
+    );
+    let resampled = (0..rendered.len()).filter(|&pixel| undithered[pixel] != recolored[pixel]);
+    let mut count = 0;
+    for pixel in resampled {
+        assert_eq!(rendered[pixel], undithered[pixel]);
+        count += 1;
+    }
+    assert!(count > 0);
+}
+
+#[test]
+fn recolors_of_non_square_values_need_a_dither_width() {
+    let dithered = coloring(Some(DitherPattern::Bayer));
+    assert_eq!(dithered.dither_row_length(64 * 64), Ok(64));
+    assert!(dithered.dither_row_length(64 * 32).is_err());
+    assert!(dithered.check_dither_width(64 * 32).is_err());
+    assert!(coloring(None).check_dither_width(64 * 32).is_ok());
+
+    let with_width = ColoringOptions {
+        dither_width: Some(64),
+        ..dithered
+    };
+    assert_eq!(with_width.dither_row_length(64 * 32), Ok(64));
+}
@@ -0,0 +1,196 @@
+//! Image file writers for the high-bit-depth outputs.
+//!
+//! The 8-bit RGBA tiles go straight to a canvas, which encodes them itself.
+//! The 16-bit and floating-point buffers (`recolor_values_rgba16`,
+//! `recolor_values_linear_rgb`) have no browser encoder, so they are written
+//! here: 16-bit PNG for graded sRGB exports, and PFM or uncompressed
+//! single-part OpenEXR for linear-light float exports.
+
+use wasm_bindgen::prelude::*;
+
+#[cfg(test)]
+#[path = "export_test.rs"]
+mod export_test;
+
+/// Checks that a pixel buffer holds exactly `width * height` pixels of
+/// `channels` samples each. Dimensions whose sample count overflows `usize`
+/// (possible on 32-bit wasm) are rejected rather than wrapped.
+fn check_buffer_len(
+    width: u32,
+    height: u32,
+    channels: usize,
+    buffer_len: usize,
+) -> Result<(), String> {
+    let expected = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|pixels| pixels.checked_mul(channels))
+        .ok_or_else(|| format!("{width}x{height}x{channels} samples overflow the address space"))?;
+    if buffer_len == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "pixel buffer holds {buffer_len} samples, expected {expected} for {width}x{height}x{channels}"
+        ))
+    }
+}
+
+/// Encodes 16-bit-per-channel RGBA (as `recolor_values_rgba16` returns it)
+/// as a 16-bit PNG.
+pub fn encode_png_rgba16(width: u32, height: u32, pixels: &[u16]) -> Result<Vec<u8>, String> {
+    check_buffer_len(width, height, 4, pixels.len())?;
+
+    // PNG stores 16-bit samples big-endian.
+    let bytes: Vec<u8> = pixels
+        .iter()
+        .flat_map(|sample| sample.to_be_bytes())
+        .collect();
+
+    let mut encoded = Vec::new();
+    let mut encoder = png::Encoder::new(&mut encoded, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Sixteen);
+    let mut writer = encoder
+        .write_header()
+        .map_err(|error| format!("PNG encoding failed: {error}"))?;
+    writer
+        .write_image_data(&bytes)
+        .map_err(|error| format!("PNG encoding failed: {error}"))?;
+    writer
+        .finish()
+        .map_err(|error| format!("PNG encoding failed: {error}"))?;
+
+    Ok(encoded)
+}
+
+/// Encodes linear RGB floats (as `recolor_values_linear_rgb` returns them,
+/// rows top to bottom) as a little-endian color PFM. PFM stores rows bottom
+/// to top, so they are flipped on the way out.
+pub fn encode_pfm_rgb(width: u32, height: u32, pixels: &[f32]) -> Result<Vec<u8>, String> {
+    check_buffer_len(width, height, 3, pixels.len())?;
+
+    // A negative scale marks little-endian samples.
+    let mut encoded = format!("PF\n{width} {height}\n-1.0\n").into_bytes();
+    encoded.reserve(pixels.len() * 4);
+
+    let row_len = width as usize * 3;
+    if row_len > 0 {
+        for row in pixels.chunks_exact(row_len).rev() {
+            for sample in row {
+                encoded.extend_from_slice(&sample.to_le_bytes());
+            }
+        }
+    }
+
+    Ok(encoded)
+}
+
+/// Appends one OpenEXR header attribute: name, type name, byte size, value.
+fn push_exr_attribute(header: &mut Vec<u8>, name: &str, type_name: &str, value: &[u8]) {
+    header.extend_from_slice(name.as_bytes());
+    header.push(0);
+    header.extend_from_slice(type_name.as_bytes());
+    header.push(0);
+    header.extend_from_slice(&(value.len() as i32).to_le_bytes());
+    header.extend_from_slice(value);
+}
+
+/// Encodes linear RGB floats (as `recolor_values_linear_rgb` returns them)
+/// as an uncompressed, single-part scanline OpenEXR file with 32-bit float
+/// `R`, `G` and `B` channels. Uncompressed keeps the writer dependency-free;
+/// any EXR tool can recompress the result.
+pub fn encode_exr_rgb(width: u32, height: u32, pixels: &[f32]) -> Result<Vec<u8>, String> {
+    check_buffer_len(width, height, 3, pixels.len())?;
+    if width == 0 || height == 0 {
+        return Err("OpenEXR images must be at least 1x1".to_string());
+    }
+    // The header's bounds and each chunk's byte size are signed 32-bit.
+    let too_large = |_| format!("{width}x{height} is too large for an OpenEXR image");
+    let x_max = i32::try_from(width - 1).map_err(too_large)?;
+    let y_max = i32::try_from(height - 1).map_err(too_large)?;
+    let line_data_len = width as usize * CHANNELS.len() * 4;
+    let line_data_len_field = i32::try_from(line_data_len).map_err(too_large)?;
+
+    const PIXEL_TYPE_FLOAT: i32 = 2;
+    // EXR lists channels alphabetically; each value is the channel's index
+    // within an interleaved RGB pixel.
+    const CHANNELS: [(&str, usize); 3] = [("B", 2), ("G", 1), ("R", 0)];
+
+    let mut channel_list = Vec::new();
+    for (name, _) in CHANNELS {
+        channel_list.extend_from_slice(name.as_bytes());
+        channel_list.push(0);
+        channel_list.extend_from_slice(&PIXEL_TYPE_FLOAT.to_le_bytes());
+        // pLinear and three reserved bytes, then x/y sampling.
+        channel_list.extend_from_slice(&[0, 0, 0, 0]);
+        channel_list.extend_from_slice(&1i32.to_le_bytes());
+        channel_list.extend_from_slice(&1i32.to_le_bytes());
+    }
+    channel_list.push(0);
+
+    let window: Vec<u8> = [0, 0, x_max, y_max]
+        .iter()
+        .flat_map(|bound: &i32| bound.to_le_bytes())
+        .collect();
+
+    // Magic number, then version 2 with no flags (single-part scanline).
+    let mut encoded = vec![0x76, 0x2f, 0x31, 0x01, 2, 0, 0, 0];
+    push_exr_attribute(&mut encoded, "channels", "chlist", &channel_list);
+    push_exr_attribute(&mut encoded, "compression", "compression", &[0]);
+    push_exr_attribute(&mut encoded, "dataWindow", "box2i", &window);
+    push_exr_attribute(&mut encoded, "displayWindow", "box2i", &window);
+    push_exr_attribute(&mut encoded, "lineOrder", "lineOrder", &[0]);
+    push_exr_attribute(
+        &mut encoded,
+        "pixelAspectRatio",
+        "float",
+        &1.0f32.to_le_bytes(),
+    );
+    push_exr_attribute(&mut encoded, "screenWindowCenter", "v2f", &[0; 8]);
+    push_exr_attribute(
+        &mut encoded,
+        "screenWindowWidth",
+        "float",
+        &1.0f32.to_le_bytes(),
+    );
+    encoded.push(0);
+
+    // Uncompressed files store one scanline per chunk: the line's y, its
+    // byte size, then each channel's samples for the whole line.
+    let chunk_len = 8 + line_data_len;
+    let table_start = encoded.len();
+    let first_chunk = table_start + height as usize * 8;
+    for y in 0..height as usize {
+        encoded.extend_from_slice(&((first_chunk + y * chunk_len) as u64).to_le_bytes());
+    }
+
+    let row_len = width as usize * 3;
+    for (y, row) in pixels.chunks_exact(row_len).enumerate() {
+        encoded.extend_from_slice(&(y as i32).to_le_bytes());
+        encoded.extend_from_slice(&line_data_len_field.to_le_bytes());
+        for (_, channel) in CHANNELS {
+            for pixel in row.chunks_exact(3) {
+                encoded.extend_from_slice(&pixel[channel].to_le_bytes());
+            }
+        }
+    }
+
+    Ok(encoded)
+}
+
+/// Encodes a `recolor_tile_rgba16` buffer as a 16-bit PNG file.
+#[wasm_bindgen]
+pub fn encode_png16(width: u32, height: u32, pixels: &[u16]) -> Result<Vec<u8>, JsValue> {
+    encode_png_rgba16(width, height, pixels).map_err(|error| JsValue::from_str(&error))
+}
+
+/// Encodes a `recolor_tile_linear_rgb` buffer as a PFM file.
+#[wasm_bindgen]
+pub fn encode_pfm(width: u32, height: u32, pixels: &[f32]) -> Result<Vec<u8>, JsValue> {
+    encode_pfm_rgb(width, height, pixels).map_err(|error| JsValue::from_str(&error))
+}
+
+/// Encodes a `recolor_tile_linear_rgb` buffer as an OpenEXR file.
+#[wasm_bindgen]
+pub fn encode_exr(width: u32, height: u32, pixels: &[f32]) -> Result<Vec<u8>, JsValue> {
+    encode_exr_rgb(width, height, pixels).map_err(|error| JsValue::from_str(&error))
+}
@@ -0,0 +1,90 @@
+use super::*;
+
+#[test]
+fn png16_round_trips_through_decoder() {
+    let pixels: Vec<u16> = (0..2 * 3 * 4).map(|i| (i as u16) * 2731).collect();
+    let encoded = encode_png_rgba16(2, 3, &pixels).unwrap();
+
+    let decoder = png::Decoder::new(encoded.as_slice());
+    let mut reader = decoder.read_info().unwrap();
+    let mut decoded = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut decoded).unwrap();
+
+    assert_eq!((info.width, info.height), (2, 3));
+    assert_eq!(info.bit_depth, png::BitDepth::Sixteen);
+    assert_eq!(info.color_type, png::ColorType::Rgba);
+    let samples: Vec<u16> = decoded
+        .chunks_exact(2)
+        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+        .collect();
+    assert_eq!(samples, pixels);
+}
+
+#[test]
+fn writers_reject_mismatched_buffers() {
+    assert!(encode_png_rgba16(2, 2, &[0; 15]).is_err());
+    assert!(encode_pfm_rgb(2, 2, &[0.0; 13]).is_err());
+    assert!(encode_exr_rgb(2, 2, &[0.0; 11]).is_err());
+    assert!(encode_exr_rgb(0, 0, &[]).is_err());
+}
+
+#[test]
+fn buffer_checks_reject_overflowing_dimensions() {
+    assert!(check_buffer_len(u32::MAX, u32::MAX, usize::MAX, 0).is_err());
+    assert!(check_buffer_len(u32::MAX, 2, usize::MAX / 2, 0).is_err());
+}
+
+#[test]
+fn pfm_stores_rows_bottom_to_top() {
+    // Two rows of one pixel: top red, bottom blue.
+    let pixels = [1.0, 0.0, 0.0, 0.0, 0.0, 0.5];
+    let encoded = encode_pfm_rgb(1, 2, &pixels).unwrap();
+
+    let header = b"PF\n1 2\n-1.0\n";
+    assert_eq!(&encoded[..header.len()], header);
+    let samples: Vec<f32> = encoded[header.len()..]
+        .chunks_exact(4)
+        .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+        .collect();
+    assert_eq!(samples, [0.0, 0.0, 0.5, 1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn exr_layout_is_consistent() {
+    let (width, height) = (3u32, 2u32);
+    let pixels: Vec<f32> = (0..width * height * 3).map(|i| i as f32 * 0.25).collect();
+    let encoded = encode_exr_rgb(width, height, &pixels).unwrap();
+
+    assert_eq!(&encoded[..4], &[0x76, 0x2f, 0x31, 0x01]);
+    assert_eq!(encoded[4], 2, "single-part scanline, version 2");
+
+    let line_data_len = width as usize * 3 * 4;
+    let chunk_len = 8 + line_data_len;
+    // The file ends exactly after the last scanline chunk, so the offset
+    // table can be located from the end.
+    let table_start = encoded.len() - height as usize * (8 + chunk_len);
+    assert_eq!(encoded[table_start - 1], 0, "header terminator");
+
+    for y in 0..height as usize {
+        let entry = &encoded[table_start + y * 8..table_start + y * 8 + 8];
+        let offset = u64::from_le_bytes(entry.try_into().unwrap()) as usize;
+        let chunk = &encoded[offset..offset + chunk_len];
+        assert_eq!(i32::from_le_bytes(chunk[..4].try_into().unwrap()), y as i32);
+        assert_eq!(
+            i32::from_le_bytes(chunk[4..8].try_into().unwrap()) as usize,
+            line_data_len
+        );
+
+        // Channels are planar per line in B, G, R order.
+        let samples: Vec<f32> = chunk[8..]
+            .chunks_exact(4)
+            .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        for x in 0..width as usize {
+            let pixel = &pixels[(y * width as usize + x) * 3..][..3];
+            assert_eq!(samples[x], pixel[2]);
+            assert_eq!(samples[width as usize + x], pixel[1]);
+            assert_eq!(samples[2 * width as usize + x], pixel[0]);
+        }
+    }
+}
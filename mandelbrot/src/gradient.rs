@@ -0,0 +1,303 @@
+//! User-defined gradient palettes.
+//!
+//! A `GradientSpec` is a list of color stops the client sends inline in
+//! `ColoringOptions::gradient`, so palettes can be designed and shared
+//! without a crate change. Each distinct spec is validated and compiled
+//! into a `Palette::Lut` through `palette_from_fn`, then kept in a small
+//! least-recently-used cache of compiled palettes keyed by a content hash
+//! of the spec (see `compiled_palette`), which also holds the re-blended and
+//! CVD-safe variants of named palettes.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{palette_from_fn, ColorVisionDeficiency, InterpolationSpace, Palette, PaletteRef};
+
+#[cfg(test)]
+#[path = "gradient_test.rs"]
+mod gradient_test;
+
+/// An inline gradient: color stops at positions in [0, 1], blended in
+/// `interpolation` space when it is compiled. Field names mirror the
+/// client's camelCase payload.
+#[derive(Clone, Debug, Deserialize, Hash, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GradientSpec {
+    pub stops: Vec<GradientStop>,
+    #[serde(default)]
+    pub interpolation: InterpolationSpace,
+    /// Blend the last stop back into the first across the ends, so the
+    /// gradient wraps seamlessly and color cycles repeat without a seam
+    /// (like the built-in "rainbow" and "sinebow"). Defaults to false: the
+    /// first and last colors extend flat to the ends.
+    #[serde(default)]
+    pub cyclic: bool,
+}
+
+/// One gradient control point.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GradientStop {
+    /// Position in [0, 1]. Stops must be in non-decreasing order; two stops
+    /// at the same position make a hard edge.
+    pub position: f64,
+    /// CSS-style hex color, `#rrggbb` or `#rgb`.
+    pub color: String,
+    /// Easing of the segment from this stop to the next.
+    #[serde(default)]
+    pub easing: GradientEasing,
+}
+
+impl Hash for GradientStop {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.position.to_bits().hash(state);
+        self.color.hash(state);
+        self.easing.hash(state);
+    }
+}
+
+/// Shape of the blend across one segment.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Hash, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum GradientEasing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// Hold the segment's start color up to the next stop.
+    Step,
+}
+
+impl GradientEasing {
+    fn apply(self, fraction: f64) -> f64 {
+        match self {
+            GradientEasing::Linear => fraction,
+            GradientEasing::EaseIn => fraction * fraction,
+            GradientEasing::EaseOut => fraction * (2.0 - fraction),
+            GradientEasing::EaseInOut => fraction * fraction * (3.0 - 2.0 * fraction),
+            GradientEasing::Step => 0.0,
+        }
+    }
+}
+
+/// Parses a `#rrggbb` or `#rgb` hex color into sRGB-encoded channels in
+/// [0, 1].
+fn parse_hex_color(color: &str) -> Result<[f64; 3], String> {
+    let digits = color.strip_prefix('#').unwrap_or(color);
+    let channel = |hex: &str| u8::from_str_radix(hex, 16).ok();
+    let parsed = match digits.len() {
+        6 if digits.bytes().all(|b| b.is_ascii_hexdigit()) => (|| {
+            Some([
+                channel(&digits[0..2])?,
+                channel(&digits[2..4])?,
+                channel(&digits[4..6])?,
+            ])
+        })(),
+        3 if digits.bytes().all(|b| b.is_ascii_hexdigit()) => (|| {
+            Some([
+                channel(&digits[0..1])? * 17,
+                channel(&digits[1..2])? * 17,
+                channel(&digits[2..3])? * 17,
+            ])
+        })(),
+        _ => None,
+    };
+    parsed
+        .map(|rgb| rgb.map(|value| f64::from(value) / 255.0))
+        .ok_or_else(|| format!("invalid color {color:?}, expected #rrggbb or #rgb"))
+}
+
+impl GradientSpec {
+    /// Checks the stops and converts them into blend coordinates, ready to
+    /// sample. Cyclic gradients gain a wrapped copy of the last stop before
+    /// 0 and of the first stop after 1, so the ends blend into each other.
+    fn control_points(&self) -> Result<Vec<(f64, [f64; 3], GradientEasing)>, String> {
+        if self.stops.len() < 2 {
+            return Err(format!(
+                "a gradient needs at least 2 stops, got {}",
+                self.stops.len()
+            ));
+        }
+
+        let mut points = Vec::with_capacity(self.stops.len() + 2);
+        for (index, stop) in self.stops.iter().enumerate() {
+            if !(stop.position.is_finite() && (0.0..=1.0).contains(&stop.position)) {
+                return Err(format!(
+                    "stop {index} position {} is outside [0, 1]",
+                    stop.position
+                ));
+            }
+            if let Some(&(previous, _, _)) = points.last() {
+                if stop.position < previous {
+                    return Err(format!(
+                        "stop {index} position {} is before the previous stop's {previous}",
+                        stop.position
+                    ));
+                }
+            }
+            let rgb =
+                parse_hex_color(&stop.color).map_err(|error| format!("stop {index}: {error}"))?;
+            points.push((
+                stop.position,
+                self.interpolation.coordinates_from_srgb(rgb),
+                stop.easing,
+            ));
+        }
+
+        if self.cyclic {
+            let (first, last) = (points[0], points[points.len() - 1]);
+            points.insert(0, (last.0 - 1.0, last.1, last.2));
+            points.push((first.0 + 1.0, first.1, first.2));
+        }
+        Ok(points)
+    }
+
+    /// Validates the gradient and samples it into a lookup-table palette.
+    pub(crate) fn compile(&self) -> Result<Palette, String> {
+        let points = self.control_points()?;
+        let interpolation = self.interpolation;
+
+        Ok(palette_from_fn(|t| {
+            let next = points.partition_point(|&(position, _, _)| position <= t);
+            if next == 0 {
+                return interpolation.coordinates_to_srgb(points[0].1);
+            }
+            if next == points.len() {
+                return interpolation.coordinates_to_srgb(points[next - 1].1);
+            }
+            let (x0, from, easing) = points[next - 1];
+            let (x1, to, _) = points[next];
+            let fraction = if x1 > x0 {
+                ((t - x0) / (x1 - x0)).clamp(0.0, 1.0)
+            } else {
+                1.0
+            };
+            interpolation.coordinates_to_srgb(interpolation.mix(from, to, easing.apply(fraction)))
+        }))
+    }
+}
+
+/// What a runtime-compiled palette is built from, borrowed from the render's
+/// options. The cache holds only its `content_hash`, so a lookup neither
+/// clones nor compares specs.
+#[derive(Clone, Copy, Hash)]
+pub(crate) struct PaletteKey<'a> {
+    pub(crate) source: PaletteSource<'a>,
+    /// See `ColoringOptions::palette_interpolation`.
+    pub(crate) interpolation: Option<InterpolationSpace>,
+    /// See `ColoringOptions::cvd_remap`.
+    pub(crate) cvd_remap: Option<ColorVisionDeficiency>,
+}
+
+/// The palette a `PaletteKey` derives from.
+#[derive(Clone, Copy, Hash)]
+pub(crate) enum PaletteSource<'a> {
+    /// A built-in or imported palette name (see `get_color_palette`).
+    Named(&'a str),
+    /// An inline gradient.
+    Gradient(&'a GradientSpec),
+}
+
+impl PaletteKey<'_> {
+    /// A 64-bit hash of everything the palette is built from. Two keys
+    /// colliding would share a palette, which at 64 bits is far less likely
+    /// than any other way a render could go wrong.
+    fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Compiled palettes kept for reuse. A gradient editor compiles one palette
+/// per edit, so older entries are evicted least recently used first; every
+/// entry can be recompiled from its key, so eviction only costs time.
+const COMPILED_PALETTE_CAPACITY: usize = 32;
+
+/// A compiled palette with the flags `get_color_palette` returns for it
+/// unreversed: whether to reverse it, and whether it is cyclic.
+type CompiledPalette = (PaletteRef, bool, bool);
+
+/// The compiled-palette cache: each palette by its key's content hash, with
+/// the tick of its last use.
+#[derive(Default)]
+struct CompiledPalettes {
+    entries: HashMap<u64, (CompiledPalette, u64)>,
+    clock: u64,
+}
+
+impl CompiledPalettes {
+    /// The palette cached under `hash`, marked most recently used.
+    fn get(&mut self, hash: u64) -> Option<CompiledPalette> {
+        self.clock += 1;
+        let (compiled, last_used) = self.entries.get_mut(&hash)?;
+        *last_used = self.clock;
+        Some(compiled.clone())
+    }
+
+    /// Caches `compiled` under `hash`, evicting the least recently used
+    /// palette when full.
+    fn insert(&mut self, hash: u64, compiled: CompiledPalette) {
+        if self.entries.len() >= COMPILED_PALETTE_CAPACITY {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(&hash, _)| hash);
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.clock += 1;
+        self.entries.insert(hash, (compiled, self.clock));
+    }
+}
+
+static COMPILED_PALETTES: Lazy<Mutex<CompiledPalettes>> =
+    Lazy::new(|| Mutex::new(CompiledPalettes::default()));
+
+/// The palette `key` describes, from the cache or compiled by `compile`
+/// (outside the lock) and cached. Compilation errors are returned, not
+/// cached.
+pub(crate) fn compiled_palette(
+    key: &PaletteKey,
+    compile: impl FnOnce() -> Result<CompiledPalette, String>,
+) -> Result<CompiledPalette, String> {
+    let hash = key.content_hash();
+    if let Some(compiled) = COMPILED_PALETTES.lock().unwrap().get(hash) {
+        return Ok(compiled);
+    }
+
+    let compiled = compile()?;
+    let mut cache = COMPILED_PALETTES.lock().unwrap();
+    // Another thread may have compiled the same palette meanwhile.
+    if let Some(compiled) = cache.get(hash) {
+        return Ok(compiled);
+    }
+    cache.insert(hash, compiled.clone());
+    Ok(compiled)
+}
+
+/// Number of compiled palettes currently cached.
+#[cfg(test)]
+fn compiled_palette_count() -> usize {
+    COMPILED_PALETTES.lock().unwrap().entries.len()
+}
+
+/// Validates a gradient payload, returning the first problem as the error
+/// message so the client's gradient editor can show it. Renders given an
+/// invalid gradient fall back to the named `palette` instead of failing.
+#[wasm_bindgen]
+pub fn validate_gradient(gradient: JsValue) -> Result<(), JsValue> {
+    let spec: GradientSpec = serde_wasm_bindgen::from_value(gradient).map_err(JsValue::from)?;
+    spec.control_points()
+        .map(|_| ())
+        .map_err(|error| JsValue::from_str(&error))
+}
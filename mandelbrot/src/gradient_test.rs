@@ -0,0 +1,234 @@
+use std::sync::Arc;
+
+use super::*;
+
+fn stop(position: f64, color: &str) -> GradientStop {
+    GradientStop {
+        position,
+        color: color.to_string(),
+        easing: GradientEasing::Linear,
+    }
+}
+
+fn spec(stops: Vec<GradientStop>, interpolation: InterpolationSpace) -> GradientSpec {
+    GradientSpec {
+        stops,
+        interpolation,
+        cyclic: false,
+    }
+}
+
+/// LUT sampling rounds twice (into the table, then between entries), so
+/// colors between table points can land one step off.
+fn assert_near(actual: [u8; 3], expected: [u8; 3]) {
+    assert!(
+        actual
+            .iter()
+            .zip(expected)
+            .all(|(&a, e)| a.abs_diff(e) <= 1),
+        "{actual:?} is not near {expected:?}"
+    );
+}
+
+fn black_to_white(interpolation: InterpolationSpace) -> GradientSpec {
+    spec(vec![stop(0.0, "#000000"), stop(1.0, "#fff")], interpolation)
+}
+
+#[test]
+fn srgb_gradient_hits_its_stops_and_blends_between() {
+    let palette = spec(
+        vec![
+            stop(0.0, "#ff0000"),
+            stop(0.5, "#00ff00"),
+            stop(1.0, "0000ff"),
+        ],
+        InterpolationSpace::Srgb,
+    )
+    .compile()
+    .unwrap();
+
+    assert_eq!(palette.eval_continuous(0.0).as_array(), [255, 0, 0]);
+    assert_near(palette.eval_continuous(0.5).as_array(), [0, 255, 0]);
+    assert_eq!(palette.eval_continuous(1.0).as_array(), [0, 0, 255]);
+    assert_near(palette.eval_continuous(0.25).as_array(), [128, 128, 0]);
+}
+
+#[test]
+fn ends_extend_flat_beyond_the_outer_stops() {
+    let palette = spec(
+        vec![stop(0.25, "#102030"), stop(0.75, "#405060")],
+        InterpolationSpace::Srgb,
+    )
+    .compile()
+    .unwrap();
+
+    assert_eq!(palette.eval_continuous(0.0).as_array(), [0x10, 0x20, 0x30]);
+    assert_eq!(palette.eval_continuous(1.0).as_array(), [0x40, 0x50, 0x60]);
+}
+
+#[test]
+fn linear_rgb_midpoint_is_brighter_than_srgb() {
+    let srgb = black_to_white(InterpolationSpace::Srgb).compile().unwrap();
+    let linear = black_to_white(InterpolationSpace::LinearRgb)
+        .compile()
+        .unwrap();
+
+    let srgb_mid = srgb.eval_continuous(0.5).r;
+    let linear_mid = linear.eval_continuous(0.5).r;
+    assert!(srgb_mid.abs_diff(128) <= 1);
+    // Half linear light encodes to about 188 in sRGB.
+    assert!(linear_mid.abs_diff(188) <= 1, "got {linear_mid}");
+}
+
+#[test]
+fn lch_blend_takes_the_short_way_around_the_hue_circle() {
+    // Red to magenta is a short hop through pinks; the long way would pass
+    // through yellow, green and cyan, with a strong green component.
+    let palette = spec(
+        vec![stop(0.0, "#ff0000"), stop(1.0, "#ff00ff")],
+        InterpolationSpace::Lch,
+    )
+    .compile()
+    .unwrap();
+
+    for i in 0..=10 {
+        let color = palette.eval_continuous(f64::from(i) / 10.0);
+        assert!(color.g < 80, "green leaked in at {i}: {color:?}");
+    }
+}
+
+#[test]
+fn lch_fade_to_grey_keeps_the_hue() {
+    let palette = spec(
+        vec![stop(0.0, "#0000ff"), stop(1.0, "#808080")],
+        InterpolationSpace::Lch,
+    )
+    .compile()
+    .unwrap();
+
+    let mid = palette.eval_continuous(0.5);
+    assert!(mid.b > mid.r && mid.b > mid.g, "hue drifted: {mid:?}");
+}
+
+#[test]
+fn easing_reshapes_segments() {
+    let with_easing = |easing| {
+        let mut gradient = black_to_white(InterpolationSpace::Srgb);
+        gradient.stops[0].easing = easing;
+        gradient.compile().unwrap().eval_continuous(0.5).r
+    };
+
+    let linear = with_easing(GradientEasing::Linear);
+    assert!(with_easing(GradientEasing::EaseIn) < linear);
+    assert!(with_easing(GradientEasing::EaseOut) > linear);
+    assert!(with_easing(GradientEasing::EaseInOut).abs_diff(linear) <= 1);
+    assert_eq!(with_easing(GradientEasing::Step), 0);
+}
+
+#[test]
+fn coincident_stops_make_a_hard_edge() {
+    let palette = spec(
+        vec![
+            stop(0.0, "#000000"),
+            stop(0.5, "#000000"),
+            stop(0.5, "#ffffff"),
+            stop(1.0, "#ffffff"),
+        ],
+        InterpolationSpace::Srgb,
+    )
+    .compile()
+    .unwrap();
+
+    assert_eq!(palette.eval_continuous(0.45).as_array(), [0, 0, 0]);
+    assert_eq!(palette.eval_continuous(0.55).as_array(), [255, 255, 255]);
+}
+
+#[test]
+fn cyclic_gradient_wraps_seamlessly() {
+    let mut gradient = spec(
+        vec![stop(0.2, "#ff0000"), stop(0.8, "#0000ff")],
+        InterpolationSpace::Srgb,
+    );
+    gradient.cyclic = true;
+    let palette = gradient.compile().unwrap();
+
+    // Both ends sit halfway across the wrapped segment from blue (0.8) to
+    // red (1.2).
+    assert_near(palette.eval_continuous(0.0).as_array(), [128, 0, 128]);
+    assert_near(palette.eval_continuous(1.0).as_array(), [128, 0, 128]);
+}
+
+#[test]
+fn invalid_gradients_are_rejected() {
+    let cases = [
+        vec![stop(0.0, "#000000")],
+        vec![stop(0.0, "#000000"), stop(1.5, "#ffffff")],
+        vec![stop(0.0, "#000000"), stop(f64::NAN, "#ffffff")],
+        vec![stop(0.6, "#000000"), stop(0.4, "#ffffff")],
+        vec![stop(0.0, "#000000"), stop(1.0, "white")],
+        vec![stop(0.0, "#00000g"), stop(1.0, "#ffffff")],
+        vec![stop(0.0, "#é0000"), stop(1.0, "#ffffff")],
+    ];
+    for stops in cases {
+        let gradient = spec(stops, InterpolationSpace::Srgb);
+        assert!(gradient.compile().is_err(), "accepted {gradient:?}");
+    }
+}
+
+#[test]
+fn compiled_palettes_are_keyed_by_content_and_bounded() {
+    let compile = |gradient: GradientSpec| {
+        let key = PaletteKey {
+            source: PaletteSource::Gradient(&gradient),
+            interpolation: None,
+            cvd_remap: None,
+        };
+        let (palette, _, _) = compiled_palette(&key, || {
+            Ok((
+                PaletteRef::Shared(Arc::new(gradient.compile()?)),
+                false,
+                false,
+            ))
+        })
+        .unwrap();
+        palette
+    };
+
+    let first = compile(black_to_white(InterpolationSpace::Srgb));
+    let again = compile(black_to_white(InterpolationSpace::Srgb));
+    let other = compile(black_to_white(InterpolationSpace::LinearRgb));
+    assert!(std::ptr::eq(&*first, &*again));
+    assert!(!std::ptr::eq(&*first, &*other));
+
+    // A stream of distinct gradients, as an editor produces, stays bounded.
+    let edits = 2 * COMPILED_PALETTE_CAPACITY;
+    for edit in 1..=edits {
+        let position = edit as f64 / edits as f64;
+        compile(spec(
+            vec![stop(0.0, "#000000"), stop(position, "#ffffff")],
+            InterpolationSpace::Srgb,
+        ));
+    }
+    assert!(compiled_palette_count() <= COMPILED_PALETTE_CAPACITY);
+}
+
+#[test]
+fn oklab_gradient_keeps_its_midpoint_bright() {
+    let srgb = spec(
+        vec![stop(0.0, "#0000ff"), stop(1.0, "#ffff00")],
+        InterpolationSpace::Srgb,
+    )
+    .compile()
+    .unwrap();
+    let oklab = spec(
+        vec![stop(0.0, "#0000ff"), stop(1.0, "#ffff00")],
+        InterpolationSpace::Oklab,
+    )
+    .compile()
+    .unwrap();
+
+    let lightness =
+        |color: colorous::Color| color.as_array().iter().map(|&c| u32::from(c)).sum::<u32>();
+    assert!(lightness(oklab.eval_continuous(0.5)) > lightness(srgb.eval_continuous(0.5)));
+    assert_eq!(oklab.eval_continuous(1.0).as_array(), [255, 255, 0]);
+}
@@ -0,0 +1,466 @@
+//! Escape-value histograms for automatic palette fitting.
+//!
+//! An `EscapeHistogram` accumulates the smoothed escape values of any number
+//! of tiles (streaming: one tile at a time, or per-worker histograms merged
+//! afterwards) and derives the two auto-coloring inputs from them: the
+//! percentile-clipped palette window for `palette_min_iter` /
+//! `palette_max_iter`, and the histogram-equalization table for
+//! `palette_cdf`. Native callers that hold a whole image can use
+//! `fit_coloring_to_values`, which does both in one call.
+//!
+//! The statistics match the web client's on-screen fit: only pixels inside
+//! the view count, each weighted by its distance from the view center, and
+//! each enters the histogram capped at its brightest 8-neighbor so isolated
+//! bright specks cannot hold the palette ceiling up.
+
+use wasm_bindgen::prelude::*;
+
+use crate::ColoringOptions;
+
+#[cfg(test)]
+#[path = "histogram_test.rs"]
+mod histogram_test;
+
+/// Buckets over the histogram's iteration domain; at a typical detected
+/// spread of a few thousand iterations this resolves to a few iterations.
+const HISTOGRAM_BUCKETS: usize = 1024;
+
+/// Entries in an equalization table (see `EscapeHistogram::palette_cdf`):
+/// enough that the lerp between entries is far below a visible color step,
+/// small enough to ride along in every render payload for free.
+pub const PALETTE_CDF_SIZE: usize = 256;
+
+/// Fraction of the escaped-pixel mass the fitted palette ceiling sits above.
+/// Escaped mass is weighted by area, so the fast-escaping exterior dominates
+/// wide views and anything below true-outlier territory would crush the
+/// boundary glow; neighbor capping already removes isolated specks, so the
+/// percentile only trims the residual tail (~14 px on a full screen).
+const CEILING_PERCENTILE: f64 = 0.99999;
+
+/// Fraction of the escaped-pixel mass the fitted palette floor sits below.
+/// The low side is far less skewed than the high side (the exterior mass
+/// sits just above the true minimum), so a much larger fraction is safe.
+const FLOOR_PERCENTILE: f64 = 0.005;
+
+/// An axis-aligned rectangle in tile space, where each tile covers a unit
+/// square with its top-left corner at its integer `(x, y)` position.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewRect {
+    pub x_min: f64,
+    pub x_max: f64,
+    pub y_min: f64,
+    pub y_max: f64,
+}
+
+#[wasm_bindgen]
+impl ViewRect {
+    #[wasm_bindgen(constructor)]
+    pub fn new(x_min: f64, x_max: f64, y_min: f64, y_max: f64) -> ViewRect {
+        ViewRect {
+            x_min,
+            x_max,
+            y_min,
+            y_max,
+        }
+    }
+
+    /// The unit square: a single tile at the origin filling the whole view,
+    /// as when fitting one standalone render.
+    pub fn unit() -> ViewRect {
+        ViewRect::new(0.0, 1.0, 0.0, 1.0)
+    }
+}
+
+/// An inclusive iteration range, as fitted by
+/// `EscapeHistogram::fitted_range`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IterationRange {
+    pub min: i32,
+    pub max: i32,
+}
+
+/// A center-weighted, neighbor-capped histogram of escape values over a
+/// fixed iteration domain. The domain is the escaped-pixel iteration range
+/// the renders report (`MandelbrotTile::min_iter` / `max_iter`, widened
+/// across every tile that will be added), so histograms of different tiles
+/// bucket identically and merge by addition.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct EscapeHistogram {
+    min: f64,
+    max: f64,
+    /// Center weighting makes the counts fractional.
+    buckets: Vec<f64>,
+    escaped_mass: f64,
+    interior_mass: f64,
+}
+
+#[wasm_bindgen]
+impl EscapeHistogram {
+    /// An empty histogram over `[min_iter, max_iter]`. A single-value domain
+    /// is widened by one so the buckets have somewhere to land.
+    #[wasm_bindgen(constructor)]
+    pub fn new(min_iter: i32, max_iter: i32) -> EscapeHistogram {
+        let min = f64::from(min_iter);
+        EscapeHistogram {
+            min,
+            max: f64::from(max_iter).max(min + 1.0),
+            buckets: vec![0.0; HISTOGRAM_BUCKETS],
+            escaped_mass: 0.0,
+            interior_mass: 0.0,
+        }
+    }
+
+    /// Adds a standalone `width` x `height` render (row-major values,
+    /// `Infinity` for interior pixels) that fills the whole view.
+    pub fn add_values(&mut self, values: &[f32], width: usize, height: usize) {
+        self.add_tile(values, width, height, 0.0, 0.0, ViewRect::unit());
+    }
+
+    /// Adds the tile at tile-space position `(tile_x, tile_y)` as seen in
+    /// `view`. Pixels whose centers fall outside the view carry no weight;
+    /// visible pixels weigh `1 / (1 + r²)`, with `r` normalized to 1 at the
+    /// view's mid-edges, so the structure being looked at dominates the fit.
+    /// Neighbors outside the view but inside the tile still cap a pixel, so
+    /// a filament crossing the view edge keeps its value.
+    pub fn add_tile(
+        &mut self,
+        values: &[f32],
+        width: usize,
+        height: usize,
+        tile_x: f64,
+        tile_y: f64,
+        view: ViewRect,
+    ) {
+        if values.len() != width * height {
+            return;
+        }
+        let Some((x0, x1, y0, y1)) = visible_pixels(width, height, tile_x, tile_y, view) else {
+            return;
+        };
+
+        let scale = HISTOGRAM_BUCKETS as f64 / (self.max - self.min);
+        let center_x = (view.x_min + view.x_max) / 2.0;
+        let center_y = (view.y_min + view.y_max) / 2.0;
+        let half_width = nonzero_or_one((view.x_max - view.x_min) / 2.0);
+        let half_height = nonzero_or_one((view.y_max - view.y_min) / 2.0);
+
+        // Squared normalized distance from the view center, per visible
+        // column; the row's term is added in the pixel loop.
+        let x_dist_sq: Vec<f64> = (x0..=x1)
+            .map(|x| {
+                let nx = (tile_x + (x as f64 + 0.5) / width as f64 - center_x) / half_width;
+                nx * nx
+            })
+            .collect();
+
+        // 3-wide horizontal maxima of every row a visible pixel can see, so
+        // the 8-neighbor maximum needs only the rows above and below plus
+        // the two lateral pixels.
+        let first_row = y0.saturating_sub(1);
+        let last_row = (y1 + 1).min(height - 1);
+        let window_rows: Vec<Vec<f32>> = (first_row..=last_row)
+            .map(|y| row_window_max(&values[y * width..(y + 1) * width]))
+            .collect();
+        let window_row = |y: usize| &window_rows[y - first_row];
+
+        for y in y0..=y1 {
+            let above = (y > 0).then(|| window_row(y - 1));
+            let below = (y + 1 < height).then(|| window_row(y + 1));
+            let ny = (tile_y + (y as f64 + 0.5) / height as f64 - center_y) / half_height;
+            let row_weight_term = 1.0 + ny * ny;
+            let row = &values[y * width..(y + 1) * width];
+
+            for x in x0..=x1 {
+                let weight = 1.0 / (row_weight_term + x_dist_sq[x - x0]);
+                let value = row[x];
+                if !value.is_finite() {
+                    self.interior_mass += weight;
+                    continue;
+                }
+                // Only a strict local maximum gets capped, so bail out on
+                // the first neighbor that matches or beats the pixel.
+                let mut capped = value;
+                if above.is_none_or(|above| value > above[x])
+                    && below.is_none_or(|below| value > below[x])
+                {
+                    let mut neighbor_max = f32::NEG_INFINITY;
+                    for neighbors in [above, below].into_iter().flatten() {
+                        neighbor_max = neighbor_max.max(neighbors[x]);
+                    }
+                    if x > 0 {
+                        neighbor_max = neighbor_max.max(row[x - 1]);
+                    }
+                    if x + 1 < width {
+                        neighbor_max = neighbor_max.max(row[x + 1]);
+                    }
+                    capped = value.min(neighbor_max);
+                }
+                let bucket = ((f64::from(capped) - self.min) * scale).floor().max(0.0) as usize;
+                self.buckets[bucket.min(HISTOGRAM_BUCKETS - 1)] += weight;
+                self.escaped_mass += weight;
+            }
+        }
+    }
+
+    /// Total weight of the escaped pixels added so far.
+    #[wasm_bindgen(getter)]
+    pub fn escaped_mass(&self) -> f64 {
+        self.escaped_mass
+    }
+
+    /// Total weight of the interior pixels added so far.
+    #[wasm_bindgen(getter)]
+    pub fn interior_mass(&self) -> f64 {
+        self.interior_mass
+    }
+
+    /// The weighted median escape value (read at the center of the bucket
+    /// where the cumulative mass crosses half), or `None` when nothing has
+    /// escaped.
+    pub fn median(&self) -> Option<f64> {
+        if self.escaped_mass <= 0.0 {
+            return None;
+        }
+        let half = self.escaped_mass / 2.0;
+        let mut cumulative = 0.0;
+        let bucket = self
+            .buckets
+            .iter()
+            .position(|&mass| {
+                cumulative += mass;
+                cumulative >= half
+            })
+            .unwrap_or(HISTOGRAM_BUCKETS - 1);
+        Some(self.bucket_edge(bucket as f64 + 0.5))
+    }
+
+    /// The auto-fit palette window: the domain clipped to the span between
+    /// `FLOOR_PERCENTILE` and `CEILING_PERCENTILE` of the escaped mass,
+    /// rounded outward to whole iterations. Always has `max > min`. Returns
+    /// `None` when nothing has escaped.
+    pub fn fitted_range(&self) -> Option<IterationRange> {
+        if self.escaped_mass <= 0.0 {
+            return None;
+        }
+        let (min, max) = (self.min, self.max);
+        if max - min < 2.0 {
+            return Some(IterationRange {
+                min: min as i32,
+                max: max as i32,
+            });
+        }
+
+        let floor_threshold = self.escaped_mass * FLOOR_PERCENTILE;
+        let ceiling_threshold = self.escaped_mass * CEILING_PERCENTILE;
+        let mut floor = None;
+        let mut ceiling = max;
+        let mut cumulative = 0.0;
+        for (bucket, &mass) in self.buckets.iter().enumerate() {
+            cumulative += mass;
+            if floor.is_none() && cumulative >= floor_threshold {
+                floor = Some(self.bucket_edge(bucket as f64).floor());
+            }
+            if cumulative >= ceiling_threshold {
+                ceiling = self.bucket_edge(bucket as f64 + 1.0).ceil();
+                break;
+            }
+        }
+        let floor = floor.unwrap_or(min);
+
+        Some(IterationRange {
+            min: floor as i32,
+            max: ceiling.max(floor + 1.0) as i32,
+        })
+    }
+
+    /// The histogram-equalization table for the palette window
+    /// `[window_min, window_max]`, ready for `ColoringOptions::palette_cdf`:
+    /// `PALETTE_CDF_SIZE` nondecreasing entries where entry k holds the
+    /// escaped mass (0..1) below the value k/(size-1) of the way through the
+    /// window, so equal palette spans cover equal visible mass.
+    ///
+    /// The table is renormalized between the mass at the window's ends, so a
+    /// manually narrowed window spends the whole palette on that slice of the
+    /// distribution. `strength` (0..1) blends it toward the identity: 0 is
+    /// the linear mapping, 1 fully equalized.
+    ///
+    /// Returns `None` — the exact linear mapping — at strength 0, for an
+    /// empty window, or when the window holds no escaped mass.
+    pub fn palette_cdf(&self, window_min: i32, window_max: i32, strength: f64) -> Option<Vec<f32>> {
+        if strength.is_nan()
+            || strength <= 0.0
+            || window_max <= window_min
+            || self.escaped_mass <= 0.0
+        {
+            return None;
+        }
+        let blend = strength.min(1.0);
+
+        // cumulative[i] is the mass of buckets [0, i).
+        let mut cumulative = Vec::with_capacity(HISTOGRAM_BUCKETS + 1);
+        cumulative.push(0.0);
+        for &mass in &self.buckets {
+            cumulative.push(cumulative[cumulative.len() - 1] + mass);
+        }
+
+        // Cumulative mass below an escape value, interpolated within its
+        // bucket; values outside the domain clamp to its ends.
+        let mass_below = |value: f64| {
+            let position = (value - self.min) / (self.max - self.min) * HISTOGRAM_BUCKETS as f64;
+            if position <= 0.0 {
+                0.0
+            } else if position >= HISTOGRAM_BUCKETS as f64 {
+                cumulative[HISTOGRAM_BUCKETS]
+            } else {
+                let bucket = position as usize;
+                cumulative[bucket] + (position - bucket as f64) * self.buckets[bucket]
+            }
+        };
+
+        let (window_min, window_max) = (f64::from(window_min), f64::from(window_max));
+        let low = mass_below(window_min);
+        let span = mass_below(window_max) - low;
+        if span <= 0.0 {
+            return None;
+        }
+
+        let mut cdf: Vec<f32> = (0..PALETTE_CDF_SIZE)
+            .map(|k| {
+                let linear = k as f64 / (PALETTE_CDF_SIZE - 1) as f64;
+                let value = window_min + (window_max - window_min) * linear;
+                let equalized = (mass_below(value) - low) / span;
+                ((1.0 - blend) * linear + blend * equalized) as f32
+            })
+            .collect();
+        // Monotone by construction; pin the ends exactly so window clamps
+        // land on the palette's true endpoints.
+        cdf[0] = 0.0;
+        cdf[PALETTE_CDF_SIZE - 1] = 1.0;
+
+        Some(cdf)
+    }
+
+    /// Adds `other`'s counts into this histogram (see `merge`).
+    #[wasm_bindgen(js_name = merge)]
+    pub fn merge_js(&mut self, other: &EscapeHistogram) -> Result<(), JsValue> {
+        self.merge(other).map_err(|error| JsValue::from_str(&error))
+    }
+}
+
+impl EscapeHistogram {
+    /// Adds `other`'s counts into this histogram, so tiles can be
+    /// accumulated independently (per worker, per thread) and combined.
+    /// Both must share the same iteration domain.
+    pub fn merge(&mut self, other: &EscapeHistogram) -> Result<(), String> {
+        if (self.min, self.max) != (other.min, other.max) {
+            return Err(format!(
+                "histogram domains differ: [{}, {}] and [{}, {}]",
+                self.min, self.max, other.min, other.max
+            ));
+        }
+        for (bucket, &mass) in self.buckets.iter_mut().zip(&other.buckets) {
+            *bucket += mass;
+        }
+        self.escaped_mass += other.escaped_mass;
+        self.interior_mass += other.interior_mass;
+        Ok(())
+    }
+
+    /// The escape value `buckets` bucket-widths above the domain's floor.
+    fn bucket_edge(&self, buckets: f64) -> f64 {
+        self.min + buckets * (self.max - self.min) / HISTOGRAM_BUCKETS as f64
+    }
+}
+
+fn nonzero_or_one(value: f64) -> f64 {
+    if value == 0.0 {
+        1.0
+    } else {
+        value
+    }
+}
+
+/// The inclusive pixel-index rect `(x0, x1, y0, y1)` of the tile's visible
+/// portion — a pixel counts when its center is inside the view — or `None`
+/// when the tile is entirely outside it.
+fn visible_pixels(
+    width: usize,
+    height: usize,
+    tile_x: f64,
+    tile_y: f64,
+    view: ViewRect,
+) -> Option<(usize, usize, usize, usize)> {
+    let first =
+        |edge: f64, origin: f64, size: usize| ((edge - origin) * size as f64 - 0.5).ceil().max(0.0);
+    let last = |edge: f64, origin: f64, size: usize| {
+        ((edge - origin) * size as f64 - 0.5)
+            .floor()
+            .min(size as f64 - 1.0)
+    };
+    let x0 = first(view.x_min, tile_x, width);
+    let x1 = last(view.x_max, tile_x, width);
+    let y0 = first(view.y_min, tile_y, height);
+    let y1 = last(view.y_max, tile_y, height);
+
+    (x0 <= x1 && y0 <= y1).then_some((x0 as usize, x1 as usize, y0 as usize, y1 as usize))
+}
+
+/// Each value's maximum with its left and right neighbors, clamped to the
+/// row.
+fn row_window_max(row: &[f32]) -> Vec<f32> {
+    (0..row.len())
+        .map(|x| {
+            let mut window_max = row[x];
+            if x > 0 {
+                window_max = window_max.max(row[x - 1]);
+            }
+            if x + 1 < row.len() {
+                window_max = window_max.max(row[x + 1]);
+            }
+            window_max
+        })
+        .collect()
+}
+
+/// Fits `options`' palette window and equalization table to a standalone
+/// `width` x `height` render's escape values, the way the web client
+/// auto-colors the map: `palette_min_iter` / `palette_max_iter` become the
+/// percentile-clipped range, and `palette_cdf` the equalization table at
+/// `equalize_strength` (0 keeps the linear mapping; see
+/// `EscapeHistogram::palette_cdf`). Leaves the options untouched when no
+/// pixel escaped, and in the fixed-palette methods (distance estimate, atom
+/// domains, fixed-range value channels), whose values are not iteration
+/// counts.
+pub fn fit_coloring_to_values(
+    options: &mut ColoringOptions,
+    values: &[f32],
+    width: usize,
+    height: usize,
+    equalize_strength: f64,
+) {
+    if options.fixed_palette() {
+        return;
+    }
+    let Some((min, max)) = values.iter().filter(|value| value.is_finite()).fold(
+        None,
+        |range: Option<(f32, f32)>, &value| {
+            Some(range.map_or((value, value), |(min, max)| {
+                (min.min(value), max.max(value))
+            }))
+        },
+    ) else {
+        return;
+    };
+
+    let mut histogram = EscapeHistogram::new(min.floor() as i32, max.ceil() as i32);
+    histogram.add_values(values, width, height);
+    let Some(range) = histogram.fitted_range() else {
+        return;
+    };
+    options.palette_min_iter = range.min;
+    options.palette_max_iter = range.max;
+    options.palette_cdf = histogram.palette_cdf(range.min, range.max, equalize_strength);
+}
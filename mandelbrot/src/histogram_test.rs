@@ -0,0 +1,164 @@
+use super::*;
+
+/// A `width` x `height` render whose values climb evenly from `min` at the
+/// top-left to `max` at the bottom-right, row by row.
+fn ramp(width: usize, height: usize, min: f32, max: f32) -> Vec<f32> {
+    let last = (width * height - 1) as f32;
+    (0..width * height)
+        .map(|index| min + (max - min) * index as f32 / last)
+        .collect()
+}
+
+fn coloring_options() -> ColoringOptions {
+    ColoringOptions {
+        palette_max_iter: 1,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn even_distribution_fits_the_domain_and_equalizes_to_identity() {
+    let mut histogram = EscapeHistogram::new(0, 1000);
+    histogram.add_values(&ramp(64, 64, 0.0, 1000.0), 64, 64);
+
+    let range = histogram.fitted_range().unwrap();
+    assert!(range.min <= 10, "{range:?}");
+    assert!(range.max >= 990, "{range:?}");
+
+    // Center weighting bends the table toward the middle, but an even ramp
+    // stays close to the identity and hits both ends exactly.
+    let cdf = histogram.palette_cdf(0, 1000, 1.0).unwrap();
+    assert_eq!(cdf.len(), PALETTE_CDF_SIZE);
+    assert_eq!((cdf[0], cdf[PALETTE_CDF_SIZE - 1]), (0.0, 1.0));
+    assert!(cdf.windows(2).all(|pair| pair[0] <= pair[1]));
+    let mid = cdf[PALETTE_CDF_SIZE / 2];
+    assert!((mid - 0.5).abs() < 0.1, "{mid}");
+}
+
+#[test]
+fn isolated_specks_do_not_hold_the_ceiling_up() {
+    let (width, height) = (32, 32);
+    let mut values = ramp(width, height, 10.0, 50.0);
+    values[16 * width + 16] = 5000.0;
+
+    let mut histogram = EscapeHistogram::new(10, 5000);
+    histogram.add_values(&values, width, height);
+    let range = histogram.fitted_range().unwrap();
+    assert!(range.max < 100, "{range:?}");
+
+    // A connected bright line keeps its value.
+    for x in 0..width {
+        values[16 * width + x] = 5000.0;
+    }
+    let mut histogram = EscapeHistogram::new(10, 5000);
+    histogram.add_values(&values, width, height);
+    let range = histogram.fitted_range().unwrap();
+    assert!(range.max >= 5000, "{range:?}");
+}
+
+#[test]
+fn interior_pixels_only_count_as_interior_mass() {
+    let mut values = ramp(8, 8, 0.0, 100.0);
+    values[..32].fill(f32::INFINITY);
+
+    let mut histogram = EscapeHistogram::new(0, 100);
+    histogram.add_values(&values, 8, 8);
+    assert!(histogram.interior_mass() > 0.0);
+    assert!(histogram.escaped_mass() > 0.0);
+    let median = histogram.median().unwrap();
+    assert!((60.0..90.0).contains(&median), "{median}");
+
+    let mut interior = EscapeHistogram::new(0, 100);
+    interior.add_values(&[f32::INFINITY; 64], 8, 8);
+    assert_eq!(interior.fitted_range(), None);
+    assert_eq!(interior.median(), None);
+    assert_eq!(interior.palette_cdf(0, 100, 1.0), None);
+}
+
+#[test]
+fn merged_histograms_match_one_streamed_histogram() {
+    let view = ViewRect::new(0.25, 1.75, 0.0, 1.0);
+    let left = ramp(16, 16, 0.0, 300.0);
+    let right = ramp(16, 16, 200.0, 900.0);
+
+    let mut streamed = EscapeHistogram::new(0, 900);
+    streamed.add_tile(&left, 16, 16, 0.0, 0.0, view);
+    streamed.add_tile(&right, 16, 16, 1.0, 0.0, view);
+
+    let mut merged = EscapeHistogram::new(0, 900);
+    let mut other = EscapeHistogram::new(0, 900);
+    merged.add_tile(&left, 16, 16, 0.0, 0.0, view);
+    other.add_tile(&right, 16, 16, 1.0, 0.0, view);
+    merged.merge(&other).unwrap();
+
+    assert_eq!(merged.fitted_range(), streamed.fitted_range());
+    assert_eq!(
+        merged.palette_cdf(0, 900, 1.0),
+        streamed.palette_cdf(0, 900, 1.0)
+    );
+    assert!(merged.merge(&EscapeHistogram::new(0, 901)).is_err());
+}
+
+#[test]
+fn only_visible_pixels_count() {
+    let values = ramp(16, 16, 0.0, 100.0);
+    let mut histogram = EscapeHistogram::new(0, 100);
+    histogram.add_tile(&values, 16, 16, 3.0, 0.0, ViewRect::unit());
+    assert_eq!(histogram.escaped_mass(), 0.0);
+
+    // Only the top half of the tile is in view, so only its low values
+    // count.
+    histogram.add_tile(&values, 16, 16, 0.0, 0.5, ViewRect::unit());
+    let range = histogram.fitted_range().unwrap();
+    assert!(range.max <= 51, "{range:?}");
+}
+
+#[test]
+fn strength_blends_the_table_toward_linear() {
+    // Most of the mass sits low in the window, so the equalized table rises
+    // steeply at first.
+    let values: Vec<f32> = ramp(32, 32, 0.0, 1.0)
+        .iter()
+        .map(|t| 1000.0 * t * t * t)
+        .collect();
+    let mut histogram = EscapeHistogram::new(0, 1000);
+    histogram.add_values(&values, 32, 32);
+
+    assert_eq!(histogram.palette_cdf(0, 1000, 0.0), None);
+    assert_eq!(histogram.palette_cdf(1000, 0, 1.0), None);
+    let full = histogram.palette_cdf(0, 1000, 1.0).unwrap();
+    let half = histogram.palette_cdf(0, 1000, 0.5).unwrap();
+    let quarter = PALETTE_CDF_SIZE / 4;
+    let linear = quarter as f32 / (PALETTE_CDF_SIZE - 1) as f32;
+    assert!(full[quarter] > 0.5, "{}", full[quarter]);
+    assert!((half[quarter] - (full[quarter] + linear) / 2.0).abs() < 1e-6);
+}
+
+#[test]
+fn fitting_fills_in_the_coloring_options() {
+    let values = ramp(32, 32, 20.0, 400.0);
+    let mut options = coloring_options();
+    fit_coloring_to_values(&mut options, &values, 32, 32, 1.0);
+    assert!(options.palette_min_iter >= 20 && options.palette_min_iter < 30);
+    assert!(options.palette_max_iter > 390 && options.palette_max_iter <= 400);
+    assert_eq!(
+        options.palette_cdf.as_ref().map(Vec::len),
+        Some(PALETTE_CDF_SIZE)
+    );
+
+    let mut linear = coloring_options();
+    fit_coloring_to_values(&mut linear, &values, 32, 32, 0.0);
+    assert_eq!(linear.palette_min_iter, options.palette_min_iter);
+    assert_eq!(linear.palette_cdf, None);
+
+    // Nothing to fit: all interior, or values that are not iteration counts.
+    let mut untouched = coloring_options();
+    fit_coloring_to_values(&mut untouched, &[f32::INFINITY; 4], 2, 2, 1.0);
+    untouched.distance_estimate = true;
+    fit_coloring_to_values(&mut untouched, &values, 32, 32, 1.0);
+    assert_eq!(
+        (untouched.palette_min_iter, untouched.palette_max_iter),
+        (0, 1)
+    );
+    assert_eq!(untouched.palette_cdf, None);
+}
@@ -0,0 +1,346 @@
+//! Multi-layer coloring.
+//!
+//! A tile can stack extra coloring layers over its base coloring: each
+//! `ColoringLayer` carries its own `ColoringOptions` (so its own coloring
+//! method, palette and window — e.g. distance-estimate brightness in greys
+//! over smooth escape time in a hue palette) and is composited onto the
+//! layers below it with a `BlendMode` and opacity. Compositing runs on the
+//! finished colors, after `color_from_smoothed_value`, in sRGB-encoded
+//! [0, 1] channels like the W3C compositing modes it follows.
+//!
+//! A layer's values are the base render's, or the tile's shared channel
+//! plane when its coloring names a `value_channel`; only a layer with its
+//! own distance-estimate or atom-domain method renders its values again
+//! (see `TileRenderOptions::layers`). They are cached next to the base values so the stack
+//! recolors without re-rendering (`recolor_tile_layers`). Non-finite layer
+//! values — the interior in the escape-time and distance-estimate methods —
+//! leave the layer transparent there, so the layers below show through.
+
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    alpha, dither, for_each_precise_color, recolor_values_opaque, ColoringOptions, PreciseColor,
+    NUM_COLOR_CHANNELS,
+};
+
+#[cfg(test)]
+#[path = "layers_test.rs"]
+mod layers_test;
+
+/// How a layer's color combines with the color below it. The formulas are
+/// the W3C compositing spec's, with the layer as the source and the stack
+/// below as the backdrop.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum BlendMode {
+    /// The layer's color replaces the backdrop's.
+    #[default]
+    Normal,
+    /// Darkens: channels multiply, so white is neutral.
+    Multiply,
+    /// Lightens: inverted channels multiply, so black is neutral.
+    Screen,
+    /// Multiplies dark backdrop channels and screens light ones, raising
+    /// the backdrop's contrast with the layer.
+    Overlay,
+    /// A gentler overlay that darkens or lightens by the layer's channels.
+    SoftLight,
+    /// The layer's hue with the backdrop's saturation and luminosity.
+    Hue,
+    /// The layer's luminosity with the backdrop's hue and saturation — e.g.
+    /// distance-estimate shading over an escape-time palette.
+    Luminosity,
+}
+
+/// One coloring layer stacked over a tile's base coloring.
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColoringLayer {
+    /// The layer's coloring, including the method (escape time, distance
+    /// estimate, atom domains) its value channel is rendered with.
+    pub coloring: ColoringOptions,
+    #[serde(default)]
+    pub blend_mode: BlendMode,
+    /// Opacity in [0, 1]. Defaults to `None`, fully opaque.
+    #[serde(default)]
+    pub opacity: Option<f64>,
+}
+
+impl ColoringLayer {
+    /// The opacity, sanitized to [0, 1]; non-finite values drop to 0 rather
+    /// than letting a NaN into every pixel.
+    fn effective_opacity(&self) -> f64 {
+        match self.opacity {
+            Some(opacity) if opacity.is_finite() => opacity.clamp(0.0, 1.0),
+            Some(_) => 0.0,
+            None => 1.0,
+        }
+    }
+}
+
+/// A base coloring plus the layers stacked over it, as `recolor_tile_layers`
+/// takes them. Field names mirror the client's camelCase payload.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayeredColoringOptions {
+    pub coloring: ColoringOptions,
+    #[serde(default)]
+    pub layers: Vec<ColoringLayer>,
+}
+
+/// W3C `Lum`: the luminosity the non-separable modes preserve.
+fn luminosity(color: PreciseColor) -> f64 {
+    0.3 * color[0] + 0.59 * color[1] + 0.11 * color[2]
+}
+
+/// W3C `SetLum`: shifts `color` to luminosity `target`, then pulls any
+/// channel outside [0, 1] back toward the luminosity without changing it.
+fn with_luminosity(color: PreciseColor, target: f64) -> PreciseColor {
+    let delta = target - luminosity(color);
+    let shifted = color.map(|channel| channel + delta);
+
+    let lum = luminosity(shifted);
+    let low = shifted.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = shifted.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    shifted.map(|channel| {
+        let mut channel = channel;
+        if low < 0.0 {
+            channel = lum + (channel - lum) * lum / (lum - low);
+        }
+        if high > 1.0 {
+            channel = lum + (channel - lum) * (1.0 - lum) / (high - lum);
+        }
+        channel
+    })
+}
+
+/// W3C `Sat`: the spread between the largest and smallest channel.
+fn saturation(color: PreciseColor) -> f64 {
+    let low = color.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = color.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    high - low
+}
+
+/// W3C `SetSat`: rescales `color`'s channels to spread `target` while
+/// keeping their order (and so the hue).
+fn with_saturation(color: PreciseColor, target: f64) -> PreciseColor {
+    let low = color.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = color.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if high <= low {
+        return [0.0; 3];
+    }
+    color.map(|channel| (channel - low) * target / (high - low))
+}
+
+/// The W3C soft-light blend of one channel.
+fn soft_light(backdrop: f64, source: f64) -> f64 {
+    if source <= 0.5 {
+        backdrop - (1.0 - 2.0 * source) * backdrop * (1.0 - backdrop)
+    } else {
+        let lifted = if backdrop <= 0.25 {
+            ((16.0 * backdrop - 12.0) * backdrop + 4.0) * backdrop
+        } else {
+            backdrop.sqrt()
+        };
+        backdrop + (2.0 * source - 1.0) * (lifted - backdrop)
+    }
+}
+
+impl BlendMode {
+    /// The fully opaque blend of `source` over `backdrop`.
+    fn blend(self, backdrop: PreciseColor, source: PreciseColor) -> PreciseColor {
+        let separable = |channel: fn(f64, f64) -> f64| {
+            [0, 1, 2].map(|index| channel(backdrop[index], source[index]))
+        };
+        match self {
+            BlendMode::Normal => source,
+            BlendMode::Multiply => separable(|b, s| b * s),
+            BlendMode::Screen => separable(|b, s| b + s - b * s),
+            BlendMode::Overlay => separable(|b, s| {
+                if b <= 0.5 {
+                    2.0 * b * s
+                } else {
+                    let b = 2.0 * b - 1.0;
+                    b + s - b * s
+                }
+            }),
+            BlendMode::SoftLight => separable(soft_light),
+            BlendMode::Hue => with_luminosity(
+                with_saturation(source, saturation(backdrop)),
+                luminosity(backdrop),
+            ),
+            BlendMode::Luminosity => with_luminosity(backdrop, luminosity(source)),
+        }
+    }
+}
+
+/// `source` composited over `backdrop` with `mode` at `opacity`.
+pub(crate) fn composite(
+    backdrop: PreciseColor,
+    source: PreciseColor,
+    mode: BlendMode,
+    opacity: f64,
+) -> PreciseColor {
+    let blended = mode.blend(backdrop, source);
+    [0, 1, 2].map(|index| {
+        (backdrop[index] + (blended[index] - backdrop[index]) * opacity).clamp(0.0, 1.0)
+    })
+}
+
+/// Composites a layer's 8-bit RGBA `layer_image` onto `image` in place.
+/// `layer_values` are the layer's cached values; pixels where they are not
+/// finite are transparent.
+pub(crate) fn composite_rgba8(
+    image: &mut [u8],
+    layer_image: &[u8],
+    layer_values: &[f32],
+    layer: &ColoringLayer,
+) {
+    let opacity = layer.effective_opacity();
+    let channels = |pixel: &[u8]| [0, 1, 2].map(|index| f64::from(pixel[index]) / 255.0);
+
+    for ((pixel, layer_pixel), value) in image
+        .chunks_exact_mut(NUM_COLOR_CHANNELS)
+        .zip(layer_image.chunks_exact(NUM_COLOR_CHANNELS))
+        .zip(layer_values)
+    {
+        if !value.is_finite() {
+            continue;
+        }
+        let color = composite(
+            channels(pixel),
+            channels(layer_pixel),
+            layer.blend_mode,
+            opacity,
+        );
+        for (channel, value) in pixel.iter_mut().zip(color) {
+            *channel = (value * 255.0).round() as u8;
+        }
+    }
+}
+
+/// The layer planes in `layer_values` (one `pixel_count`-long plane per
+/// layer, in layer order), or `None` when the buffer does not hold exactly
+/// one plane per layer.
+pub(crate) fn layer_planes<'a>(
+    layer_values: &'a [f32],
+    pixel_count: usize,
+    layers: &[ColoringLayer],
+) -> Option<Vec<&'a [f32]>> {
+    if layer_values.len() != pixel_count * layers.len() {
+        return None;
+    }
+    Some(if pixel_count == 0 {
+        vec![&[][..]; layers.len()]
+    } else {
+        layer_values.chunks_exact(pixel_count).collect()
+    })
+}
+
+/// Typed core of `recolor_tile_layers`: recolors the base `values` and each
+/// layer's plane of `layer_values`, compositing the layers in order. A
+/// `layer_values` buffer that does not hold one plane per layer is ignored,
+/// leaving the base coloring. The base coloring's `dither` and alpha
+/// settings apply to the composite.
+pub fn recolor_layered_values(
+    values: &[f32],
+    layer_values: &[f32],
+    options: &LayeredColoringOptions,
+) -> Vec<u8> {
+    let mut image = layered_rgba8(values, layer_values, &options.coloring, &options.layers);
+    alpha::apply_alpha_rgba8(&mut image, values, &options.coloring);
+    image
+}
+
+/// `recolor_layered_values` before the base coloring's alpha: the opaque
+/// composite.
+pub(crate) fn layered_rgba8(
+    values: &[f32],
+    layer_values: &[f32],
+    coloring: &ColoringOptions,
+    layers: &[ColoringLayer],
+) -> Vec<u8> {
+    if let (Some(pattern), Ok(row_length)) =
+        (coloring.dither, coloring.dither_row_length(values.len()))
+    {
+        let colors = layered_precise_colors(values, layer_values, coloring, layers);
+        return dither::dithered_rgba8(&colors, row_length, pattern);
+    }
+
+    let mut image = recolor_values_opaque(values, coloring);
+    if let Some(planes) = layer_planes(layer_values, values.len(), layers) {
+        for (layer, plane) in layers.iter().zip(planes) {
+            composite_rgba8(&mut image, &layer_rgba8(plane, layer), plane, layer);
+        }
+    }
+    image
+}
+
+/// A layer's opaque 8-bit colors for its `plane` of values. Only the
+/// composite is dithered (by the base coloring), so the layer's own
+/// `dither` is ignored.
+pub(crate) fn layer_rgba8(plane: &[f32], layer: &ColoringLayer) -> Vec<u8> {
+    if layer.coloring.dither.is_none() {
+        return recolor_values_opaque(plane, &layer.coloring);
+    }
+    let undithered = ColoringOptions {
+        dither: None,
+        ..layer.coloring.clone()
+    };
+    recolor_values_opaque(plane, &undithered)
+}
+
+/// The unquantized colors of a layered coloring (see
+/// `recolor_layered_values`), for the high-bit-depth outputs.
+pub(crate) fn layered_precise_colors(
+    values: &[f32],
+    layer_values: &[f32],
+    coloring: &ColoringOptions,
+    layers: &[ColoringLayer],
+) -> Vec<PreciseColor> {
+    let mut colors = vec![[0.0; 3]; values.len()];
+    for_each_precise_color(values, coloring, |pixel_index, color| {
+        colors[pixel_index] = color;
+    });
+    composite_precise_layers(&mut colors, layer_values, layers);
+    colors
+}
+
+/// Composites `layers`, colored from `layer_values`, over the base
+/// `colors`, like `layered_precise_colors`.
+pub(crate) fn composite_precise_layers(
+    colors: &mut [PreciseColor],
+    layer_values: &[f32],
+    layers: &[ColoringLayer],
+) {
+    if let Some(planes) = layer_planes(layer_values, colors.len(), layers) {
+        for (layer, plane) in layers.iter().zip(planes) {
+            let opacity = layer.effective_opacity();
+            for_each_precise_color(plane, &layer.coloring, |pixel_index, color| {
+                if plane[pixel_index].is_finite() {
+                    colors[pixel_index] =
+                        composite(colors[pixel_index], color, layer.blend_mode, opacity);
+                }
+            });
+        }
+    }
+}
+
+/// Recolors a layered tile from its cached base `values` and `layerValues`
+/// (see `MandelbrotTile::layer_values`), so layer settings change without a
+/// re-render. `options` is a `LayeredColoringOptions` payload.
+#[wasm_bindgen]
+pub fn recolor_tile_layers(
+    values: &[f32],
+    layer_values: &[f32],
+    options: JsValue,
+) -> Result<Vec<u8>, JsValue> {
+    let options: LayeredColoringOptions =
+        serde_wasm_bindgen::from_value(options).map_err(JsValue::from)?;
+    options
+        .coloring
+        .check_dither_width(values.len())
+        .map_err(|error| JsValue::from_str(&error))?;
+    Ok(recolor_layered_values(values, layer_values, &options))
+}
@@ -0,0 +1,209 @@
+use super::*;
+use crate::test_fixtures::{self, coloring};
+use crate::{recolor_values, OutputFormat, TileRenderOptions};
+
+fn layer(coloring: ColoringOptions, blend_mode: BlendMode, opacity: Option<f64>) -> ColoringLayer {
+    ColoringLayer {
+        coloring,
+        blend_mode,
+        opacity,
+    }
+}
+
+fn distance_estimate_coloring() -> ColoringOptions {
+    ColoringOptions {
+        distance_estimate: true,
+        ..coloring("greys")
+    }
+}
+
+/// The full-set tile with `layers`, returning `output_format`.
+fn full_set_tile(layers: Vec<ColoringLayer>, output_format: OutputFormat) -> TileRenderOptions {
+    TileRenderOptions {
+        output_format,
+        layers,
+        ..test_fixtures::full_set_tile()
+    }
+}
+
+fn assert_close(actual: PreciseColor, expected: PreciseColor) {
+    assert!(
+        actual
+            .iter()
+            .zip(expected)
+            .all(|(a, e)| (a - e).abs() < 1e-9),
+        "{actual:?} is not {expected:?}"
+    );
+}
+
+#[test]
+fn separable_modes_follow_the_compositing_formulas() {
+    let backdrop = [0.2, 0.5, 0.8];
+    let grey = [0.5; 3];
+
+    assert_close(BlendMode::Normal.blend(backdrop, grey), grey);
+    assert_close(BlendMode::Multiply.blend(backdrop, [1.0; 3]), backdrop);
+    assert_close(BlendMode::Multiply.blend(backdrop, grey), [0.1, 0.25, 0.4]);
+    assert_close(BlendMode::Screen.blend(backdrop, [0.0; 3]), backdrop);
+    assert_close(BlendMode::Screen.blend(backdrop, grey), [0.6, 0.75, 0.9]);
+    // Overlay multiplies dark backdrop channels and screens light ones.
+    assert_close(BlendMode::Overlay.blend(backdrop, grey), [0.2, 0.5, 0.8]);
+    assert_close(
+        BlendMode::Overlay.blend(backdrop, [0.0; 3]),
+        [0.0, 0.0, 0.6],
+    );
+    // Mid-grey is soft light's neutral source.
+    assert_close(BlendMode::SoftLight.blend(backdrop, grey), backdrop);
+    let darkened = BlendMode::SoftLight.blend(backdrop, [0.0; 3]);
+    let lightened = BlendMode::SoftLight.blend(backdrop, [1.0; 3]);
+    for channel in 0..3 {
+        assert!(darkened[channel] < backdrop[channel]);
+        assert!(lightened[channel] > backdrop[channel]);
+    }
+}
+
+#[test]
+fn non_separable_modes_trade_hue_and_luminosity() {
+    let red = [1.0, 0.0, 0.0];
+    let dark_grey = [0.2; 3];
+
+    // Luminosity: the backdrop's hue at the source's luminosity.
+    let shaded = BlendMode::Luminosity.blend(red, dark_grey);
+    assert!((luminosity(shaded) - 0.2).abs() < 1e-9, "{shaded:?}");
+    assert!(
+        shaded[0] > shaded[1] && shaded[1] == shaded[2],
+        "{shaded:?}"
+    );
+
+    // Hue: a grey backdrop has no saturation to give the source's hue.
+    assert_close(BlendMode::Hue.blend(dark_grey, red), dark_grey);
+    let blue = [0.0, 0.0, 1.0];
+    let recolored = BlendMode::Hue.blend(red, blue);
+    assert!((luminosity(recolored) - luminosity(red)).abs() < 1e-9);
+    assert!(recolored[2] > recolored[0], "{recolored:?}");
+}
+
+#[test]
+fn opacity_fades_the_layer() {
+    let backdrop = [0.2, 0.4, 0.6];
+    let source = [1.0, 1.0, 1.0];
+    assert_close(
+        composite(backdrop, source, BlendMode::Normal, 0.0),
+        backdrop,
+    );
+    assert_close(
+        composite(backdrop, source, BlendMode::Normal, 0.5),
+        [0.6, 0.7, 0.8],
+    );
+
+    let faded = |opacity| layer(coloring("turbo"), BlendMode::Normal, opacity);
+    assert_eq!(faded(None).effective_opacity(), 1.0);
+    assert_eq!(faded(Some(2.0)).effective_opacity(), 1.0);
+    assert_eq!(faded(Some(f64::NAN)).effective_opacity(), 0.0);
+}
+
+#[test]
+fn layers_are_transparent_where_their_values_are_not_finite() {
+    let values = [10.0, 50.0, 90.0, f32::INFINITY];
+    let layer_values = [f32::INFINITY, 20.0, 60.0, 80.0];
+    let options = LayeredColoringOptions {
+        coloring: coloring("turbo"),
+        layers: vec![layer(coloring("greys"), BlendMode::Normal, None)],
+    };
+
+    let base = recolor_values(&values, &options.coloring);
+    let top = recolor_values(&layer_values, &options.layers[0].coloring);
+    let layered = recolor_layered_values(&values, &layer_values, &options);
+    assert_eq!(layered[..4], base[..4]);
+    assert_eq!(layered[4..], top[4..]);
+
+    // A buffer that does not hold one plane per layer leaves the base.
+    assert_eq!(recolor_layered_values(&values, &[1.0], &options), base);
+}
+
+#[test]
+fn rendered_layers_match_a_recolor_of_their_values() {
+    let tile = full_set_tile(
+        vec![
+            layer(
+                distance_estimate_coloring(),
+                BlendMode::Luminosity,
+                Some(0.8),
+            ),
+            layer(coloring("rainbow"), BlendMode::SoftLight, None),
+        ],
+        OutputFormat::Rgba8,
+    );
+    let rendered = tile.render_layered();
+    assert_eq!(rendered.layer_values.len(), 2 * 24 * 20);
+
+    let options = LayeredColoringOptions {
+        coloring: tile.coloring.clone(),
+        layers: tile.layers.clone(),
+    };
+    assert_eq!(
+        recolor_layered_values(&rendered.values, &rendered.layer_values, &options),
+        rendered.image
+    );
+
+    // The layers change the picture.
+    let plain = full_set_tile(Vec::new(), OutputFormat::Rgba8).render_layered();
+    assert!(plain.layer_values.is_empty());
+    assert_eq!(plain.values, rendered.values);
+    assert_ne!(plain.image, rendered.image);
+}
+
+#[test]
+fn layers_reuse_the_values_of_their_coloring_method() {
+    let tile = full_set_tile(
+        vec![
+            layer(coloring("rainbow"), BlendMode::Overlay, None),
+            layer(distance_estimate_coloring(), BlendMode::Multiply, Some(0.5)),
+            layer(
+                ColoringOptions {
+                    palette: "viridis".to_string(),
+                    ..distance_estimate_coloring()
+                },
+                BlendMode::Screen,
+                None,
+            ),
+        ],
+        OutputFormat::Rgba8,
+    );
+    let rendered = tile.render_layered();
+    let planes: Vec<&[f32]> = rendered.layer_values.chunks(24 * 20).collect();
+
+    // An escape-time layer recolors the base values; distance-estimate
+    // layers share one render of that method.
+    assert_eq!(planes[0], rendered.values);
+    let distance_estimate = full_set_tile(Vec::new(), OutputFormat::Rgba8);
+    let distance_estimate = TileRenderOptions {
+        coloring: distance_estimate_coloring(),
+        ..distance_estimate
+    }
+    .render_layered();
+    assert_eq!(planes[1], distance_estimate.values);
+    assert_eq!(planes[2], distance_estimate.values);
+}
+
+#[test]
+fn high_bit_depth_outputs_include_the_layers() {
+    let layers = vec![layer(
+        distance_estimate_coloring(),
+        BlendMode::Multiply,
+        None,
+    )];
+    let layered = full_set_tile(layers, OutputFormat::Rgba16).render_layered();
+    let plain = full_set_tile(Vec::new(), OutputFormat::Rgba16).render_layered();
+
+    assert_eq!(layered.image16.len(), 24 * 20 * 4);
+    assert_ne!(layered.image16, plain.image16);
+    // The 16-bit image agrees with the 8-bit one to within rounding.
+    for (&wide, &narrow) in layered.image16.iter().zip(&layered.image) {
+        let narrowed = (f64::from(wide) / 257.0).round();
+        assert!(
+            (narrowed - f64::from(narrow)).abs() <= 2.0,
+            "{wide} vs {narrow}"
+        );
+    }
+}
@@ -1,24 +1,73 @@
 #![allow(clippy::too_many_arguments)]
 
+mod accessibility;
+mod alpha;
+mod channels;
+mod dither;
+mod export;
 mod float_exp;
+mod gradient;
+mod histogram;
+mod layers;
+mod palette_import;
 mod perturbation;
+mod polynomial;
+mod progressive;
+mod session;
+#[cfg(feature = "parallel")]
+mod threads;
 mod utils;
+mod zoom_reuse;
+
+pub use accessibility::{
+    palette_metrics, simulate_color_vision_deficiency_rgba8, ColorVisionDeficiency,
+    PaletteAnalysisOptions, PaletteMetrics,
+};
+pub use alpha::AlphaRamp;
+pub use channels::ValueChannel;
+pub use dither::DitherPattern;
+pub use export::{encode_exr_rgb, encode_pfm_rgb, encode_png_rgba16};
+pub use gradient::{GradientEasing, GradientSpec, GradientStop};
+pub use histogram::{
+    fit_coloring_to_values, EscapeHistogram, IterationRange, ViewRect, PALETTE_CDF_SIZE,
+};
+pub use layers::{recolor_layered_values, BlendMode, ColoringLayer, LayeredColoringOptions};
+pub use palette_import::{import_palette_file, ImportedPalette, PaletteFileFormat};
+pub use perturbation::{
+    clear_orbit_cache, orbit_cache_stats, set_orbit_cache_budget, OrbitCacheStats,
+};
+pub use polynomial::PolynomialTerm;
+pub use progressive::ProgressiveTile;
+pub use session::RenderSession;
+#[cfg(feature = "parallel")]
+pub use threads::TileQueue;
+#[cfg(all(feature = "parallel", target_arch = "wasm32"))]
+pub use threads::{init_render_threads, run_render_thread};
 
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 #[cfg(test)]
 #[path = "lib_test.rs"]
 mod lib_test;
+#[cfg(test)]
+mod test_fixtures;
 
 use itertools_num::linspace;
 use num::complex::Complex64;
-use palette::{FromColor, Hsl, Hsluv, IntoColor, Lch, Lighten, Okhsl, Saturate, ShiftHue, Srgb};
+use palette::white_point::D65;
+use palette::{
+    FromColor, Hsl, Hsluv, IntoColor, Lch, Lighten, Okhsl, Oklab, Oklch, Saturate, ShiftHue, Srgb,
+};
 use serde::Deserialize;
 use wasm_bindgen::prelude::*;
 
 const ESCAPE_RADIUS: f64 = 3.0;
 type RgbColor = [u8; 3];
+/// An sRGB-encoded color with f64 channels in [0, 1]: the unquantized form
+/// of `RgbColor` that the high-bit-depth outputs are produced from.
+type PreciseColor = [f64; 3];
 
 // First Brent-periodicity save point; the save interval doubles from here.
 const PERIODICITY_FIRST_SAVE: u32 = 8;
@@ -75,13 +124,21 @@ const QUADRATIC_STREAM_STRIDE: u32 = 32;
 
 // Mariani–Silver subdivision: rects whose width or height is at or below
 // this compute all their pixels directly instead of testing their border.
-#[cfg(target_arch = "wasm32")]
 const MARIANI_LEAF: usize = 8;
 
 // Sentinel iteration count for pixels not yet computed during subdivision.
-#[cfg(target_arch = "wasm32")]
 pub(crate) const UNCOMPUTED: u32 = u32::MAX;
 
+// Parallel renders (the `parallel` feature) split a tile into about this many
+// row bands per thread, so a band of slow interior rows doesn't leave the
+// other threads idle, but never into bands shorter than
+// `PARALLEL_MIN_BAND_ROWS`, below which per-band subdivision loses most of
+// its interior fill.
+#[cfg(feature = "parallel")]
+const PARALLEL_BANDS_PER_THREAD: usize = 4;
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_BAND_ROWS: usize = 32;
+
 /// True when `c = re + im*i` lies inside (or on) the main cardioid or the
 /// period-2 bulb. Closed-form membership: such points never escape, so the
 /// escape loop would run out its full iteration budget on them.
@@ -144,6 +201,17 @@ struct RenderedTile {
     /// The precision path that produced this tile (for the client's
     /// diagnostics overlay, issue #50).
     tier: RenderTier,
+    /// Each pixel's escape iteration count and final `z` on the
+    /// perturbation tiers and formula tiles, from which the escape-derived
+    /// value channels are read (see `TileRenderOptions::render_channels`).
+    /// Empty on the direct `z^power + c` path, for tiles rendered solid
+    /// black, and for tiles seeded from a parent that read no channels (see
+    /// `progressive::PartialTile::finish`).
+    escape_results: Vec<(u32, Complex64)>,
+    /// Row-major indices of the pixels `antialias_tile` resampled, with the
+    /// unquantized average of their samples' colors: every output depth
+    /// colors them with it instead of with the pixel's value.
+    antialiased: Vec<(usize, PreciseColor)>,
 }
 
 impl RenderedTile {
@@ -157,6 +225,8 @@ impl RenderedTile {
             values: vec![f32::INFINITY; image_width * image_height],
             stats: TileIterationStats::default(),
             tier,
+            escape_results: Vec::new(),
+            antialiased: Vec::new(),
         }
     }
 }
@@ -168,8 +238,107 @@ const NUM_COLOR_CHANNELS: usize = 4;
 /// interpolation between entries stays visually seamless.
 const PALETTE_LUT_SIZE: usize = 256;
 
+/// The color space a palette blends neighboring colors in. Field values
+/// mirror the client's camelCase payload.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Hash, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum InterpolationSpace {
+    /// Straight blend of the sRGB-encoded channels, like CSS gradients.
+    #[default]
+    Srgb,
+    /// Blend in linear light: physically correct mixing, brighter midpoints.
+    LinearRgb,
+    /// CIE LCh with hue taking the shorter way around, which keeps
+    /// saturated midpoints between distant hues.
+    Lch,
+    /// OKLab: perceptually uniform steps without the muddy, darkened
+    /// midpoints of sRGB blends.
+    Oklab,
+    /// OKLCh, OKLab's polar form, with hue taking the shorter way around.
+    Oklch,
+}
+
+impl InterpolationSpace {
+    /// Converts sRGB-encoded channels into this space's blend coordinates.
+    fn coordinates_from_srgb(self, rgb: [f64; 3]) -> [f64; 3] {
+        let srgb = Srgb::new(rgb[0], rgb[1], rgb[2]);
+        match self {
+            InterpolationSpace::Srgb => rgb,
+            InterpolationSpace::LinearRgb => rgb.map(srgb_to_linear),
+            InterpolationSpace::Lch => {
+                let lch = Lch::<D65, f64>::from_color(srgb);
+                [lch.l, lch.chroma, lch.hue.into_positive_degrees()]
+            }
+            InterpolationSpace::Oklab => {
+                let oklab = Oklab::<f64>::from_color(srgb);
+                [oklab.l, oklab.a, oklab.b]
+            }
+            InterpolationSpace::Oklch => {
+                let oklch = Oklch::<f64>::from_color(srgb);
+                [oklch.l, oklch.chroma, oklch.hue.into_positive_degrees()]
+            }
+        }
+    }
+
+    /// Converts blend coordinates back to sRGB-encoded channels, possibly
+    /// out of gamut (callers clamp).
+    fn coordinates_to_srgb(self, coordinates: [f64; 3]) -> [f64; 3] {
+        let [x, y, z] = coordinates;
+        let srgb = match self {
+            InterpolationSpace::Srgb => return coordinates,
+            InterpolationSpace::LinearRgb => return coordinates.map(linear_to_srgb),
+            InterpolationSpace::Lch => Srgb::<f64>::from_color(Lch::<D65, f64>::new(x, y, z)),
+            InterpolationSpace::Oklab => Srgb::<f64>::from_color(Oklab::new(x, y, z)),
+            InterpolationSpace::Oklch => Srgb::<f64>::from_color(Oklch::new(x, y, z)),
+        };
+        [srgb.red, srgb.green, srgb.blue]
+    }
+
+    /// Blends two colors' coordinates; `fraction` 0 gives `from`.
+    fn mix(self, from: [f64; 3], to: [f64; 3], fraction: f64) -> [f64; 3] {
+        let lerp = |a: f64, b: f64| a + (b - a) * fraction;
+        match self {
+            InterpolationSpace::Srgb
+            | InterpolationSpace::LinearRgb
+            | InterpolationSpace::Oklab => {
+                [0, 1, 2].map(|channel| lerp(from[channel], to[channel]))
+            }
+            InterpolationSpace::Lch | InterpolationSpace::Oklch => {
+                // A grey has no meaningful hue: borrow the other end's, so
+                // fading to grey desaturates instead of sweeping through
+                // unrelated hues. Far below a visible chroma in both spaces.
+                const ACHROMATIC_CHROMA: f64 = 1e-4;
+                let (mut from_hue, mut to_hue) = (from[2], to[2]);
+                if from[1] < ACHROMATIC_CHROMA {
+                    from_hue = to_hue;
+                } else if to[1] < ACHROMATIC_CHROMA {
+                    to_hue = from_hue;
+                }
+                let mut hue_delta = (to_hue - from_hue).rem_euclid(360.0);
+                if hue_delta > 180.0 {
+                    hue_delta -= 360.0;
+                }
+                [
+                    lerp(from[0], to[0]),
+                    lerp(from[1], to[1]),
+                    from_hue + hue_delta * fraction,
+                ]
+            }
+        }
+    }
+}
+
 /// A color palette the renderer can sample continuously: either a colorous
 /// gradient used as-is, or a lookup table built by `palette_from_fn`.
+///
+/// Neighboring entries always blend in sRGB: a palette blended in another
+/// space is built in that space once (see `palette_from_control_points`),
+/// not re-blended per pixel. Between two of the `PALETTE_LUT_SIZE` entries
+/// the sRGB blend strays from the selected space's curve, however densely
+/// `color_density` stretches the palette: together with the entries' own
+/// 8-bit rounding, by under one 8-bit step in OKLab and under three where
+/// an OKLCh hue sweep turns fastest (see
+/// `test_dense_color_density_follows_the_interpolation_space`).
 enum Palette {
     Original(colorous::Gradient),
     Lut(Vec<colorous::Color>),
@@ -177,25 +346,98 @@ enum Palette {
 
 impl Palette {
     /// Returns the color at position `t` in [0, 1], like
-    /// `colorous::Gradient::eval_continuous`.
+    /// `colorous::Gradient::eval_continuous`, blending the same neighboring
+    /// entries as `eval_continuous_precise` (see `neighbors`) in `u8` steps.
     fn eval_continuous(&self, t: f64) -> colorous::Color {
-        match self {
-            Palette::Original(gradient) => gradient.eval_continuous(t),
-            Palette::Lut(lut) => {
-                let position = t.clamp(0.0, 1.0) * (lut.len() - 1) as f64;
-                let index = position as usize;
-                let next_index = (index + 1).min(lut.len() - 1);
-                let fraction = position - index as f64;
-                let lerp = |a: u8, b: u8| {
-                    (f64::from(a) + (f64::from(b) - f64::from(a)) * fraction).round() as u8
-                };
-                let (from, to) = (lut[index], lut[next_index]);
+        let (from, to, fraction) = self.neighbors(t);
+        let lerp =
+            |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * fraction).round() as u8;
+        colorous::Color {
+            r: lerp(from.r, to.r),
+            g: lerp(from.g, to.g),
+            b: lerp(from.b, to.b),
+        }
+    }
+
+    /// The color at position `t` in [0, 1] as sRGB-encoded channels in
+    /// [0, 1], interpolated in f64 between the same neighboring entries as
+    /// `eval_continuous` instead of rounding each step back to `u8`: only
+    /// the rounding of the blend differs, which is what bands once exports
+    /// are graded.
+    fn eval_continuous_precise(&self, t: f64) -> PreciseColor {
+        let (from, to, fraction) = self.neighbors(t);
+        let channels = |color: colorous::Color| color.as_array().map(|c| f64::from(c) / 255.0);
+        let (from, to) = (channels(from), channels(to));
+        [0, 1, 2].map(|channel| from[channel] + (to[channel] - from[channel]) * fraction)
+    }
+
+    /// The two entries around position `t` in [0, 1] and the fraction of
+    /// the way from the first to the second. Colorous gradients are sampled
+    /// at `PALETTE_LUT_SIZE` points (their own resolution), so every palette
+    /// is sampled as a lookup table.
+    fn neighbors(&self, t: f64) -> (colorous::Color, colorous::Color, f64) {
+        let len = match self {
+            Palette::Original(_) => PALETTE_LUT_SIZE,
+            Palette::Lut(colors) => colors.len(),
+        };
+        let position = t.clamp(0.0, 1.0) * (len - 1) as f64;
+        let index = position as usize;
+        let next_index = (index + 1).min(len - 1);
+        let entry = |index: usize| match self {
+            Palette::Original(gradient) => {
+                gradient.eval_continuous(index as f64 / (PALETTE_LUT_SIZE - 1) as f64)
+            }
+            Palette::Lut(colors) => colors[index],
+        };
+        (entry(index), entry(next_index), position - index as f64)
+    }
+
+    /// Evenly spaced colors to rebuild this palette from in another blend
+    /// space (see `interpolated_palette`): a lookup table's own entries —
+    /// the listed colors of a sparse imported palette, or dense samples a
+    /// re-blend leaves essentially unchanged — or a colorous gradient
+    /// sampled at `RESAMPLED_CONTROL_POINTS` points.
+    fn control_points(&self) -> Vec<(f64, PreciseColor)> {
+        let count = match self {
+            Palette::Original(_) => RESAMPLED_CONTROL_POINTS,
+            Palette::Lut(colors) => colors.len(),
+        };
+        (0..count)
+            .map(|i| {
+                let t = i as f64 / (count - 1) as f64;
+                (t, self.eval_continuous_precise(t))
+            })
+            .collect()
+    }
+
+    /// The same palette with every entry daltonized for `deficiency` (see
+    /// `ColorVisionDeficiency::daltonize`). Backs `ColoringOptions::cvd_remap`.
+    fn daltonized(&self, deficiency: ColorVisionDeficiency) -> Palette {
+        let channel = |v: f64| (v * 255.0).round() as u8;
+        let colors = self
+            .entries()
+            .into_iter()
+            .map(|color| {
+                let [r, g, b] =
+                    deficiency.daltonize(color.as_array().map(|c| f64::from(c) / 255.0));
                 colorous::Color {
-                    r: lerp(from.r, to.r),
-                    g: lerp(from.g, to.g),
-                    b: lerp(from.b, to.b),
+                    r: channel(r),
+                    g: channel(g),
+                    b: channel(b),
                 }
-            }
+            })
+            .collect();
+        Palette::Lut(colors)
+    }
+
+    /// The palette's entries: a lookup table's own, or a colorous gradient
+    /// sampled at `PALETTE_LUT_SIZE` points.
+    fn entries(&self) -> Vec<colorous::Color> {
+        match self {
+            Palette::Original(gradient) => (0..PALETTE_LUT_SIZE)
+                .map(|i| gradient.eval_continuous(i as f64 / (PALETTE_LUT_SIZE - 1) as f64))
+                .collect(),
+            Palette::Lut(colors) => colors.clone(),
         }
     }
 }
@@ -204,7 +446,7 @@ impl Palette {
 /// [0, 1]. Channel values outside [0, 1] are clamped.
 fn palette_from_fn(rgb_at: impl Fn(f64) -> [f64; 3]) -> Palette {
     let channel = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
-    let lut = (0..PALETTE_LUT_SIZE)
+    let colors = (0..PALETTE_LUT_SIZE)
         .map(|i| {
             let rgb = rgb_at(i as f64 / (PALETTE_LUT_SIZE - 1) as f64);
             colorous::Color {
@@ -214,19 +456,54 @@ fn palette_from_fn(rgb_at: impl Fn(f64) -> [f64; 3]) -> Palette {
             }
         })
         .collect();
-    Palette::Lut(lut)
+    Palette::Lut(colors)
 }
 
+/// Builds a palette lookup table through sRGB-encoded control points at
+/// ascending positions in [0, 1], blended in `interpolation` space. Colors
+/// extend flat before the first point and after the last.
+fn palette_from_control_points(
+    interpolation: InterpolationSpace,
+    points: &[(f64, PreciseColor)],
+) -> Palette {
+    let coordinates: Vec<(f64, [f64; 3])> = points
+        .iter()
+        .map(|&(position, rgb)| (position, interpolation.coordinates_from_srgb(rgb)))
+        .collect();
+    palette_from_fn(|t| {
+        let next = coordinates
+            .partition_point(|&(position, _)| position <= t)
+            .min(coordinates.len() - 1);
+        if next == 0 {
+            return points[0].1;
+        }
+        let (x0, from) = coordinates[next - 1];
+        let (x1, to) = coordinates[next];
+        let fraction = if x1 > x0 {
+            ((t - x0) / (x1 - x0)).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        interpolation.coordinates_to_srgb(interpolation.mix(from, to, fraction))
+    })
+}
+
+/// Number of evenly spaced samples a colorous gradient is rebuilt from when
+/// re-blended in another space. Enough to follow every colorous gradient's
+/// shape; few enough that the blend between them shows.
+const RESAMPLED_CONTROL_POINTS: usize = 16;
+
 /// MATLAB's classic "jet" colormap: dark blue -> cyan -> yellow -> dark
 /// red. The palette turbo was designed to replace; kept here for its vivid,
 /// high-local-contrast fractal look.
-fn jet_color(t: f64) -> [f64; 3] {
-    [
-        1.5 - (4.0 * t - 3.0).abs(),
-        1.5 - (4.0 * t - 2.0).abs(),
-        1.5 - (4.0 * t - 1.0).abs(),
-    ]
-}
+const JET_POINTS: &[(f64, PreciseColor)] = &[
+    (0.0, [0.0, 0.0, 0.5]),
+    (0.125, [0.0, 0.0, 1.0]),
+    (0.375, [0.0, 1.0, 1.0]),
+    (0.625, [1.0, 1.0, 0.0]),
+    (0.875, [1.0, 0.0, 0.0]),
+    (1.0, [0.5, 0.0, 0.0]),
+];
 
 /// gnuplot's default pm3d palette: black -> blue -> violet -> red ->
 /// yellow. True black at one end with a full rainbow-like sweep, unlike
@@ -239,47 +516,29 @@ fn gnuplot_color(t: f64) -> [f64; 3] {
 /// black -> violet -> blue -> green -> yellow -> red -> grey. Spans nearly
 /// the full lightness range with the strongest local contrast of any
 /// palette here — filigree detail renders especially crisply.
-fn nipy_spectral_color(t: f64) -> [f64; 3] {
-    const POINTS: &[(f64, [f64; 3])] = &[
-        (0.00, [0.0, 0.0, 0.0]),
-        (0.05, [0.4667, 0.0, 0.5333]),
-        (0.10, [0.5333, 0.0, 0.6]),
-        (0.15, [0.0, 0.0, 0.6667]),
-        (0.20, [0.0, 0.0, 0.8667]),
-        (0.25, [0.0, 0.4667, 0.8667]),
-        (0.30, [0.0, 0.6, 0.8667]),
-        (0.35, [0.0, 0.6667, 0.6667]),
-        (0.40, [0.0, 0.6667, 0.5333]),
-        (0.45, [0.0, 0.6, 0.0]),
-        (0.50, [0.0, 0.7333, 0.0]),
-        (0.55, [0.0, 0.8667, 0.0]),
-        (0.60, [0.0, 1.0, 0.0]),
-        (0.65, [0.7333, 1.0, 0.0]),
-        (0.70, [0.9333, 0.9333, 0.0]),
-        (0.75, [1.0, 0.8, 0.0]),
-        (0.80, [1.0, 0.6, 0.0]),
-        (0.85, [1.0, 0.0, 0.0]),
-        (0.90, [0.8667, 0.0, 0.0]),
-        (0.95, [0.8, 0.0, 0.0]),
-        (1.00, [0.8, 0.8, 0.8]),
-    ];
-
-    let t = t.clamp(0.0, 1.0);
-    let next = POINTS
-        .partition_point(|&(x, _)| x <= t)
-        .min(POINTS.len() - 1);
-    if next == 0 {
-        return POINTS[0].1;
-    }
-    let (x0, from) = POINTS[next - 1];
-    let (x1, to) = POINTS[next];
-    let fraction = if x1 > x0 { (t - x0) / (x1 - x0) } else { 0.0 };
-    [
-        from[0] + (to[0] - from[0]) * fraction,
-        from[1] + (to[1] - from[1]) * fraction,
-        from[2] + (to[2] - from[2]) * fraction,
-    ]
-}
+const NIPY_SPECTRAL_POINTS: &[(f64, PreciseColor)] = &[
+    (0.00, [0.0, 0.0, 0.0]),
+    (0.05, [0.4667, 0.0, 0.5333]),
+    (0.10, [0.5333, 0.0, 0.6]),
+    (0.15, [0.0, 0.0, 0.6667]),
+    (0.20, [0.0, 0.0, 0.8667]),
+    (0.25, [0.0, 0.4667, 0.8667]),
+    (0.30, [0.0, 0.6, 0.8667]),
+    (0.35, [0.0, 0.6667, 0.6667]),
+    (0.40, [0.0, 0.6667, 0.5333]),
+    (0.45, [0.0, 0.6, 0.0]),
+    (0.50, [0.0, 0.7333, 0.0]),
+    (0.55, [0.0, 0.8667, 0.0]),
+    (0.60, [0.0, 1.0, 0.0]),
+    (0.65, [0.7333, 1.0, 0.0]),
+    (0.70, [0.9333, 0.9333, 0.0]),
+    (0.75, [1.0, 0.8, 0.0]),
+    (0.80, [1.0, 0.6, 0.0]),
+    (0.85, [1.0, 0.0, 0.0]),
+    (0.90, [0.8667, 0.0, 0.0]),
+    (0.95, [0.8, 0.0, 0.0]),
+    (1.00, [0.8, 0.8, 0.8]),
+];
 
 static COLOR_PALETTES: Lazy<HashMap<String, Palette>> = Lazy::new(|| {
     use Palette::Original;
@@ -292,11 +551,14 @@ static COLOR_PALETTES: Lazy<HashMap<String, Palette>> = Lazy::new(|| {
     map.insert("turbo".to_string(), Original(colorous::TURBO));
     map.insert("viridis".to_string(), Original(colorous::VIRIDIS));
 
-    map.insert("jet".to_string(), palette_from_fn(jet_color));
+    map.insert(
+        "jet".to_string(),
+        palette_from_control_points(InterpolationSpace::Srgb, JET_POINTS),
+    );
     map.insert("gnuplot".to_string(), palette_from_fn(gnuplot_color));
     map.insert(
         "nipySpectral".to_string(),
-        palette_from_fn(nipy_spectral_color),
+        palette_from_control_points(InterpolationSpace::Srgb, NIPY_SPECTRAL_POINTS),
     );
 
     map.insert("brownGreen".to_string(), Original(colorous::BROWN_GREEN));
@@ -375,19 +637,34 @@ fn calculate_escape_iterations_quadratic(
         return (max_iterations, c);
     }
 
-    let mut z = c;
+    iterate_to_escape(c, max_iterations, escape_radius_squared, |z| z * z + c)
+}
+
+/// The escape-time loop the direct f64 kernels share: iterates `step` from
+/// `z0` until `z` leaves the escape radius or `max_iterations` is reached,
+/// returning the iteration count and final `z`.
+///
+/// Brent-style periodicity: if `z` exactly revisits a saved value, the orbit
+/// is numerically periodic and can never escape, so it stops early as
+/// non-escaping.
+#[inline]
+pub(crate) fn iterate_to_escape(
+    z0: Complex64,
+    max_iterations: u32,
+    escape_radius_squared: f64,
+    step: impl Fn(Complex64) -> Complex64,
+) -> (u32, Complex64) {
+    let mut z = z0;
     let mut iter = 0;
 
-    // Brent-style periodicity: if z exactly revisits a saved value, the orbit
-    // is numerically periodic and can never escape.
     let mut saved = z;
     let mut next_save = PERIODICITY_FIRST_SAVE;
 
     while z.norm_sqr() < escape_radius_squared && iter < max_iterations {
-        z = z * z + c;
+        z = step(z);
         iter += 1;
 
-        if iter % PERIODICITY_CHECK_STRIDE == 0 {
+        if iter.is_multiple_of(PERIODICITY_CHECK_STRIDE) {
             if z == saved {
                 return (max_iterations, z);
             }
@@ -417,30 +694,10 @@ fn calculate_escape_iterations_general(
     escape_radius_squared: f64,
     exponent: u32,
 ) -> (u32, Complex64) {
-    let mut z = c;
-    let mut iter = 0;
-
-    // Brent-style periodicity, as in the quadratic loop. There is no
-    // closed-form interior test for general exponents.
-    let mut saved = z;
-    let mut next_save = PERIODICITY_FIRST_SAVE;
-
-    while z.norm_sqr() < escape_radius_squared && iter < max_iterations {
-        z = z.powu(exponent) + c;
-        iter += 1;
-
-        if iter % PERIODICITY_CHECK_STRIDE == 0 {
-            if z == saved {
-                return (max_iterations, z);
-            }
-            if iter == next_save {
-                saved = z;
-                next_save = next_save.saturating_mul(2);
-            }
-        }
-    }
-
-    (iter, z)
+    // There is no closed-form interior test for general exponents.
+    iterate_to_escape(c, max_iterations, escape_radius_squared, |z| {
+        z.powu(exponent) + c
+    })
 }
 
 /// Escape-time iteration for a Julia set: unlike the Mandelbrot iteration
@@ -461,32 +718,13 @@ fn calculate_julia_escape_iterations(
     escape_radius_squared: f64,
     exponent: u32,
 ) -> (u32, Complex64) {
-    let mut z = z0;
-    let mut iter = 0;
-
-    let mut saved = z;
-    let mut next_save = PERIODICITY_FIRST_SAVE;
-
-    while z.norm_sqr() < escape_radius_squared && iter < max_iterations {
-        z = if exponent == 2 {
+    iterate_to_escape(z0, max_iterations, escape_radius_squared, |z| {
+        if exponent == 2 {
             z * z + c
         } else {
             z.powu(exponent) + c
-        };
-        iter += 1;
-
-        if iter % PERIODICITY_CHECK_STRIDE == 0 {
-            if z == saved {
-                return (max_iterations, z);
-            }
-            if iter == next_save {
-                saved = z;
-                next_save = next_save.saturating_mul(2);
-            }
         }
-    }
-
-    (iter, z)
+    })
 }
 
 /// Exterior distance estimate for a single point `c`, in the same units as the
@@ -686,6 +924,55 @@ fn period_at_c(c: Complex64, max_iterations: u32, escape_radius_squared: f64) ->
     None
 }
 
+// Stripes per full turn of `arg z` in the stripe-average channel.
+const STRIPE_DENSITY: f64 = 5.0;
+
+/// The stripe average of an escaping point `c` (Härkönen, "On Smooth
+/// Fractal Coloring Techniques", 2007): the mean over its orbit of
+/// `(1 + sin(STRIPE_DENSITY · arg z_n)) / 2`, in `[0, 1]`. The means with and
+/// without the final orbit point are blended by the smooth escape fraction
+/// (see `smoothed_escape_value`), so the value stays continuous across
+/// iteration bands. `None` for points that do not escape.
+fn stripe_average_at_c(
+    c: Complex64,
+    max_iterations: u32,
+    escape_radius_squared: f64,
+    exponent: u32,
+) -> Option<f64> {
+    if exponent == 2 && in_main_cardioid_or_bulb(c.re, c.im) {
+        return None;
+    }
+
+    let stripe = |z: Complex64| 0.5 + 0.5 * (STRIPE_DENSITY * z.arg()).sin();
+    let mut z = c;
+    let mut last = stripe(z);
+    let mut sum = last;
+    let mut iter = 1u32;
+
+    while z.norm_sqr() < escape_radius_squared && iter < max_iterations {
+        z = z.powu(exponent) + c;
+        iter += 1;
+        last = stripe(z);
+        sum += last;
+    }
+
+    if z.norm_sqr() < escape_radius_squared {
+        return None;
+    }
+
+    let count = f64::from(iter);
+    let average = sum / count;
+    let previous_average = if iter > 1 {
+        (sum - last) / (count - 1.0)
+    } else {
+        average
+    };
+    let fraction = (smoothed_escape_value(iter, z, max_iterations, exponent, true)
+        - f64::from(iter - 1))
+    .clamp(0.0, 1.0);
+    Some(previous_average + (average - previous_average) * fraction)
+}
+
 /// Escape-time iteration for two pixels at once, one per 128-bit SIMD lane
 /// (quadratic case). Escaped lanes are frozen with a mask while the other lane
 /// keeps iterating. The lane arithmetic is IEEE-identical to the scalar loop
@@ -1814,7 +2101,8 @@ fn stream_escape_general<const CHAINS: usize>(
 
 /// Renders the full pixel grid via Mariani–Silver subdivision. Each wave
 /// streams the pending rects' uncomputed border-ring pixels through the
-/// lane-refilling kernel in one call; a rect whose entire ring reports
+/// lane-refilling kernel in one call (on wasm32; native builds batch them
+/// through the fixed-width loops); a rect whose entire ring reports
 /// `max_iterations` fills its inside as interior without computing it, and
 /// any other rect splits into quadrants for the next wave. Rects at or below
 /// `MARIANI_LEAF` compute all their pixels directly. Every pixel is computed
@@ -1825,7 +2113,6 @@ fn stream_escape_general<const CHAINS: usize>(
 /// the Green's function, which holds for every multibrot degree), and
 /// breakable only by sub-pixel exterior channels: the same assumption
 /// `rect_in_set` already makes at tile level for every exponent.
-#[cfg(target_arch = "wasm32")]
 fn stream_tile_subdivided(
     re_values: &[f64],
     im_values: &[f64],
@@ -1847,32 +2134,144 @@ fn stream_tile_subdivided(
                     .iter()
                     .map(|&pixel| (re_values[pixel % width], im_values[pixel / width])),
             );
-            if exponent == 2 {
-                stream_escape_quadratic::<QUADRATIC_STREAM_CHAINS>(
-                    &points,
-                    max_iterations,
-                    ESCAPE_RADIUS.powi(2),
-                    wave_results,
-                );
-            } else {
-                stream_escape_general::<STREAM_CHAINS>(
-                    &points,
-                    max_iterations,
-                    ESCAPE_RADIUS.powi(2),
-                    exponent,
-                    wave_results,
-                );
-            }
+            escape_points(&points, max_iterations, exponent, wave_results);
         },
     );
 }
 
+/// `stream_tile_subdivided` over the row bands of `results` (see
+/// `for_each_row_band`), as shallow escape-time tiles render. Slots of
+/// `results` already filled are kept, so a render can be seeded with
+/// results it already has.
+pub(crate) fn complete_tile_subdivided(
+    re_values: &[f64],
+    im_values: &[f64],
+    max_iterations: u32,
+    exponent: u32,
+    results: &mut [(u32, Complex64)],
+) {
+    let width = re_values.len();
+    for_each_row_band(width, results, |first_row, band_results| {
+        let band_rows = band_results.len() / width;
+        stream_tile_subdivided(
+            re_values,
+            &im_values[first_row..first_row + band_rows],
+            max_iterations,
+            exponent,
+            band_results,
+        );
+    });
+}
+
+/// Escape results for `points` into the matching slots of `results`: the
+/// lane-refilling stream kernel matching the exponent (quadratic or
+/// general) on wasm32, the fixed-batch loops natively.
+pub(crate) fn escape_points(
+    points: &[(f64, f64)],
+    max_iterations: u32,
+    exponent: u32,
+    results: &mut [(u32, Complex64)],
+) {
+    #[cfg(target_arch = "wasm32")]
+    if exponent == 2 {
+        stream_escape_quadratic::<QUADRATIC_STREAM_CHAINS>(
+            points,
+            max_iterations,
+            ESCAPE_RADIUS.powi(2),
+            results,
+        );
+    } else {
+        stream_escape_general::<STREAM_CHAINS>(
+            points,
+            max_iterations,
+            ESCAPE_RADIUS.powi(2),
+            exponent,
+            results,
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    escape_points_batched(points, max_iterations, exponent, results);
+}
+
+/// Escape results for `points` into the matching slots of `results`, four
+/// (then two, then one) points per call: the native counterpart of the
+/// stream kernels.
+#[cfg(not(target_arch = "wasm32"))]
+fn escape_points_batched(
+    points: &[(f64, f64)],
+    max_iterations: u32,
+    exponent: u32,
+    results: &mut [(u32, Complex64)],
+) {
+    let mut quads = points.chunks_exact(4);
+    let mut quad_results = results.chunks_exact_mut(4);
+    for (quad, quad_result) in (&mut quads).zip(&mut quad_results) {
+        quad_result.copy_from_slice(&calculate_escape_iterations_quad(
+            [quad[0], quad[1], quad[2], quad[3]],
+            max_iterations,
+            exponent,
+        ));
+    }
+
+    let (rest, rest_results) = (quads.remainder(), quad_results.into_remainder());
+    let mut pairs = rest.chunks_exact(2);
+    let mut pair_results = rest_results.chunks_exact_mut(2);
+    for (pair, pair_result) in (&mut pairs).zip(&mut pair_results) {
+        pair_result.copy_from_slice(&calculate_escape_iterations_pair(
+            pair[0],
+            pair[1],
+            max_iterations,
+            exponent,
+        ));
+    }
+
+    if let (Some(&(re, im)), Some(result)) = (
+        pairs.remainder().first(),
+        pair_results.into_remainder().first_mut(),
+    ) {
+        *result = calculate_escape_iterations(re, im, max_iterations, exponent);
+    }
+}
+
+/// Runs `render_band(first_row, band_results)` over row bands of the
+/// row-major, `width`-wide `results`, each band rendering its rows into its
+/// slice. With the `parallel` feature the bands run concurrently on the
+/// rayon pool (on wasm32, the worker pool `threads` starts); otherwise one
+/// band covers the whole tile.
+pub(crate) fn for_each_row_band(
+    width: usize,
+    results: &mut [(u32, Complex64)],
+    render_band: impl Fn(usize, &mut [(u32, Complex64)]) + Sync,
+) {
+    if width == 0 || results.is_empty() {
+        return;
+    }
+
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+
+        let height = results.len() / width;
+        let band_rows = height
+            .div_ceil(rayon::current_num_threads() * PARALLEL_BANDS_PER_THREAD)
+            .max(PARALLEL_MIN_BAND_ROWS);
+        results
+            .par_chunks_mut(band_rows * width)
+            .enumerate()
+            .for_each(|(band, band_results)| render_band(band * band_rows, band_results));
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    render_band(0, results);
+}
+
 /// The Mariani–Silver wave/worklist machinery, generic over the streaming
 /// kernel: each wave hands `compute_wave` the pending rects' uncomputed
 /// pixel indices (row-major) to resolve into the matching slots of the
-/// output slice in one kernel call. `results` must arrive filled with
-/// `UNCOMPUTED`.
-#[cfg(target_arch = "wasm32")]
+/// output slice in one kernel call. Only the `UNCOMPUTED` slots of
+/// `results` are computed or filled; slots that arrive filled are kept, and
+/// the ring tests read them like computed ones.
 pub(crate) fn subdivide_tile_streamed(
     width: usize,
     height: usize,
@@ -2297,19 +2696,109 @@ pub fn transform_color(
     transformed_color
 }
 
+/// `transform_color` without the final `u8` truncation: the same color-space
+/// adjustments applied to an unquantized color, for the high-bit-depth
+/// outputs. Untransformed colors pass through exactly.
+fn transform_color_precise(
+    color: PreciseColor,
+    color_space: &ValidColorSpace,
+    shift_hue_amount: f32,
+    saturate_amount: f32,
+    lighten_amount: f32,
+) -> PreciseColor {
+    if shift_hue_amount == 0.0 && saturate_amount == 0.0 && lighten_amount == 0.0 {
+        return color;
+    }
+
+    let srgb_color = Srgb::new(color[0] as f32, color[1] as f32, color[2] as f32);
+
+    let modified_color: Srgb = match color_space {
+        ValidColorSpace::Hsl => Hsl::from_color(srgb_color)
+            .shift_hue(shift_hue_amount)
+            .saturate(saturate_amount)
+            .lighten(lighten_amount)
+            .into_color(),
+        ValidColorSpace::Hsluv => Hsluv::from_color(srgb_color)
+            .shift_hue(shift_hue_amount)
+            .saturate(saturate_amount)
+            .lighten(lighten_amount)
+            .into_color(),
+        ValidColorSpace::Lch => Lch::from_color(srgb_color)
+            .shift_hue(shift_hue_amount)
+            .saturate(saturate_amount)
+            .lighten(lighten_amount)
+            .into_color(),
+        ValidColorSpace::Okhsl => Okhsl::from_color(srgb_color)
+            .shift_hue(shift_hue_amount)
+            .saturate(saturate_amount)
+            .lighten(lighten_amount)
+            .into_color(),
+    };
+
+    [
+        f64::from(modified_color.red).clamp(0.0, 1.0),
+        f64::from(modified_color.green).clamp(0.0, 1.0),
+        f64::from(modified_color.blue).clamp(0.0, 1.0),
+    ]
+}
+
+/// Decodes one sRGB-encoded channel in [0, 1] to linear light (the IEC
+/// 61966-2-1 transfer function).
+fn srgb_to_linear(encoded: f64) -> f64 {
+    if encoded <= 0.04045 {
+        encoded / 12.92
+    } else {
+        ((encoded + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Encodes one linear-light channel in [0, 1] back to sRGB (the inverse of
+/// `srgb_to_linear`).
+fn linear_to_srgb(linear: f64) -> f64 {
+    if linear <= 0.0031308 {
+        linear * 12.92
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// A resolved palette: a built-in table, or one built at runtime (imported,
+/// or compiled from a gradient or a named palette) and shared with the
+/// registry that holds it.
+#[derive(Clone)]
+enum PaletteRef {
+    Builtin(&'static Palette),
+    Shared(Arc<Palette>),
+}
+
+impl std::ops::Deref for PaletteRef {
+    type Target = Palette;
+
+    fn deref(&self) -> &Palette {
+        match self {
+            PaletteRef::Builtin(palette) => palette,
+            PaletteRef::Shared(palette) => palette,
+        }
+    }
+}
+
 /// Determines the color palette to use based on the given color scheme and reverse colors option.
 ///
 /// # Parameters
-/// - `color_scheme`: The name of the color scheme to use.
+/// - `color_scheme`: The name of a built-in or imported color scheme.
 /// - `reverse_colors`: Whether to reverse the colors of the color scheme.
 ///
 /// # Returns
 /// A tuple containing the selected color palette, whether the colors should
 /// be reversed, and whether the palette is cyclical (starts and ends on the
 /// same color, so repeats of it tile seamlessly).
-fn get_color_palette(color_scheme: &str, reverse_colors: bool) -> (&'static Palette, bool, bool) {
+fn get_color_palette(color_scheme: &str, reverse_colors: bool) -> (PaletteRef, bool, bool) {
     static FALLBACK_PALETTE: Lazy<Palette> = Lazy::new(|| Palette::Original(colorous::TURBO));
 
+    if let Some((palette, is_cyclic)) = palette_import::imported_palette(color_scheme) {
+        return (PaletteRef::Shared(palette), reverse_colors, is_cyclic);
+    }
+
     let palette = COLOR_PALETTES
         .get(color_scheme)
         .or_else(|| REVERSE_COLOR_PALETTES.get(color_scheme))
@@ -2323,16 +2812,64 @@ fn get_color_palette(color_scheme: &str, reverse_colors: bool) -> (&'static Pale
 
     let is_cyclic = matches!(color_scheme, "rainbow" | "sinebow");
 
-    (palette, should_reverse_colors, is_cyclic)
+    (
+        PaletteRef::Builtin(palette),
+        should_reverse_colors,
+        is_cyclic,
+    )
 }
 
-/// Remaps a normalized palette position through a histogram-equalization
-/// lookup table (a monotone CDF over the palette window, sampled uniformly
-/// across [0, 1]) with linear interpolation between entries. The client
-/// builds one table per viewport from the visible escape-value distribution,
-/// so equal palette spans cover equal visible pixel mass (histogram
-/// coloring). Runs after the linear min/max normalization and before
-/// `apply_color_cycles`, so the palette window keeps its clamping meaning and
+/// A built-in or imported palette rebuilt with its control points blended
+/// in `interpolation` space (see `ColoringOptions::palette_interpolation`),
+/// or `None` for sRGB, the space every named palette is defined in.
+fn interpolated_palette(color_scheme: &str, interpolation: InterpolationSpace) -> Option<Palette> {
+    if interpolation == InterpolationSpace::Srgb {
+        return None;
+    }
+    let points = match color_scheme {
+        "jet" => JET_POINTS.to_vec(),
+        "nipySpectral" => NIPY_SPECTRAL_POINTS.to_vec(),
+        _ => get_color_palette(color_scheme, false).0.control_points(),
+    };
+    Some(palette_from_control_points(interpolation, &points))
+}
+
+/// A palette resolved once for a render or recolor, with the flags
+/// `get_color_palette` returns for it, so per-pixel loops never look a
+/// palette up by name.
+#[derive(Clone)]
+struct SelectedPalette {
+    palette: PaletteRef,
+    reverse: bool,
+    cyclic: bool,
+}
+
+impl SelectedPalette {
+    /// Resolves a built-in or imported palette name (see
+    /// `get_color_palette`).
+    fn named(color_scheme: &str, reverse_colors: bool) -> Self {
+        let (palette, reverse, cyclic) = get_color_palette(color_scheme, reverse_colors);
+        SelectedPalette {
+            palette,
+            reverse,
+            cyclic,
+        }
+    }
+
+    /// The palette, whether to reverse it, and whether it is cyclic, in the
+    /// order `get_color_palette` returns them.
+    fn parts(&self) -> (&Palette, bool, bool) {
+        (&self.palette, self.reverse, self.cyclic)
+    }
+}
+
+/// Remaps a normalized palette position through a histogram-equalization
+/// lookup table (a monotone CDF over the palette window, sampled uniformly
+/// across [0, 1]) with linear interpolation between entries. The client
+/// builds one table per viewport from the visible escape-value distribution,
+/// so equal palette spans cover equal visible pixel mass (histogram
+/// coloring). Runs after the linear min/max normalization and before
+/// `apply_color_cycles`, so the palette window keeps its clamping meaning and
 /// color cycles become mass-uniform. Tables with fewer than two entries
 /// cannot be interpolated and fall back to the identity (linear) mapping.
 fn apply_palette_cdf(norm: f64, cdf: &[f32]) -> f64 {
@@ -2349,6 +2886,63 @@ fn apply_palette_cdf(norm: f64, cdf: &[f32]) -> f64 {
     (start + (end - start) * fraction).clamp(0.0, 1.0)
 }
 
+/// Transfer curve between the normalized escape value and the palette
+/// position. Field values mirror the client's camelCase payload.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum TransferFunction {
+    #[default]
+    Linear,
+    Sqrt,
+    CubeRoot,
+    /// Logarithm of the iteration count above the palette minimum, which
+    /// tames the long tails of deep views.
+    Log,
+    /// The logarithm applied twice, for views spanning many orders of
+    /// magnitude of iterations.
+    LogLog,
+    /// `position^gamma`, with `ColoringOptions::transfer_gamma`.
+    Power,
+}
+
+/// A `TransferFunction` resolved against one coloring's parameters (see
+/// `ColoringOptions::palette_transfer`), ready to apply per pixel.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PaletteTransfer {
+    Linear,
+    Sqrt,
+    CubeRoot,
+    /// `scale` is the palette window's span, in the cached values' units.
+    Log {
+        scale: f64,
+    },
+    LogLog {
+        scale: f64,
+    },
+    Power {
+        gamma: f64,
+    },
+}
+
+impl PaletteTransfer {
+    /// Remaps a normalized palette position in [0, 1], keeping both ends
+    /// fixed. Runs after the min/max normalization and histogram
+    /// equalization and before `apply_color_cycles`, so the window keeps
+    /// its clamping meaning and color cycles repeat the reshaped ramp.
+    fn apply(self, norm: f64) -> f64 {
+        match self {
+            PaletteTransfer::Linear => norm,
+            PaletteTransfer::Sqrt => norm.sqrt(),
+            PaletteTransfer::CubeRoot => norm.cbrt(),
+            PaletteTransfer::Log { scale } => (scale * norm).ln_1p() / scale.ln_1p(),
+            PaletteTransfer::LogLog { scale } => {
+                (scale * norm).ln_1p().ln_1p() / scale.ln_1p().ln_1p()
+            }
+            PaletteTransfer::Power { gamma } => norm.powf(gamma),
+        }
+    }
+}
+
 /// A triangle wave over palette positions: ascend through the palette, then
 /// descend, with period 2 (in palette lengths). Continuous everywhere — the
 /// folds are reflections, not jumps — which is what makes repeated
@@ -2502,9 +3096,94 @@ fn color_from_smoothed_value(
     max_iterations_threshold: f64,
     palette_cdf: Option<&[f32]>,
     palette_offset: f64,
+    palette_transfer: PaletteTransfer,
 ) -> RgbColor {
-    if !smoothed_value.is_finite() {
+    let Some(position) = palette_position(
+        smoothed_value,
+        should_reverse_colors,
+        palette_is_cyclic,
+        color_cycles,
+        min_iterations_threshold,
+        max_iterations_threshold,
+        palette_cdf,
+        palette_offset,
+        palette_transfer,
+    ) else {
         return [0, 0, 0];
+    };
+
+    let color = palette.eval_continuous(position);
+
+    transform_color(
+        color,
+        color_space,
+        shift_hue_amount,
+        saturate_amount,
+        lighten_amount,
+    )
+    .as_array()
+}
+
+/// `color_from_smoothed_value` without quantization: the same palette
+/// position and color-space adjustments, with the palette sampled by
+/// `Palette::eval_continuous_precise`. Feeds the 16-bit and floating-point
+/// outputs (`recolor_values_rgba16`, `recolor_values_linear_rgb`).
+fn precise_color_from_smoothed_value(
+    smoothed_value: f64,
+    palette: &Palette,
+    should_reverse_colors: bool,
+    palette_is_cyclic: bool,
+    color_cycles: u32,
+    color_space: &ValidColorSpace,
+    shift_hue_amount: f32,
+    saturate_amount: f32,
+    lighten_amount: f32,
+    min_iterations_threshold: f64,
+    max_iterations_threshold: f64,
+    palette_cdf: Option<&[f32]>,
+    palette_offset: f64,
+    palette_transfer: PaletteTransfer,
+) -> PreciseColor {
+    let Some(position) = palette_position(
+        smoothed_value,
+        should_reverse_colors,
+        palette_is_cyclic,
+        color_cycles,
+        min_iterations_threshold,
+        max_iterations_threshold,
+        palette_cdf,
+        palette_offset,
+        palette_transfer,
+    ) else {
+        return [0.0, 0.0, 0.0];
+    };
+
+    transform_color_precise(
+        palette.eval_continuous_precise(position),
+        color_space,
+        shift_hue_amount,
+        saturate_amount,
+        lighten_amount,
+    )
+}
+
+/// The position in [0, 1] at which a smoothed escape value samples the
+/// palette, shared by every output depth: min/max normalization, then
+/// histogram equalization, the transfer curve, color cycles and reversal.
+/// `None` for non-finite (interior) values, which render black.
+fn palette_position(
+    smoothed_value: f64,
+    should_reverse_colors: bool,
+    palette_is_cyclic: bool,
+    color_cycles: u32,
+    min_iterations_threshold: f64,
+    max_iterations_threshold: f64,
+    palette_cdf: Option<&[f32]>,
+    palette_offset: f64,
+    palette_transfer: PaletteTransfer,
+) -> Option<f64> {
+    if !smoothed_value.is_finite() {
+        return None;
     }
 
     // Normalize the value between min and max thresholds to get 0.0 to 1.0
@@ -2524,22 +3203,15 @@ fn color_from_smoothed_value(
         norm = apply_palette_cdf(norm, cdf);
     }
 
+    norm = palette_transfer.apply(norm);
+
     norm = apply_color_cycles(norm, color_cycles, palette_is_cyclic, palette_offset);
 
     if should_reverse_colors {
         norm = 1.0 - norm;
     }
 
-    let color = palette.eval_continuous(norm);
-
-    transform_color(
-        color,
-        color_space,
-        shift_hue_amount,
-        saturate_amount,
-        lighten_amount,
-    )
-    .as_array()
+    Some(norm)
 }
 
 /// Maps an escape-time result to a color. Production code composes
@@ -2581,6 +3253,7 @@ fn color_from_escape_result(
         max_iterations_threshold,
         None,
         0.0,
+        PaletteTransfer::Linear,
     )
 }
 
@@ -2630,6 +3303,7 @@ fn render_mandelbrot_set(
     palette_max_iter: i32,
     palette_cdf: Option<&[f32]>,
     palette_offset: f64,
+    palette_transfer: PaletteTransfer,
 ) -> RenderedTile {
     let output_size: usize = image_width * image_height * NUM_COLOR_CHANNELS;
     let mut img: Vec<u8> = vec![0; output_size];
@@ -2673,6 +3347,7 @@ fn render_mandelbrot_set(
             max_iterations_threshold,
             palette_cdf,
             palette_offset,
+            palette_transfer,
         );
 
         let index = pixel_index * NUM_COLOR_CHANNELS;
@@ -2682,76 +3357,169 @@ fn render_mandelbrot_set(
     };
 
     // Tiles render via Mariani–Silver subdivision over the lane-refilling
-    // stream kernel matching the exponent (quadratic or general); non-wasm
-    // builds use the fixed-batch loop instead.
-    #[cfg(target_arch = "wasm32")]
-    {
-        let im_values: Vec<f64> = im_range.collect();
-        let mut results =
-            vec![(UNCOMPUTED, Complex64::new(0.0, 0.0)); re_values.len() * im_values.len()];
-        stream_tile_subdivided(
-            &re_values,
-            &im_values,
-            max_iterations,
-            exponent,
-            &mut results,
-        );
+    // stream kernel matching the exponent (quadratic or general) on wasm32,
+    // and over the fixed-batch loops natively; each row band subdivides on
+    // its own (see `for_each_row_band`).
+    let im_values: Vec<f64> = im_range.collect();
+    let mut results =
+        vec![(UNCOMPUTED, Complex64::new(0.0, 0.0)); re_values.len() * im_values.len()];
+    complete_tile_subdivided(
+        &re_values,
+        &im_values,
+        max_iterations,
+        exponent,
+        &mut results,
+    );
 
-        for (pixel_index, &(escape_iterations, z)) in results.iter().enumerate() {
-            write_pixel(pixel_index, escape_iterations, z);
+    for (pixel_index, &(escape_iterations, z)) in results.iter().enumerate() {
+        write_pixel(pixel_index, escape_iterations, z);
+    }
+
+    // Both callers of this loop are the direct f64 path.
+    RenderedTile {
+        image: img,
+        values,
+        stats,
+        tier: RenderTier::Direct,
+        escape_results: Vec::new(),
+        antialiased: Vec::new(),
+    }
+}
+
+// Adaptive anti-aliasing resamples a pixel when a neighbor's escape value
+// lies at least this far away, measured in palette lengths (the value
+// difference over the palette window, times the color cycles). A thirty-
+// second of the palette is a clearly visible color step on every palette,
+// while smooth gradients step far less between neighbors.
+const ADAPTIVE_AA_THRESHOLD: f64 = 1.0 / 32.0;
+
+/// Deterministic jitter in [0, 1) for sample `sample` of the pixel at
+/// (column, row), one independent value per `axis`. A fixed hash rather than
+/// an RNG keeps renders reproducible, so a re-render or a neighboring tile
+/// never shimmers.
+fn sample_jitter(column: usize, row: usize, sample: u32, axis: u32) -> f64 {
+    let mut hash = (column as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+        ^ (row as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f)
+        ^ u64::from(sample * 2 + axis).wrapping_mul(0x1656_67b1_9e37_79f9);
+    // SplitMix64 finalizer.
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^= hash >> 31;
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Whether two neighboring cached values differ enough for adaptive
+/// anti-aliasing to resample the pixel: one interior and one exterior, or
+/// exterior values at least `ADAPTIVE_AA_THRESHOLD` palette lengths apart.
+fn values_differ_strongly(
+    value: f32,
+    neighbor: f32,
+    palette_thresholds: (f64, f64),
+    color_cycles: u32,
+) -> bool {
+    match (value.is_finite(), neighbor.is_finite()) {
+        (true, true) => {
+            let (min_threshold, max_threshold) = palette_thresholds;
+            let span = (f64::from(value) - f64::from(neighbor)).abs()
+                / (max_threshold - min_threshold)
+                * f64::from(color_cycles.max(1));
+            span >= ADAPTIVE_AA_THRESHOLD
         }
+        (false, false) => false,
+        _ => true,
     }
+}
 
-    #[cfg(not(target_arch = "wasm32"))]
-    for (x, im) in im_range.enumerate() {
-        let mut y = 0;
-        while y + 3 < re_values.len() {
-            let results = calculate_escape_iterations_quad(
-                [
-                    (re_values[y], im),
-                    (re_values[y + 1], im),
-                    (re_values[y + 2], im),
-                    (re_values[y + 3], im),
-                ],
-                max_iterations,
-                exponent,
-            );
+/// Anti-aliases a rendered tile in place. Each selected pixel is replaced by
+/// the average of a `grid x grid` jittered (stratified) set of samples inside
+/// its footprint, averaged in linear light — averaging sRGB-encoded values
+/// would darken every edge. With `adaptive` only pixels that
+/// `values_differ_strongly` from one of their eight neighbors are resampled
+/// (using `supersampling`, at least 2, as the grid); otherwise every pixel
+/// is, at `supersampling` (a factor of 0 or 1 leaves the tile untouched).
+///
+/// `sample_value` maps a fractional pixel position (column, row) to the
+/// tile's value domain (smoothed escape value, DE brightness, ...) and
+/// `color_of` maps such a value to its unquantized color. The cached
+/// `values` keep the single center sample, so recoloring an anti-aliased
+/// tile reproduces the aliased image; changing the factor needs a re-render.
+/// Tiles with no finite value are interior (see `rect_in_set`) and skipped.
+fn antialias_tile(
+    mut rendered: RenderedTile,
+    image_width: usize,
+    image_height: usize,
+    supersampling: u32,
+    adaptive: bool,
+    palette_thresholds: (f64, f64),
+    color_cycles: u32,
+    sample_value: impl Fn(f64, f64) -> f64,
+    color_of: impl Fn(f64) -> PreciseColor,
+) -> RenderedTile {
+    let grid = if adaptive {
+        supersampling.max(2)
+    } else {
+        supersampling
+    };
+    if grid <= 1 || !rendered.values.iter().any(|value| value.is_finite()) {
+        return rendered;
+    }
 
-            for (lane, &(escape_iterations, z)) in results.iter().enumerate() {
-                write_pixel(x * image_width + y + lane, escape_iterations, z);
+    let values = &rendered.values;
+    let needs_resample = |column: usize, row: usize| {
+        if !adaptive {
+            return true;
+        }
+        let value = values[row * image_width + column];
+        for neighbor_row in row.saturating_sub(1)..(row + 2).min(image_height) {
+            for neighbor_column in column.saturating_sub(1)..(column + 2).min(image_width) {
+                let neighbor = values[neighbor_row * image_width + neighbor_column];
+                if values_differ_strongly(value, neighbor, palette_thresholds, color_cycles) {
+                    return true;
+                }
             }
-
-            y += 4;
         }
-        while y + 1 < re_values.len() {
-            let results = calculate_escape_iterations_pair(
-                (re_values[y], im),
-                (re_values[y + 1], im),
-                max_iterations,
-                exponent,
-            );
+        false
+    };
 
-            for (lane, &(escape_iterations, z)) in results.iter().enumerate() {
-                write_pixel(x * image_width + y + lane, escape_iterations, z);
+    let mut resampled: Vec<(usize, PreciseColor)> = Vec::new();
+    for row in 0..image_height {
+        for column in 0..image_width {
+            if !needs_resample(column, row) {
+                continue;
             }
 
-            y += 2;
-        }
+            let mut linear_sum = [0.0; 3];
+            for sample_row in 0..grid {
+                for sample_column in 0..grid {
+                    let sample = sample_row * grid + sample_column;
+                    let offset = |cell: u32, axis: u32| {
+                        (f64::from(cell) + sample_jitter(column, row, sample, axis))
+                            / f64::from(grid)
+                            - 0.5
+                    };
+                    let color = color_of(sample_value(
+                        column as f64 + offset(sample_column, 0),
+                        row as f64 + offset(sample_row, 1),
+                    ));
+                    for channel in 0..3 {
+                        linear_sum[channel] += srgb_to_linear(color[channel]);
+                    }
+                }
+            }
 
-        if y < re_values.len() {
-            let (escape_iterations, z) =
-                calculate_escape_iterations(re_values[y], im, max_iterations, exponent);
-            write_pixel(x * image_width + y, escape_iterations, z);
+            let sample_count = f64::from(grid * grid);
+            let color = linear_sum.map(|sum| linear_to_srgb(sum / sample_count).clamp(0.0, 1.0));
+            resampled.push((row * image_width + column, color));
         }
     }
 
-    // Both callers of this loop are the direct f64 path.
-    RenderedTile {
-        image: img,
-        values,
-        stats,
-        tier: RenderTier::Direct,
+    for &(pixel_index, color) in &resampled {
+        let index = pixel_index * NUM_COLOR_CHANNELS;
+        let pixel = color.map(|channel| (channel * 255.0).round() as u8);
+        rendered.image[index..index + 3].copy_from_slice(&pixel);
     }
+    rendered.antialiased = resampled;
+    rendered
 }
 
 /// Creates a solid black image
@@ -2774,8 +3542,7 @@ fn generate_mandelbrot_set_image(
     exponent: u32,
     image_width: usize,
     image_height: usize,
-    color_scheme: &str,
-    reverse_colors: bool,
+    selected_palette: &SelectedPalette,
     shift_hue_amount: f32,
     saturate_amount: f32,
     lighten_amount: f32,
@@ -2786,9 +3553,9 @@ fn generate_mandelbrot_set_image(
     color_cycles: u32,
     palette_cdf: Option<&[f32]>,
     palette_offset: f64,
+    palette_transfer: PaletteTransfer,
 ) -> RenderedTile {
-    let (palette, should_reverse_colors, palette_is_cyclic) =
-        get_color_palette(color_scheme, reverse_colors);
+    let (palette, should_reverse_colors, palette_is_cyclic) = selected_palette.parts();
 
     let re_range = linspace(re_min, re_max, image_width);
     let im_range = linspace(im_max, im_min, image_height);
@@ -2817,6 +3584,7 @@ fn generate_mandelbrot_set_image(
         palette_max_iter,
         palette_cdf,
         palette_offset,
+        palette_transfer,
     )
 }
 
@@ -2847,17 +3615,16 @@ fn generate_distance_estimate_image(
     exponent: u32,
     image_width: usize,
     image_height: usize,
-    color_scheme: &str,
-    reverse_colors: bool,
+    selected_palette: &SelectedPalette,
     shift_hue_amount: f32,
     saturate_amount: f32,
     lighten_amount: f32,
     color_space: ValidColorSpace,
     color_cycles: u32,
     palette_offset: f64,
+    palette_transfer: PaletteTransfer,
 ) -> RenderedTile {
-    let (palette, should_reverse_colors, palette_is_cyclic) =
-        get_color_palette(color_scheme, reverse_colors);
+    let (palette, should_reverse_colors, palette_is_cyclic) = selected_palette.parts();
 
     let re_range = linspace(re_min, re_max, image_width);
     let im_range = linspace(im_max, im_min, image_height);
@@ -2910,6 +3677,7 @@ fn generate_distance_estimate_image(
                 1.0,
                 None,
                 palette_offset,
+                palette_transfer,
             );
 
             let index = pixel_index * NUM_COLOR_CHANNELS;
@@ -2926,6 +3694,8 @@ fn generate_distance_estimate_image(
         // iteration range to auto-fit.
         stats: TileIterationStats::default(),
         tier: RenderTier::Direct,
+        escape_results: Vec::new(),
+        antialiased: Vec::new(),
     }
 }
 
@@ -2958,17 +3728,16 @@ fn generate_atom_domain_image(
     max_iterations: u32,
     image_width: usize,
     image_height: usize,
-    color_scheme: &str,
-    reverse_colors: bool,
+    selected_palette: &SelectedPalette,
     shift_hue_amount: f32,
     saturate_amount: f32,
     lighten_amount: f32,
     color_space: ValidColorSpace,
     color_cycles: u32,
     palette_offset: f64,
+    palette_transfer: PaletteTransfer,
 ) -> RenderedTile {
-    let (palette, should_reverse_colors, palette_is_cyclic) =
-        get_color_palette(color_scheme, reverse_colors);
+    let (palette, should_reverse_colors, palette_is_cyclic) = selected_palette.parts();
 
     let re_range = linspace(re_min, re_max, image_width);
     let im_range = linspace(im_max, im_min, image_height);
@@ -3019,6 +3788,7 @@ fn generate_atom_domain_image(
                 1.0,
                 None,
                 palette_offset,
+                palette_transfer,
             );
 
             let index = pixel_index * NUM_COLOR_CHANNELS;
@@ -3035,6 +3805,91 @@ fn generate_atom_domain_image(
         // no iteration range to auto-fit.
         stats: TileIterationStats::default(),
         tier: RenderTier::Direct,
+        escape_results: Vec::new(),
+        antialiased: Vec::new(),
+    }
+}
+
+/// Whether every pixel on the border of a transformed tile (`point_at`
+/// maps a pixel position to its point) is in the set: the rotated
+/// counterpart of `rect_in_set`. Tiles must be at least one pixel wide and
+/// high.
+fn transformed_border_in_set(
+    image_width: usize,
+    image_height: usize,
+    max_iterations: u32,
+    exponent: u32,
+    point_at: impl Fn(f64, f64) -> Complex64,
+) -> bool {
+    let pixel_point = |column: usize, row: usize| {
+        let c = point_at(column as f64, row as f64);
+        (c.re, c.im)
+    };
+    let (last_column, last_row) = (image_width - 1, image_height - 1);
+    (0..image_width).all(|column| {
+        points_in_set_pair(
+            pixel_point(column, 0),
+            pixel_point(column, last_row),
+            max_iterations,
+            exponent,
+        )
+    }) && (0..image_height).all(|row| {
+        points_in_set_pair(
+            pixel_point(0, row),
+            pixel_point(last_column, row),
+            max_iterations,
+            exponent,
+        )
+    })
+}
+
+/// Renders a direct-path tile whose pixel grid is rotated or skewed (see
+/// `perturbation::ViewTransform`), in any mode. `sample` maps a pixel
+/// position to its cached value and escape iterations, and `color_of` maps
+/// a value to its color.
+///
+/// A plain scalar loop, like the distance-estimate renderer: the streaming
+/// kernels walk an axis-aligned re x im grid, which a transformed view is
+/// not. Interior tiles never reach it: `progressive::PixelSampler` checks
+/// their (parallelogram) border with `transformed_border_in_set`.
+fn render_transformed_tile(
+    image_width: usize,
+    image_height: usize,
+    max_iterations: u32,
+    sample: impl Fn(f64, f64) -> (f64, u32),
+    color_of: impl Fn(f64) -> RgbColor,
+) -> RenderedTile {
+    let output_size: usize = image_width * image_height * NUM_COLOR_CHANNELS;
+    let mut img: Vec<u8> = vec![0; output_size];
+    let mut values: Vec<f32> = vec![f32::INFINITY; image_width * image_height];
+    let mut stats = TileIterationStats::default();
+    for alpha_idx in (3..output_size).step_by(NUM_COLOR_CHANNELS) {
+        img[alpha_idx] = 255;
+    }
+
+    for row in 0..image_height {
+        for col in 0..image_width {
+            let (value, escape_iterations) = sample(col as f64, row as f64);
+            stats.record(escape_iterations, max_iterations);
+
+            let pixel_index = row * image_width + col;
+            values[pixel_index] = value as f32;
+
+            let pixel = color_of(value);
+            let index = pixel_index * NUM_COLOR_CHANNELS;
+            img[index] = pixel[0];
+            img[index + 1] = pixel[1];
+            img[index + 2] = pixel[2];
+        }
+    }
+
+    RenderedTile {
+        image: img,
+        values,
+        stats,
+        tier: RenderTier::Direct,
+        escape_results: Vec::new(),
+        antialiased: Vec::new(),
     }
 }
 
@@ -3066,8 +3921,7 @@ fn generate_julia_image(
     exponent: u32,
     image_width: usize,
     image_height: usize,
-    color_scheme: &str,
-    reverse_colors: bool,
+    selected_palette: &SelectedPalette,
     shift_hue_amount: f32,
     saturate_amount: f32,
     lighten_amount: f32,
@@ -3078,9 +3932,9 @@ fn generate_julia_image(
     color_cycles: u32,
     palette_cdf: Option<&[f32]>,
     palette_offset: f64,
+    palette_transfer: PaletteTransfer,
 ) -> RenderedTile {
-    let (palette, should_reverse_colors, palette_is_cyclic) =
-        get_color_palette(color_scheme, reverse_colors);
+    let (palette, should_reverse_colors, palette_is_cyclic) = selected_palette.parts();
 
     // The starting point z0 sweeps the fixed window; im runs top-to-bottom so
     // the thumbnail's orientation matches the map (increasing im upward).
@@ -3144,6 +3998,7 @@ fn generate_julia_image(
                 max_iterations_threshold,
                 palette_cdf,
                 palette_offset,
+                palette_transfer,
             );
 
             let index = pixel_index * NUM_COLOR_CHANNELS;
@@ -3158,21 +4013,30 @@ fn generate_julia_image(
         values,
         stats,
         tier: RenderTier::Direct,
+        escape_results: Vec::new(),
+        antialiased: Vec::new(),
     }
 }
 
-#[wasm_bindgen]
-pub fn get_mandelbrot_set_image(
-    re_min: f64,
-    re_max: f64,
-    im_min: f64,
-    im_max: f64,
+/// Renders an exponential-map (log-polar) strip around the world origin (see
+/// `perturbation::ExponentialMapFrame` for the geometry): x is the angle, y
+/// the log-radius from `outer_radius_log2` inward. One tall strip covers
+/// many zoom decades, and a zoom video's frames are reprojections of it
+/// rather than separate renders. Escape-time coloring only — the
+/// distance-estimate and atom-domain kernels are direct-f64 only.
+///
+/// A plain scalar loop over the float-exp perturbation kernel: each row's
+/// deltas have their own magnitude, which the tile kernels' shared
+/// per-frame scaling cannot express.
+fn generate_exponential_map_image(
+    origin_re: &str,
+    origin_im: &str,
+    outer_radius_log2: f64,
     max_iterations: u32,
     exponent: u32,
     image_width: usize,
     image_height: usize,
-    color_scheme: String,
-    reverse_colors: bool,
+    selected_palette: &SelectedPalette,
     shift_hue_amount: f32,
     saturate_amount: f32,
     lighten_amount: f32,
@@ -3180,37 +4044,141 @@ pub fn get_mandelbrot_set_image(
     smooth_coloring: bool,
     palette_min_iter: i32,
     palette_max_iter: i32,
-) -> Vec<u8> {
-    generate_mandelbrot_set_image(
-        re_min,
-        re_max,
-        im_min,
-        im_max,
-        max_iterations,
-        exponent,
+    color_cycles: u32,
+    palette_cdf: Option<&[f32]>,
+    palette_offset: f64,
+    palette_transfer: PaletteTransfer,
+) -> RenderedTile {
+    let frame = match perturbation::ExponentialMapFrame::new(
+        origin_re,
+        origin_im,
+        outer_radius_log2,
         image_width,
         image_height,
-        &color_scheme,
-        reverse_colors,
-        shift_hue_amount,
-        saturate_amount,
-        lighten_amount,
-        color_space,
-        smooth_coloring,
-        palette_min_iter,
-        palette_max_iter,
-        // Signature predates the color-cycles control; a single palette pass
-        // matches its historical output.
-        1,
-        // Frozen signature: no histogram equalization, linear mapping, no
-        // palette offset.
-        None,
-        0.0,
-    )
-    .image
-}
-
-/// Renders a Mandelbrot set image at any zoom depth, returning the RGBA
+        max_iterations,
+        exponent,
+        ESCAPE_RADIUS,
+    ) {
+        Ok(frame) => frame,
+        Err(_) => {
+            return RenderedTile::solid_black(image_width, image_height, RenderTier::FloatExp)
+        }
+    };
+
+    let (palette, should_reverse_colors, palette_is_cyclic) = selected_palette.parts();
+
+    let min_iterations_threshold = f64::from(palette_min_iter);
+    let max_iterations_threshold =
+        f64::from(palette_max_iter).max(min_iterations_threshold + f64::EPSILON);
+
+    let output_size: usize = image_width * image_height * NUM_COLOR_CHANNELS;
+    let mut img: Vec<u8> = vec![0; output_size];
+    let mut values: Vec<f32> = vec![f32::INFINITY; image_width * image_height];
+    let mut stats = TileIterationStats::default();
+    for alpha_idx in (3..output_size).step_by(NUM_COLOR_CHANNELS) {
+        img[alpha_idx] = 255;
+    }
+
+    for row in 0..image_height {
+        for col in 0..image_width {
+            let (escape_iterations, z) = frame.escape_iterations(col, row);
+            stats.record(escape_iterations, max_iterations);
+
+            let smoothed_value = smoothed_escape_value(
+                escape_iterations,
+                z,
+                max_iterations,
+                exponent,
+                smooth_coloring,
+            );
+
+            let pixel_index = row * image_width + col;
+            values[pixel_index] = smoothed_value as f32;
+
+            let pixel = color_from_smoothed_value(
+                smoothed_value,
+                palette,
+                should_reverse_colors,
+                palette_is_cyclic,
+                color_cycles,
+                &color_space,
+                shift_hue_amount,
+                saturate_amount,
+                lighten_amount,
+                min_iterations_threshold,
+                max_iterations_threshold,
+                palette_cdf,
+                palette_offset,
+                palette_transfer,
+            );
+
+            let index = pixel_index * NUM_COLOR_CHANNELS;
+            img[index] = pixel[0];
+            img[index + 1] = pixel[1];
+            img[index + 2] = pixel[2];
+        }
+    }
+
+    RenderedTile {
+        image: img,
+        values,
+        stats,
+        tier: RenderTier::FloatExp,
+        escape_results: Vec::new(),
+        antialiased: Vec::new(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn get_mandelbrot_set_image(
+    re_min: f64,
+    re_max: f64,
+    im_min: f64,
+    im_max: f64,
+    max_iterations: u32,
+    exponent: u32,
+    image_width: usize,
+    image_height: usize,
+    color_scheme: String,
+    reverse_colors: bool,
+    shift_hue_amount: f32,
+    saturate_amount: f32,
+    lighten_amount: f32,
+    color_space: ValidColorSpace,
+    smooth_coloring: bool,
+    palette_min_iter: i32,
+    palette_max_iter: i32,
+) -> Vec<u8> {
+    generate_mandelbrot_set_image(
+        re_min,
+        re_max,
+        im_min,
+        im_max,
+        max_iterations,
+        exponent,
+        image_width,
+        image_height,
+        &SelectedPalette::named(&color_scheme, reverse_colors),
+        shift_hue_amount,
+        saturate_amount,
+        lighten_amount,
+        color_space,
+        smooth_coloring,
+        palette_min_iter,
+        palette_max_iter,
+        // Signature predates the color-cycles control; a single palette pass
+        // matches its historical output.
+        1,
+        // Frozen signature: no histogram equalization, linear mapping, no
+        // palette offset.
+        None,
+        0.0,
+        PaletteTransfer::Linear,
+    )
+    .image
+}
+
+/// Renders a Mandelbrot set image at any zoom depth, returning the RGBA
 /// bytes plus the iteration stats observed while rendering.
 ///
 /// The view is described by an arbitrary-precision world origin (decimal
@@ -3220,41 +4188,19 @@ pub fn get_mandelbrot_set_image(
 /// origin. Views whose pixel spacing leaves f64 coordinates enough headroom
 /// use the direct f64 renderer; finer views use perturbation theory with an
 /// arbitrary-precision reference orbit, so zoom depth is not limited by f64
-/// precision.
+/// precision. `sampler` holds the view and the path it renders by (see
+/// `progressive::PixelSampler`); whole untransformed direct tiles run the
+/// streaming kernels, every other tile the sampler's own.
 fn render_tile_precise(
-    origin_re: &str,
-    origin_im: &str,
-    tile_x_min: f64,
-    tile_x_max: f64,
-    tile_y_min: f64,
-    tile_y_max: f64,
-    tile_zoom: i32,
-    zoom_offset: u32,
-    max_iterations: u32,
-    exponent: u32,
-    image_width: usize,
-    image_height: usize,
-    color_scheme: &str,
-    reverse_colors: bool,
+    sampler: &progressive::PixelSampler,
+    selected_palette: &SelectedPalette,
     shift_hue_amount: f32,
     saturate_amount: f32,
     lighten_amount: f32,
     color_space: ValidColorSpace,
-    smooth_coloring: bool,
     palette_min_iter: i32,
     palette_max_iter: i32,
     color_cycles: u32,
-    // Distance-estimate rendering mode (issue #46). Only the direct f64 path
-    // honors it; deeper (perturbation/float-exp) tiles fall back to escape-time
-    // coloring gracefully — see the `distance_estimate` handling below and the
-    // client gating that keeps DE mode on shallow views.
-    distance_estimate: bool,
-    // Atom-domain rendering mode (issue #45): color each pixel by the iteration
-    // index of its orbit's nearest approach to the origin, visualizing the
-    // set's period structure. Like distance-estimate mode it is direct-f64 only
-    // and gated to shallow views by the client; deeper tiles fall back to
-    // escape-time coloring.
-    atom_domain: bool,
     // Optional histogram-equalization lookup table over the palette window
     // (see `apply_palette_cdf`); `None` keeps the linear mapping. Only the
     // escape-time paths consume it — the fixed-palette modes (distance
@@ -3264,205 +4210,352 @@ fn render_tile_precise(
     // Unlike the equalization table it applies in every mode — the
     // fixed-palette modes repeat and shift their palette the same way.
     palette_offset: f64,
+    // Transfer curve onto the palette (see `PaletteTransfer::apply`),
+    // likewise applied in every mode.
+    palette_transfer: PaletteTransfer,
+    // Anti-aliasing (see `antialias_tile`): the per-axis supersampling
+    // factor, and whether to resample only pixels on strong value edges.
+    // Every path and mode honors it.
+    supersampling: u32,
+    adaptive_antialiasing: bool,
 ) -> RenderedTile {
-    let pixel_spacing =
-        perturbation::pixel_spacing(tile_x_min, tile_x_max, tile_zoom, zoom_offset, image_width)
-            .min(perturbation::pixel_spacing(
-                tile_y_min,
-                tile_y_max,
-                tile_zoom,
-                zoom_offset,
-                image_height,
-            ));
-
-    let use_perturbation = pixel_spacing < perturbation::MIN_DIRECT_PIXEL_SPACING
-        && (2..=perturbation::MAX_PERTURBED_EXPONENT).contains(&exponent);
-
-    if !use_perturbation {
-        // Shallow view (or unsupported exponent): f64 has enough precision to
-        // compute the bounds directly.
-        let origin_re_f64: f64 = origin_re.parse().unwrap_or(0.0);
-        let origin_im_f64: f64 = origin_im.parse().unwrap_or(0.0);
-
-        let scaled_offset = |tile_coordinate: f64| {
-            float_exp::ldexp(
-                perturbation::tile_coordinate_offset(tile_coordinate, tile_zoom),
-                -(zoom_offset as i64),
-            )
-        };
-
-        let re_min = origin_re_f64 + scaled_offset(tile_x_min);
-        let re_max = origin_re_f64 + scaled_offset(tile_x_max);
-        let im_max = origin_im_f64 - scaled_offset(tile_y_min);
-        let im_min = origin_im_f64 - scaled_offset(tile_y_max);
-
-        if distance_estimate {
-            return generate_distance_estimate_image(
-                re_min,
-                re_max,
-                im_min,
-                im_max,
-                pixel_spacing,
-                max_iterations,
-                exponent,
-                image_width,
-                image_height,
-                color_scheme,
-                reverse_colors,
-                shift_hue_amount,
-                saturate_amount,
-                lighten_amount,
-                color_space,
-                color_cycles,
-                palette_offset,
-            );
-        }
-
-        if atom_domain {
-            return generate_atom_domain_image(
-                re_min,
-                re_max,
-                im_min,
-                im_max,
-                max_iterations,
-                image_width,
-                image_height,
-                color_scheme,
-                reverse_colors,
-                shift_hue_amount,
-                saturate_amount,
-                lighten_amount,
-                color_space,
-                color_cycles,
-                palette_offset,
-            );
-        }
-
-        return generate_mandelbrot_set_image(
-            re_min,
-            re_max,
-            im_min,
-            im_max,
-            max_iterations,
-            exponent,
-            image_width,
-            image_height,
-            color_scheme,
-            reverse_colors,
+    let (palette, should_reverse_colors, palette_is_cyclic) = selected_palette.parts();
+    let (image_width, image_height) = (sampler.image_width, sampler.image_height);
+    let (max_iterations, exponent) = (sampler.max_iterations, sampler.power);
+    // Distance-estimate (issue #46) and atom-domain (issue #45) values map
+    // over a fixed palette window. Only the direct f64 `z^power + c` path
+    // computes them; deeper tiles and formula tiles fall back to escape-time
+    // coloring gracefully, and the client keeps both modes gated to shallow
+    // views, so the fallback is a safety net rather than a normal path.
+    let fixed_palette = !sampler.escape_valued();
+
+    // Unquantized color of a resampled value; the fixed-palette modes map
+    // over their fixed `0..1` domain without equalization.
+    let min_iterations_threshold = f64::from(palette_min_iter);
+    let max_iterations_threshold =
+        f64::from(palette_max_iter).max(min_iterations_threshold + f64::EPSILON);
+    let palette_window = if fixed_palette {
+        (0.0, 1.0)
+    } else {
+        (min_iterations_threshold, max_iterations_threshold)
+    };
+    let window_cdf = if fixed_palette { None } else { palette_cdf };
+    let sample_color = |value: f64| {
+        precise_color_from_smoothed_value(
+            value,
+            palette,
+            should_reverse_colors,
+            palette_is_cyclic,
+            color_cycles,
+            &color_space,
             shift_hue_amount,
             saturate_amount,
             lighten_amount,
-            color_space,
-            smooth_coloring,
-            palette_min_iter,
-            palette_max_iter,
+            palette_window.0,
+            palette_window.1,
+            window_cdf,
+            palette_offset,
+            palette_transfer,
+        )
+    };
+    let tile_color = |value: f64| {
+        color_from_smoothed_value(
+            value,
+            palette,
+            should_reverse_colors,
+            palette_is_cyclic,
             color_cycles,
-            palette_cdf,
+            &color_space,
+            shift_hue_amount,
+            saturate_amount,
+            lighten_amount,
+            palette_window.0,
+            palette_window.1,
+            window_cdf,
             palette_offset,
-        );
-    }
+            palette_transfer,
+        )
+    };
 
-    // Distance-estimate and atom-domain modes are only implemented on the
-    // direct f64 path (each ships direct-first). A deep-zoom tile reaching here
-    // in either mode falls through to the escape-time perturbation renderer
-    // below — a graceful, if unstyled, image. The client keeps both modes gated
-    // to shallow views, so this fallback is a safety net rather than a normal
-    // path.
-    let _ = distance_estimate;
-    let _ = atom_domain;
-
-    // Which perturbation tier this view falls into, computed independently of
-    // frame construction so a failed frame (below) still reports the right
-    // tier to the diagnostics overlay.
-    let effective_zoom = tile_zoom as i64 + zoom_offset as i64;
-    let perturbation_tier = if perturbation::uses_float_exp(effective_zoom, exponent) {
-        RenderTier::FloatExp
-    } else {
-        RenderTier::Perturbation
+    let rendered = match &sampler.method {
+        progressive::SampleMethod::Interior(tier) => {
+            RenderedTile::solid_black(image_width, image_height, *tier)
+        }
+        progressive::SampleMethod::Direct {
+            grid,
+            pixel_spacing,
+            axes: Some(_),
+        } => {
+            let (re_min, re_max, im_min, im_max) = grid.axis_aligned_bounds();
+            if sampler.distance_estimate {
+                generate_distance_estimate_image(
+                    re_min,
+                    re_max,
+                    im_min,
+                    im_max,
+                    *pixel_spacing,
+                    max_iterations,
+                    exponent,
+                    image_width,
+                    image_height,
+                    selected_palette,
+                    shift_hue_amount,
+                    saturate_amount,
+                    lighten_amount,
+                    color_space,
+                    color_cycles,
+                    palette_offset,
+                    palette_transfer,
+                )
+            } else if sampler.atom_domain {
+                generate_atom_domain_image(
+                    re_min,
+                    re_max,
+                    im_min,
+                    im_max,
+                    max_iterations,
+                    image_width,
+                    image_height,
+                    selected_palette,
+                    shift_hue_amount,
+                    saturate_amount,
+                    lighten_amount,
+                    color_space,
+                    color_cycles,
+                    palette_offset,
+                    palette_transfer,
+                )
+            } else {
+                // The sampler already ruled out an interior tile, so this
+                // skips `generate_mandelbrot_set_image`'s `rect_in_set`.
+                render_mandelbrot_set(
+                    linspace(re_min, re_max, image_width),
+                    linspace(im_max, im_min, image_height),
+                    max_iterations,
+                    exponent,
+                    image_width,
+                    image_height,
+                    palette,
+                    should_reverse_colors,
+                    palette_is_cyclic,
+                    color_cycles,
+                    &color_space,
+                    shift_hue_amount,
+                    saturate_amount,
+                    lighten_amount,
+                    sampler.smooth_coloring,
+                    palette_min_iter,
+                    palette_max_iter,
+                    palette_cdf,
+                    palette_offset,
+                    palette_transfer,
+                )
+            }
+        }
+        progressive::SampleMethod::Direct { axes: None, .. } => render_transformed_tile(
+            image_width,
+            image_height,
+            max_iterations,
+            |column, row| sampler.sample(column, row),
+            tile_color,
+        ),
+        progressive::SampleMethod::DirectFormula { .. }
+        | progressive::SampleMethod::Perturbed(_) => {
+            let output_size: usize = image_width * image_height * NUM_COLOR_CHANNELS;
+            let mut img: Vec<u8> = vec![0; output_size];
+            let mut values: Vec<f32> = vec![f32::INFINITY; image_width * image_height];
+            let mut stats = TileIterationStats::default();
+
+            let escape_results = sampler.escape_results();
+            for (pixel_index, &escape) in escape_results.iter().enumerate() {
+                stats.record(escape.0, max_iterations);
+
+                let smoothed_value = sampler.escape_value(escape);
+                values[pixel_index] = smoothed_value as f32;
+
+                let pixel = tile_color(smoothed_value);
+                let index = pixel_index * NUM_COLOR_CHANNELS;
+                img[index] = pixel[0];
+                img[index + 1] = pixel[1];
+                img[index + 2] = pixel[2];
+                img[index + 3] = 255;
+            }
+
+            RenderedTile {
+                image: img,
+                values,
+                stats,
+                tier: sampler.tier(),
+                escape_results,
+                antialiased: Vec::new(),
+            }
+        }
     };
 
-    let frame = match perturbation::PerturbedFrame::new(
-        origin_re,
-        origin_im,
-        tile_x_min,
-        tile_x_max,
-        tile_y_min,
-        tile_y_max,
-        tile_zoom,
-        zoom_offset,
+    antialias_tile(
+        rendered,
         image_width,
         image_height,
-        max_iterations,
-        exponent,
-        ESCAPE_RADIUS,
-    ) {
-        Ok(frame) => frame,
-        Err(_) => return RenderedTile::solid_black(image_width, image_height, perturbation_tier),
-    };
-
-    if frame.border_in_set(image_width, image_height) {
-        return RenderedTile::solid_black(image_width, image_height, perturbation_tier);
-    }
-
-    let (palette, should_reverse_colors, palette_is_cyclic) =
-        get_color_palette(color_scheme, reverse_colors);
-
-    let min_iterations_threshold = f64::from(palette_min_iter);
-    let max_iterations_threshold =
-        f64::from(palette_max_iter).max(min_iterations_threshold + f64::EPSILON);
+        supersampling,
+        adaptive_antialiasing,
+        palette_window,
+        color_cycles,
+        |column, row| sampler.sample(column, row).0,
+        sample_color,
+    )
+}
 
-    let output_size: usize = image_width * image_height * NUM_COLOR_CHANNELS;
-    let mut img: Vec<u8> = vec![0; output_size];
-    let mut values: Vec<f32> = vec![f32::INFINITY; image_width * image_height];
-    let mut stats = TileIterationStats::default();
+/// The finest complex-plane distance between neighboring pixels of a tile.
+/// A stretching transform packs pixels closer along its short axis, so the
+/// finest spacing shrinks by its smaller singular value.
+#[allow(clippy::too_many_arguments)]
+fn tile_pixel_spacing(
+    tile_x_min: f64,
+    tile_x_max: f64,
+    tile_y_min: f64,
+    tile_y_max: f64,
+    tile_zoom: i32,
+    zoom_offset: u32,
+    view_transform: perturbation::ViewTransform,
+    image_width: usize,
+    image_height: usize,
+) -> f64 {
+    perturbation::pixel_spacing(tile_x_min, tile_x_max, tile_zoom, zoom_offset, image_width).min(
+        perturbation::pixel_spacing(tile_y_min, tile_y_max, tile_zoom, zoom_offset, image_height),
+    ) * view_transform.min_scale()
+}
 
-    let escape_results = frame.compute_all(image_width, image_height);
-    for (pixel_index, &(escape_iterations, z)) in escape_results.iter().enumerate() {
-        stats.record(escape_iterations, max_iterations);
+/// Whether a tile at this pixel spacing renders by perturbation rather than
+/// directly in f64.
+fn uses_perturbation(pixel_spacing: f64, exponent: u32) -> bool {
+    pixel_spacing < perturbation::MIN_DIRECT_PIXEL_SPACING
+        && (2..=perturbation::MAX_PERTURBED_EXPONENT).contains(&exponent)
+}
 
-        let smoothed_value = smoothed_escape_value(
+/// A shallow (direct f64) point's cached value in the active mode, plus the
+/// escape iterations the stats record. The fixed-palette modes report
+/// `max_iterations`, which the stats skip: there is nothing to auto-fit, as
+/// in their axis-aligned kernels.
+fn direct_sample(
+    c: Complex64,
+    max_iterations: u32,
+    exponent: u32,
+    pixel_spacing: f64,
+    smooth_coloring: bool,
+    distance_estimate: bool,
+    atom_domain: bool,
+) -> (f64, u32) {
+    let escape_radius_squared = ESCAPE_RADIUS * ESCAPE_RADIUS;
+    if distance_estimate {
+        let brightness = distance_estimate_brightness(
+            distance_estimate_at_c(c, max_iterations, escape_radius_squared, exponent),
+            pixel_spacing,
+        );
+        (f64::from(brightness as f32), max_iterations)
+    } else if atom_domain {
+        let value = atom_domain_value(atom_domain_index_at_c(
+            c,
+            max_iterations,
+            escape_radius_squared,
+        ));
+        (f64::from(value as f32), max_iterations)
+    } else {
+        let (escape_iterations, z) =
+            calculate_escape_iterations(c.re, c.im, max_iterations, exponent);
+        let value = smoothed_escape_value(
             escape_iterations,
             z,
             max_iterations,
             exponent,
             smooth_coloring,
         );
-        values[pixel_index] = smoothed_value as f32;
+        (value, escape_iterations)
+    }
+}
 
-        let pixel = color_from_smoothed_value(
-            smoothed_value,
-            palette,
-            should_reverse_colors,
-            palette_is_cyclic,
-            color_cycles,
-            &color_space,
-            shift_hue_amount,
-            saturate_amount,
-            lighten_amount,
-            min_iterations_threshold,
-            max_iterations_threshold,
-            palette_cdf,
-            palette_offset,
-        );
+/// The pixel grid of a shallow (direct f64) tile: tile-scale offsets of the
+/// first column/row and the per-pixel steps, matching `linspace` (endpoints
+/// inclusive) like `PerturbedFrame`.
+struct DirectGrid {
+    origin: Complex64,
+    zoom_offset: u32,
+    view_transform: perturbation::ViewTransform,
+    x_min_offset: f64,
+    x_max_offset: f64,
+    y_min_offset: f64,
+    y_max_offset: f64,
+    column_step: f64,
+    row_step: f64,
+}
 
-        let index = pixel_index * NUM_COLOR_CHANNELS;
-        img[index] = pixel[0];
-        img[index + 1] = pixel[1];
-        img[index + 2] = pixel[2];
-        img[index + 3] = 255;
+impl DirectGrid {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        origin_re: &str,
+        origin_im: &str,
+        tile_x_min: f64,
+        tile_x_max: f64,
+        tile_y_min: f64,
+        tile_y_max: f64,
+        tile_zoom: i32,
+        zoom_offset: u32,
+        view_transform: perturbation::ViewTransform,
+        image_width: usize,
+        image_height: usize,
+    ) -> DirectGrid {
+        let grid_step = |first: f64, last: f64, pixels: usize| {
+            if pixels > 1 {
+                (last - first) / (pixels - 1) as f64
+            } else {
+                0.0
+            }
+        };
+        let x_min_offset = perturbation::tile_coordinate_offset(tile_x_min, tile_zoom);
+        let x_max_offset = perturbation::tile_coordinate_offset(tile_x_max, tile_zoom);
+        let y_min_offset = -perturbation::tile_coordinate_offset(tile_y_min, tile_zoom);
+        let y_max_offset = -perturbation::tile_coordinate_offset(tile_y_max, tile_zoom);
+
+        DirectGrid {
+            origin: Complex64::new(
+                origin_re.parse().unwrap_or(0.0),
+                origin_im.parse().unwrap_or(0.0),
+            ),
+            zoom_offset,
+            view_transform,
+            x_min_offset,
+            x_max_offset,
+            y_min_offset,
+            y_max_offset,
+            column_step: grid_step(x_min_offset, x_max_offset, image_width),
+            row_step: grid_step(y_min_offset, y_max_offset, image_height),
+        }
     }
 
-    RenderedTile {
-        image: img,
-        values,
-        stats,
-        // Authoritative tier from the frame that actually ran, matching the
-        // `perturbation_tier` computed above.
-        tier: if frame.uses_float_exp() {
-            RenderTier::FloatExp
-        } else {
-            RenderTier::Perturbation
-        },
+    fn scaled_offset(&self, offset: f64) -> f64 {
+        float_exp::ldexp(offset, -(self.zoom_offset as i64))
+    }
+
+    /// The point at a possibly fractional pixel position. The transform
+    /// acts on the tile-scale offsets, before the deep-zoom scaling.
+    fn point_at(&self, column: f64, row: f64) -> Complex64 {
+        let (re_offset, im_offset) = self.view_transform.apply(
+            self.x_min_offset + self.column_step * column,
+            self.y_min_offset + self.row_step * row,
+        );
+        Complex64::new(
+            self.origin.re + self.scaled_offset(re_offset),
+            self.origin.im + self.scaled_offset(im_offset),
+        )
+    }
+
+    /// `(re_min, re_max, im_min, im_max)` of an untransformed grid.
+    fn axis_aligned_bounds(&self) -> (f64, f64, f64, f64) {
+        (
+            self.origin.re + self.scaled_offset(self.x_min_offset),
+            self.origin.re + self.scaled_offset(self.x_max_offset),
+            self.origin.im + self.scaled_offset(self.y_max_offset),
+            self.origin.im + self.scaled_offset(self.y_min_offset),
+        )
     }
 }
 
@@ -3496,38 +4589,45 @@ pub fn get_mandelbrot_image_precise(
     palette_min_iter: i32,
     palette_max_iter: i32,
 ) -> Vec<u8> {
-    render_tile_precise(
-        &origin_re,
-        &origin_im,
-        tile_x_min,
-        tile_x_max,
-        tile_y_min,
-        tile_y_max,
-        tile_zoom,
+    // Frozen signature: axis-aligned views only, and the bench harness only
+    // measures escape-time rendering.
+    let view = TileRenderOptions::axis_aligned_view(
+        origin_re,
+        origin_im,
+        TileBounds {
+            x_min: tile_x_min,
+            x_max: tile_x_max,
+            y_min: tile_y_min,
+            y_max: tile_y_max,
+            zoom: tile_zoom,
+        },
         zoom_offset,
         max_iterations,
         exponent,
         image_width,
         image_height,
-        &color_scheme,
-        reverse_colors,
+        smooth_coloring,
+    );
+    render_tile_precise(
+        &progressive::PixelSampler::new(&view),
+        &SelectedPalette::named(&color_scheme, reverse_colors),
         shift_hue_amount,
         saturate_amount,
         lighten_amount,
         color_space,
-        smooth_coloring,
         palette_min_iter,
         palette_max_iter,
         // Frozen signature (bench harness): predates the color-cycles
         // control, so it renders a single palette pass.
         1,
-        // The bench harness only measures escape-time rendering.
-        false,
-        false,
         // Frozen signature: no histogram equalization, linear mapping, no
         // palette offset.
         None,
         0.0,
+        PaletteTransfer::Linear,
+        // Frozen signature: one sample per pixel.
+        1,
+        false,
     )
     .image
 }
@@ -3553,6 +4653,31 @@ pub struct MandelbrotTile {
     /// discriminant (0 direct, 1 perturbation, 2 float-exp). Drives the
     /// client's diagnostics overlay (issue #50).
     pub tier: u8,
+    /// 16-bit-per-channel RGBA of the same tile (see
+    /// `recolor_values_rgba16`), or empty unless `OutputFormat::Rgba16` was
+    /// requested.
+    #[wasm_bindgen(getter_with_clone)]
+    pub image16: Vec<u16>,
+    /// Linear-light RGB floats of the same tile (see
+    /// `recolor_values_linear_rgb`), or empty unless
+    /// `OutputFormat::LinearRgbF32` was requested.
+    #[wasm_bindgen(getter_with_clone)]
+    pub linear_rgb: Vec<f32>,
+    /// Per-pixel values of each coloring layer (see `layers`), one plane of
+    /// `image_width * image_height` values per layer in layer order, for
+    /// `recolor_tile_layers`. Empty when the render had no layers or values
+    /// were not requested.
+    #[wasm_bindgen(getter_with_clone)]
+    pub layer_values: Vec<f32>,
+    /// The requested value channels (see `channels`), one plane of
+    /// `image_width * image_height` values per entry of `channel_names`, in
+    /// that order. Empty when no channels were requested.
+    #[wasm_bindgen(getter_with_clone)]
+    pub channels: Vec<f32>,
+    /// The channel directory: `ValueChannel::name` of each plane in
+    /// `channels`.
+    #[wasm_bindgen(getter_with_clone)]
+    pub channel_names: Vec<String>,
 }
 
 impl MandelbrotTile {
@@ -3572,8 +4697,101 @@ impl MandelbrotTile {
             min_iter,
             max_iter,
             tier: rendered.tier as u8,
+            image16: Vec::new(),
+            linear_rgb: Vec::new(),
+            layer_values: Vec::new(),
+            channels: Vec::new(),
+            channel_names: Vec::new(),
         }
     }
+
+    /// Like `from_rendered`, additionally filling the high-bit-depth buffer
+    /// `output_format` asks for, and dithering the 8-bit image and applying
+    /// the alpha if `coloring` asks for them. All are produced from the
+    /// tile's cached values (and `layer_values`, composited per `layers`)
+    /// through the recolor pipeline, so they match what a later recolor of
+    /// the same values returns.
+    fn from_rendered_with_output(
+        mut rendered: RenderedTile,
+        image_width: usize,
+        include_values: bool,
+        output_format: OutputFormat,
+        coloring: &ColoringOptions,
+        layers: &[ColoringLayer],
+        layer_values: Vec<f32>,
+    ) -> Self {
+        // Every output but the plain 8-bit image reads one precise-color
+        // pass over the values, with the anti-aliased pixels' averages (from
+        // which their 8-bit color was quantized) in place of their values'.
+        let precise_colors = if coloring.dither.is_some() || output_format != OutputFormat::Rgba8 {
+            let mut colors = vec![[0.0; 3]; rendered.values.len()];
+            for_each_precise_color(&rendered.values, coloring, |pixel_index, color| {
+                colors[pixel_index] = color;
+            });
+            for &(pixel_index, color) in &rendered.antialiased {
+                colors[pixel_index] = color;
+            }
+            layers::composite_precise_layers(&mut colors, &layer_values, layers);
+            colors
+        } else {
+            Vec::new()
+        };
+        if let Some(pattern) = coloring.dither {
+            // Anti-aliased pixels are left alone: they already average finer
+            // than one 8-bit step.
+            let mut antialiased = vec![false; precise_colors.len()];
+            for &(pixel_index, _) in &rendered.antialiased {
+                antialiased[pixel_index] = true;
+            }
+            for (pixel_index, &color) in precise_colors.iter().enumerate() {
+                if !antialiased[pixel_index] {
+                    let (column, row) = (pixel_index % image_width, pixel_index / image_width);
+                    let index = pixel_index * NUM_COLOR_CHANNELS;
+                    rendered.image[index..index + 3]
+                        .copy_from_slice(&dither::quantize(color, pattern.threshold(column, row)));
+                }
+            }
+        }
+        alpha::apply_alpha_rgba8(&mut rendered.image, &rendered.values, coloring);
+        let image16 = match output_format {
+            OutputFormat::Rgba16 => {
+                let mut image16 = rgba16_from_precise_colors(&precise_colors);
+                alpha::apply_alpha_rgba16(&mut image16, &rendered.values, coloring);
+                image16
+            }
+            _ => Vec::new(),
+        };
+        let linear_rgb = match output_format {
+            OutputFormat::LinearRgbF32 => linear_rgb_from_precise_colors(&precise_colors),
+            _ => Vec::new(),
+        };
+
+        MandelbrotTile {
+            image16,
+            linear_rgb,
+            layer_values: if include_values {
+                layer_values
+            } else {
+                Vec::new()
+            },
+            ..MandelbrotTile::from_rendered(rendered, include_values)
+        }
+    }
+}
+
+/// Extra pixel formats a tile render can return next to its 8-bit RGBA
+/// `image`. Both derive from the same palette position as the 8-bit output
+/// but skip its per-step `u8` rounding, so graded exports do not band.
+#[derive(Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum OutputFormat {
+    /// Only the 8-bit `image` (the historical output).
+    #[default]
+    Rgba8,
+    /// Also fill `MandelbrotTile::image16`.
+    Rgba16,
+    /// Also fill `MandelbrotTile::linear_rgb`.
+    LinearRgbF32,
 }
 
 /// A rectangle in Leaflet tile coordinates (see `render_tile_precise` for
@@ -3594,6 +4812,10 @@ pub struct TileBounds {
 #[derive(Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ColoringOptions {
+    /// A built-in palette name, or an imported one (see
+    /// `import_palette_file`), which must have been imported into the
+    /// deserializing instance.
+    #[serde(deserialize_with = "palette_import::deserialize_palette_name")]
     pub palette: String,
     pub reverse_colors: bool,
     pub shift_hue_amount: f32,
@@ -3636,15 +4858,184 @@ pub struct ColoringOptions {
     /// 0 so payloads that omit it render unshifted.
     #[serde(default)]
     pub palette_offset: f64,
+    /// Inline gradient palette (see `gradient::GradientSpec`), used in place
+    /// of the named `palette` when present and valid. An invalid gradient
+    /// falls back to `palette`, so a half-edited gradient still renders.
+    /// Defaults to `None` so payloads that omit it use the named palette.
+    #[serde(default)]
+    pub gradient: Option<GradientSpec>,
+    /// Space to blend the palette's control points in (see
+    /// `InterpolationSpace`), overriding the palette's own: sRGB for the
+    /// built-in and imported palettes, a gradient's `interpolation`. The
+    /// palette is rebuilt in that space once (see `interpolated_palette`);
+    /// sampling it stays a plain sRGB lookup between neighboring entries
+    /// (see `Palette`). Defaults to `None`, which keeps the palette's own
+    /// space.
+    #[serde(default)]
+    pub palette_interpolation: Option<InterpolationSpace>,
+    /// Transfer curve from the normalized escape value to the palette
+    /// position (see `PaletteTransfer::apply`). Defaults to `Linear`, the
+    /// mapping this option predates.
+    #[serde(default)]
+    pub transfer_function: TransferFunction,
+    /// Exponent of the `Power` transfer curve; values below 1 spread the
+    /// low end of the window, above 1 the high end. Defaults to `None`,
+    /// which (like any non-positive or non-finite gamma) leaves the curve
+    /// linear.
+    #[serde(default)]
+    pub transfer_gamma: Option<f64>,
+    /// Which value channel (see `channels::ValueChannel`) the values being
+    /// colored hold, when they come from `MandelbrotTile::channels` rather
+    /// than `values`. The fixed-range channels color over `0..1` like the
+    /// distance-estimate and atom-domain methods. Renders pick their kernel
+    /// from the method flags and ignore it, except in coloring layers, which
+    /// color the named channel. Defaults to `None`: the values are whatever
+    /// the coloring method produces.
+    #[serde(default)]
+    pub value_channel: Option<ValueChannel>,
+    /// Remaps the palette for viewers with this color vision deficiency
+    /// (see `ColorVisionDeficiency::daltonize`), so neighboring palette
+    /// colors they would confuse stay distinguishable. Defaults to `None`,
+    /// the palette as designed.
+    #[serde(default)]
+    pub cvd_remap: Option<ColorVisionDeficiency>,
+    /// Dithers the 8-bit output with this pattern instead of truncating
+    /// each channel (see `dither`), breaking up banding in slow gradients.
+    /// The high-bit-depth outputs do not band and ignore it. Defaults to
+    /// `None`, no dithering.
+    #[serde(default)]
+    pub dither: Option<DitherPattern>,
+    /// Row length, in pixels, of the values a recolor dithers, which places
+    /// each value in the pattern; renders use their own image width.
+    /// Defaults to `None`, which only square tiles may leave it: dithering
+    /// values of any other shape without it is an error.
+    #[serde(default)]
+    pub dither_width: Option<u32>,
+    /// Makes interior pixels transparent instead of opaque black (see
+    /// `alpha`). Defaults to false, opaque.
+    #[serde(default)]
+    pub transparent_interior: bool,
+    /// Derives each escaping pixel's alpha from its value over the palette
+    /// window (see `AlphaRamp`). Defaults to `None`, opaque.
+    #[serde(default)]
+    pub alpha_ramp: Option<AlphaRamp>,
+    /// Premultiplies the color channels by the alpha, for compositors that
+    /// expect it. Only meaningful with `transparent_interior` or
+    /// `alpha_ramp`. Defaults to false, straight alpha.
+    #[serde(default)]
+    pub premultiplied_alpha: bool,
+}
+
+impl Default for ColoringOptions {
+    /// Turbo over iterations `0..100` with no adjustments, in the Hsl color
+    /// space, and every optional setting at its payload default. Lets code
+    /// building options directly (tests, native exports) name only what
+    /// differs.
+    fn default() -> ColoringOptions {
+        ColoringOptions {
+            palette: "turbo".to_string(),
+            reverse_colors: false,
+            shift_hue_amount: 0.0,
+            saturate_amount: 0.0,
+            lighten_amount: 0.0,
+            color_space: 0,
+            palette_min_iter: 0,
+            palette_max_iter: 100,
+            color_density: 1,
+            distance_estimate: false,
+            atom_domain: false,
+            palette_cdf: None,
+            palette_offset: 0.0,
+            gradient: None,
+            palette_interpolation: None,
+            transfer_function: TransferFunction::Linear,
+            transfer_gamma: None,
+            value_channel: None,
+            cvd_remap: None,
+            dither: None,
+            dither_width: None,
+            transparent_interior: false,
+            alpha_ramp: None,
+            premultiplied_alpha: false,
+        }
+    }
+}
+
+/// The quantity a render bakes into `values`: the one part of a coloring
+/// that recoloring cannot change.
+#[derive(Clone, Copy, PartialEq)]
+enum ValueMethod {
+    EscapeTime,
+    DistanceEstimate,
+    AtomDomain,
 }
 
 impl ColoringOptions {
+    /// The method `render_tile_precise` picks for this coloring; distance
+    /// estimation wins when both flags are set.
+    fn value_method(&self) -> ValueMethod {
+        if self.distance_estimate {
+            ValueMethod::DistanceEstimate
+        } else if self.atom_domain {
+            ValueMethod::AtomDomain
+        } else {
+            ValueMethod::EscapeTime
+        }
+    }
+
+    /// Whether the values color over the fixed `0..1` domain: the
+    /// distance-estimate and atom-domain methods, and the fixed-range value
+    /// channels.
+    pub(crate) fn fixed_palette(&self) -> bool {
+        self.distance_estimate
+            || self.atom_domain
+            || self
+                .value_channel
+                .is_some_and(ValueChannel::has_fixed_range)
+    }
+
+    /// The row length a recolor of `pixel_count` values dithers with (see
+    /// `dither_width`), or an error when the values are not a square tile
+    /// and no width was given.
+    pub(crate) fn dither_row_length(&self, pixel_count: usize) -> Result<usize, String> {
+        if let Some(width) = self.dither_width.filter(|&width| width > 0) {
+            return Ok(width as usize);
+        }
+        let side = (pixel_count as f64).sqrt().round() as usize;
+        if side * side == pixel_count {
+            Ok(side.max(1))
+        } else {
+            Err(format!(
+                "dithering {pixel_count} values that are not a square tile needs a ditherWidth"
+            ))
+        }
+    }
+
+    /// Checks that a recolor of `pixel_count` values can place them in the
+    /// dither pattern (see `dither_row_length`). The recolor exports return
+    /// this error; the typed cores leave such values undithered.
+    pub(crate) fn check_dither_width(&self, pixel_count: usize) -> Result<(), String> {
+        match self.dither {
+            Some(_) => self.dither_row_length(pixel_count).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    /// The value the palette normalizes for a cached value (see
+    /// `ValueChannel::palette_value`).
+    fn palette_value(&self, value: f32) -> f64 {
+        match self.value_channel {
+            Some(channel) => channel.palette_value(value),
+            None => f64::from(value),
+        }
+    }
+
     /// The palette-normalization domain: the user's iteration thresholds in
     /// escape-time mode, or the fixed `0..1` range in the palette-independent
     /// methods (distance-estimate and atom-domain), whose cached `values` are
     /// already normalized.
     fn palette_thresholds(&self) -> (f64, f64) {
-        if self.distance_estimate || self.atom_domain {
+        if self.fixed_palette() {
             (0.0, 1.0)
         } else {
             let min = f64::from(self.palette_min_iter);
@@ -3655,125 +5046,551 @@ impl ColoringOptions {
         }
     }
 
-    fn color_space(&self) -> ValidColorSpace {
-        match self.color_space {
-            0 => ValidColorSpace::Hsl,
-            1 => ValidColorSpace::Hsluv,
-            3 => ValidColorSpace::Okhsl,
-            // 2 is the client default; unknown values fall back to it.
-            _ => ValidColorSpace::Lch,
+    fn color_space(&self) -> ValidColorSpace {
+        match self.color_space {
+            0 => ValidColorSpace::Hsl,
+            1 => ValidColorSpace::Hsluv,
+            3 => ValidColorSpace::Okhsl,
+            // 2 is the client default; unknown values fall back to it.
+            _ => ValidColorSpace::Lch,
+        }
+    }
+
+    /// The histogram-equalization table to color with, or `None` for the
+    /// linear mapping. The fixed-palette methods (distance estimate, atom
+    /// domains) always map linearly over their fixed `0..1` domain, so a
+    /// stray table is ignored there.
+    fn effective_palette_cdf(&self) -> Option<&[f32]> {
+        if self.fixed_palette() {
+            None
+        } else {
+            self.palette_cdf.as_deref()
+        }
+    }
+
+    /// The palette offset, sanitized to [0, 1]. Clamped rather than wrapped:
+    /// in the phase-shift regime a full palette length is a meaningful,
+    /// distinct shift (the single-pass rotation branch wraps internally,
+    /// where a full turn is the identity — see `apply_color_cycles`). The
+    /// payload is untrusted at this boundary, so non-finite values drop to
+    /// 0 rather than letting a NaN propagate into every pixel's position.
+    fn effective_palette_offset(&self) -> f64 {
+        if self.palette_offset.is_finite() {
+            self.palette_offset.clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// The transfer curve to color with. The logarithmic curves scale by
+    /// the palette window's span (at least 1), so `Log` compresses
+    /// iteration counts above `palette_min_iter` the same way at any
+    /// window size.
+    fn palette_transfer(&self) -> PaletteTransfer {
+        let (min, max) = self.palette_thresholds();
+        let scale = (max - min).max(1.0);
+        match self.transfer_function {
+            TransferFunction::Linear => PaletteTransfer::Linear,
+            TransferFunction::Sqrt => PaletteTransfer::Sqrt,
+            TransferFunction::CubeRoot => PaletteTransfer::CubeRoot,
+            TransferFunction::Log => PaletteTransfer::Log { scale },
+            TransferFunction::LogLog => PaletteTransfer::LogLog { scale },
+            TransferFunction::Power => match self.transfer_gamma {
+                Some(gamma) if gamma.is_finite() && gamma > 0.0 => PaletteTransfer::Power { gamma },
+                _ => PaletteTransfer::Linear,
+            },
+        }
+    }
+
+    /// The palette to color with, resolved once: the compiled `gradient` if
+    /// it is valid, otherwise `palette`; re-blended in
+    /// `palette_interpolation` space if one is set, then remapped for
+    /// `cvd_remap` if one is set. Derived palettes are compiled once into
+    /// the bounded `gradient::compiled_palette` cache and keep the flags of
+    /// the palette they derive from.
+    fn selected_palette(&self) -> SelectedPalette {
+        let derived = |source| {
+            let key = gradient::PaletteKey {
+                source,
+                interpolation: self.palette_interpolation,
+                cvd_remap: self.cvd_remap,
+            };
+            gradient::compiled_palette(&key, || {
+                let (mut palette, should_reverse_colors, is_cyclic) = match key.source {
+                    gradient::PaletteSource::Gradient(spec) => {
+                        let spec = GradientSpec {
+                            interpolation: key.interpolation.unwrap_or(spec.interpolation),
+                            ..spec.clone()
+                        };
+                        (
+                            PaletteRef::Shared(Arc::new(spec.compile()?)),
+                            false,
+                            spec.cyclic,
+                        )
+                    }
+                    gradient::PaletteSource::Named(name) => {
+                        let (palette, should_reverse_colors, is_cyclic) =
+                            get_color_palette(name, false);
+                        let palette = match key
+                            .interpolation
+                            .and_then(|interpolation| interpolated_palette(name, interpolation))
+                        {
+                            Some(interpolated) => PaletteRef::Shared(Arc::new(interpolated)),
+                            None => palette,
+                        };
+                        (palette, should_reverse_colors, is_cyclic)
+                    }
+                };
+                if let Some(deficiency) = key.cvd_remap {
+                    palette = PaletteRef::Shared(Arc::new(palette.daltonized(deficiency)));
+                }
+                Ok((palette, should_reverse_colors, is_cyclic))
+            })
+        };
+
+        let compiled = match &self.gradient {
+            Some(spec) => derived(gradient::PaletteSource::Gradient(spec)).ok(),
+            None => None,
+        };
+        let compiled = match compiled {
+            Some(compiled) => compiled,
+            None if self.palette_interpolation.is_none() && self.cvd_remap.is_none() => {
+                return SelectedPalette::named(&self.palette, self.reverse_colors);
+            }
+            None => derived(gradient::PaletteSource::Named(&self.palette))
+                .expect("re-blending or remapping a named palette cannot fail"),
+        };
+        let (palette, should_reverse_colors, is_cyclic) = compiled;
+        SelectedPalette {
+            palette,
+            reverse: should_reverse_colors != self.reverse_colors,
+            cyclic: is_cyclic,
+        }
+    }
+}
+
+/// Everything a tile render needs, as one deserializable object so new
+/// settings are a field addition here and in the client payload — not a new
+/// positional argument threaded through every caller.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TileRenderOptions {
+    origin_re: String,
+    origin_im: String,
+    bounds: TileBounds,
+    zoom_offset: u32,
+    max_iterations: u32,
+    power: u32,
+    image_width: usize,
+    image_height: usize,
+    /// Baked into the returned escape values (unlike `coloring`, which only
+    /// affects the RGBA bytes), so changing it requires a re-render.
+    smooth_coloring: bool,
+    include_values: bool,
+    coloring: ColoringOptions,
+    /// High-bit-depth buffer to return alongside the 8-bit image. Defaults
+    /// to `Rgba8` (none) so payloads that omit it render as before.
+    #[serde(default)]
+    output_format: OutputFormat,
+    /// Anti-aliasing samples per pixel axis: each pixel averages an `N x N`
+    /// jittered grid (see `antialias_tile`). 0 or 1 (the default) renders
+    /// one sample per pixel. Only the 8-bit `image` is anti-aliased; the
+    /// cached `values` (and the high-bit-depth outputs derived from them)
+    /// keep the center sample.
+    #[serde(default)]
+    supersampling: u32,
+    /// Resample only pixels whose escape value differs strongly from a
+    /// neighbor's, at `supersampling` (minimum 2) samples per axis — most of
+    /// the quality of full supersampling at a fraction of the cost, since
+    /// smooth gradients need no extra samples. Defaults to false.
+    #[serde(default)]
+    adaptive_antialiasing: bool,
+    /// View rotation in degrees, counterclockwise about the world origin
+    /// (see `perturbation::ViewTransform`). Defaults to 0.
+    #[serde(default)]
+    rotation: f64,
+    /// Optional row-major 2x2 skew/stretch matrix applied before the
+    /// rotation, e.g. to unskew a stretched deep location. Defaults to
+    /// `None` (no skew).
+    #[serde(default)]
+    transform_matrix: Option<[f64; 4]>,
+    /// Coloring layers composited over `coloring`, bottom to top (see
+    /// `layers::ColoringLayer`). Layers recolor the base `values`, or the
+    /// channel plane they name, without anti-aliasing; only a layer whose
+    /// distance-estimate or atom-domain method differs from the base
+    /// coloring's renders the tile again, once per method. Defaults to none.
+    #[serde(default)]
+    layers: Vec<ColoringLayer>,
+    /// Value channels to return alongside `values` (see
+    /// `MandelbrotTile::channels`). Defaults to none.
+    #[serde(default)]
+    channels: Vec<ValueChannel>,
+    /// Iterate the sum of these terms plus `c` (see `polynomial`) instead of
+    /// `z^power + c`, e.g. `z^3 + a z^2 + c`. Formula tiles render escape
+    /// values only: the distance-estimate and atom-domain modes and the
+    /// direct-only channels are unavailable. Validated as it deserializes,
+    /// so a payload with an invalid formula is rejected (see
+    /// `polynomial::deserialize_formula`); an empty list is none, the
+    /// default.
+    #[serde(default, deserialize_with = "polynomial::deserialize_formula")]
+    formula: Option<polynomial::Polynomial>,
+}
+
+impl Default for TileRenderOptions {
+    /// A 256x256 tile of the whole set (zoom 2 around the world origin) at
+    /// 500 iterations of `z^2 + c`, smoothed, with the default coloring and
+    /// every optional setting at its payload default.
+    fn default() -> TileRenderOptions {
+        TileRenderOptions {
+            origin_re: "0".to_string(),
+            origin_im: "0".to_string(),
+            bounds: TileBounds {
+                x_min: 1.3,
+                x_max: 3.2,
+                y_min: 1.8,
+                y_max: 3.3,
+                zoom: 2,
+            },
+            zoom_offset: 0,
+            max_iterations: 500,
+            power: 2,
+            image_width: 256,
+            image_height: 256,
+            smooth_coloring: true,
+            include_values: true,
+            coloring: ColoringOptions::default(),
+            output_format: OutputFormat::Rgba8,
+            supersampling: 0,
+            adaptive_antialiasing: false,
+            rotation: 0.0,
+            transform_matrix: None,
+            layers: Vec::new(),
+            channels: Vec::new(),
+            formula: None,
+        }
+    }
+}
+
+impl TileRenderOptions {
+    /// Renders this tile colored with `coloring` — the base coloring or one
+    /// of the `layers`. The distance-estimate (issue #46) and atom-domain
+    /// (issue #45) flags ride on the coloring so they reach `recolor_tile`
+    /// too, but a render also needs them directly to pick the kernel and
+    /// bake the matching quantity into the cached `values`.
+    fn render(&self, coloring: &ColoringOptions) -> RenderedTile {
+        self.render_sampled(coloring, self.supersampling, self.adaptive_antialiasing)
+    }
+
+    /// The values a render with `coloring`'s value method produces, for a
+    /// layer whose method differs from the base coloring's. Layers recolor
+    /// values, so the pass skips anti-aliasing.
+    fn render_values(&self, coloring: &ColoringOptions) -> Vec<f32> {
+        self.render_sampled(coloring, 0, false).values
+    }
+
+    /// `render` at the given anti-aliasing settings.
+    fn render_sampled(
+        &self,
+        coloring: &ColoringOptions,
+        supersampling: u32,
+        adaptive_antialiasing: bool,
+    ) -> RenderedTile {
+        render_tile_precise(
+            &progressive::PixelSampler::with_coloring(self, coloring),
+            &coloring.selected_palette(),
+            coloring.shift_hue_amount,
+            coloring.saturate_amount,
+            coloring.lighten_amount,
+            coloring.color_space(),
+            coloring.palette_min_iter,
+            coloring.palette_max_iter,
+            coloring.color_density.max(1),
+            coloring.effective_palette_cdf(),
+            coloring.effective_palette_offset(),
+            coloring.palette_transfer(),
+            supersampling,
+            adaptive_antialiasing,
+        )
+    }
+
+    /// The options of an untransformed `z^power + c` tile with the default
+    /// coloring: the view the positional entrypoints and zoom-out keyframes
+    /// describe.
+    #[allow(clippy::too_many_arguments)]
+    fn axis_aligned_view(
+        origin_re: String,
+        origin_im: String,
+        bounds: TileBounds,
+        zoom_offset: u32,
+        max_iterations: u32,
+        power: u32,
+        image_width: usize,
+        image_height: usize,
+        smooth_coloring: bool,
+    ) -> TileRenderOptions {
+        TileRenderOptions {
+            origin_re,
+            origin_im,
+            bounds,
+            zoom_offset,
+            max_iterations,
+            power,
+            image_width,
+            image_height,
+            smooth_coloring,
+            ..TileRenderOptions::default()
+        }
+    }
+
+    fn view_transform(&self) -> perturbation::ViewTransform {
+        perturbation::ViewTransform::new(self.rotation, self.transform_matrix)
+    }
+
+    /// Whether the tile's outputs read value channels: requested ones, or
+    /// ones its layers color.
+    fn reads_channels(&self) -> bool {
+        !self.channels.is_empty()
+            || self
+                .layers
+                .iter()
+                .any(|layer| layer.coloring.value_channel.is_some())
+    }
+
+    /// The `formula` to iterate, or `None` for `z^power + c`.
+    fn polynomial(&self) -> Option<&polynomial::Polynomial> {
+        self.formula.as_ref()
+    }
+
+    /// Renders the base coloring and composites each of the `layers` over
+    /// it: the body of `render_tile`.
+    fn render_layered(&self) -> MandelbrotTile {
+        self.layered_over(self.render(&self.coloring))
+    }
+
+    /// `render_layered` from an already-rendered base coloring: colors the
+    /// layers and channels and assembles the tile's outputs.
+    fn layered_over(&self, mut rendered: RenderedTile) -> MandelbrotTile {
+        let pixel_count = rendered.values.len();
+
+        // The requested channels, then any more the layers color.
+        let mut channels: Vec<ValueChannel> = Vec::new();
+        for &channel in &self.channels {
+            if !channels.contains(&channel) {
+                channels.push(channel);
+            }
+        }
+        let requested_count = channels.len();
+        for layer in &self.layers {
+            if let Some(channel) = layer.coloring.value_channel {
+                if !channels.contains(&channel) {
+                    channels.push(channel);
+                }
+            }
+        }
+        let mut planes = self.render_channels(&channels, &rendered);
+        let plane = |channel: ValueChannel| {
+            let index = channels.iter().position(|&c| c == channel).unwrap();
+            &planes[index * pixel_count..(index + 1) * pixel_count]
+        };
+
+        // Only direct `z^power + c` tiles honor the distance-estimate and
+        // atom-domain methods; every other tile renders escape values for
+        // any coloring.
+        let honors_methods =
+            matches!(rendered.tier, RenderTier::Direct) && self.polynomial().is_none();
+        let method_of = |coloring: &ColoringOptions| {
+            if honors_methods {
+                coloring.value_method()
+            } else {
+                ValueMethod::EscapeTime
+            }
+        };
+        let base_method = method_of(&self.coloring);
+        // Values rendered for layers whose method differs from the base's.
+        let mut method_values: Vec<(ValueMethod, Vec<f32>)> = Vec::new();
+
+        let mut layer_values = Vec::with_capacity(pixel_count * self.layers.len());
+        for layer in &self.layers {
+            // Layers recolor (without anti-aliasing) their channel's plane,
+            // or the base values when they share its method; only a layer
+            // with another method renders, once per method.
+            let values = match layer.coloring.value_channel {
+                Some(channel) => plane(channel).to_vec(),
+                None => {
+                    let method = method_of(&layer.coloring);
+                    if method == base_method {
+                        rendered.values.clone()
+                    } else if let Some((_, values)) = method_values
+                        .iter()
+                        .find(|(rendered, _)| *rendered == method)
+                    {
+                        values.clone()
+                    } else {
+                        let values = self.render_values(&layer.coloring);
+                        method_values.push((method, values.clone()));
+                        values
+                    }
+                }
+            };
+            let image = layers::layer_rgba8(&values, layer);
+            layers::composite_rgba8(&mut rendered.image, &image, &values, layer);
+            layer_values.extend(values);
+        }
+
+        planes.truncate(requested_count * pixel_count);
+        let channel_names = channels[..requested_count]
+            .iter()
+            .map(|channel| channel.name().to_string())
+            .collect();
+
+        MandelbrotTile {
+            channels: planes,
+            channel_names,
+            ..MandelbrotTile::from_rendered_with_output(
+                rendered,
+                self.image_width,
+                self.include_values,
+                self.output_format,
+                &self.coloring,
+                &self.layers,
+                layer_values,
+            )
+        }
+    }
+
+    /// One plane per channel (row-major, one value per pixel), in order.
+    /// Shallow `z^power + c` tiles compute every channel in f64; other tiles
+    /// read the escape-derived ones from `rendered`'s escape results and
+    /// leave the rest `NaN`.
+    fn render_channels(&self, channels: &[ValueChannel], rendered: &RenderedTile) -> Vec<f32> {
+        if channels.is_empty() {
+            return Vec::new();
         }
-    }
-
-    /// The histogram-equalization table to color with, or `None` for the
-    /// linear mapping. The fixed-palette methods (distance estimate, atom
-    /// domains) always map linearly over their fixed `0..1` domain, so a
-    /// stray table is ignored there.
-    fn effective_palette_cdf(&self) -> Option<&[f32]> {
-        if self.distance_estimate || self.atom_domain {
-            None
-        } else {
-            self.palette_cdf.as_deref()
+        let exponent = self
+            .polynomial()
+            .map_or(self.power, |formula| formula.degree());
+        // Direct z^power tiles compute their channels per pixel: distance
+        // estimates, periods and stripe averages need more than the escape
+        // results hold.
+        let direct = matches!(rendered.tier, RenderTier::Direct) && self.polynomial().is_none();
+        if !direct && !rendered.escape_results.is_empty() {
+            return channels::escape_channel_planes(
+                channels,
+                &rendered.escape_results,
+                self.max_iterations,
+                exponent,
+            );
         }
-    }
+        let (width, height) = (self.image_width, self.image_height);
+        let view_transform = self.view_transform();
+        let pixel_spacing = tile_pixel_spacing(
+            self.bounds.x_min,
+            self.bounds.x_max,
+            self.bounds.y_min,
+            self.bounds.y_max,
+            self.bounds.zoom,
+            self.zoom_offset,
+            view_transform,
+            width,
+            height,
+        );
 
-    /// The palette offset, sanitized to [0, 1]. Clamped rather than wrapped:
-    /// in the phase-shift regime a full palette length is a meaningful,
-    /// distinct shift (the single-pass rotation branch wraps internally,
-    /// where a full turn is the identity — see `apply_color_cycles`). The
-    /// payload is untrusted at this boundary, so non-finite values drop to
-    /// 0 rather than letting a NaN propagate into every pixel's position.
-    fn effective_palette_offset(&self) -> f64 {
-        if self.palette_offset.is_finite() {
-            self.palette_offset.clamp(0.0, 1.0)
-        } else {
-            0.0
+        if direct {
+            let grid = DirectGrid::new(
+                &self.origin_re,
+                &self.origin_im,
+                self.bounds.x_min,
+                self.bounds.x_max,
+                self.bounds.y_min,
+                self.bounds.y_max,
+                self.bounds.zoom,
+                self.zoom_offset,
+                view_transform,
+                width,
+                height,
+            );
+            let pixel_count = width * height;
+            let mut planes = vec![0.0; pixel_count * channels.len()];
+            for row in 0..height {
+                for column in 0..width {
+                    let c = grid.point_at(column as f64, row as f64);
+                    // Shared by every escape-derived channel of the pixel.
+                    let mut escape = None;
+                    for (index, &channel) in channels.iter().enumerate() {
+                        planes[index * pixel_count + row * width + column] =
+                            channels::direct_channel_value(
+                                channel,
+                                c,
+                                || {
+                                    *escape.get_or_insert_with(|| {
+                                        calculate_escape_iterations(
+                                            c.re,
+                                            c.im,
+                                            self.max_iterations,
+                                            self.power,
+                                        )
+                                    })
+                                },
+                                self.max_iterations,
+                                self.power,
+                                pixel_spacing,
+                            );
+                    }
+                }
+            }
+            return planes;
         }
-    }
-}
-
-/// Everything a tile render needs, as one deserializable object so new
-/// settings are a field addition here and in the client payload — not a new
-/// positional argument threaded through every caller.
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TileRenderOptions {
-    origin_re: String,
-    origin_im: String,
-    bounds: TileBounds,
-    zoom_offset: u32,
-    max_iterations: u32,
-    power: u32,
-    image_width: usize,
-    image_height: usize,
-    /// Baked into the returned escape values (unlike `coloring`, which only
-    /// affects the RGBA bytes), so changing it requires a re-render.
-    smooth_coloring: bool,
-    include_values: bool,
-    coloring: ColoringOptions,
-}
 
-impl TileRenderOptions {
-    /// Whether this render uses distance-estimate mode (issue #46). The flag
-    /// rides on `coloring` so it reaches `recolor_tile` too, but a render also
-    /// needs it directly to pick the DE kernel and bake brightness into the
-    /// cached `values`.
-    fn distance_estimate(&self) -> bool {
-        self.coloring.distance_estimate
-    }
-
-    /// Whether this render uses atom-domain mode (issue #45). Like
-    /// `distance_estimate`, the flag rides on `coloring` so it reaches
-    /// `recolor_tile` too, but a render needs it directly to pick the
-    /// atom-domain kernel and bake the period value into the cached `values`.
-    fn atom_domain(&self) -> bool {
-        self.coloring.atom_domain
+        // A tile rendered solid black: every pixel is interior.
+        channels::escape_channel_planes(
+            channels,
+            &vec![(self.max_iterations, Complex64::new(0.0, 0.0)); width * height],
+            self.max_iterations,
+            exponent,
+        )
     }
 }
 
 /// Renders a Mandelbrot tile from a single options object (the production
-/// client's entrypoint; see `TileRenderOptions`). Behaves exactly like
-/// `get_mandelbrot_tile_precise`, which is kept positional only because the
-/// bench harness replays recorded positional argument lists against current
-/// and archived builds.
+/// client's entrypoint; see `TileRenderOptions`). Without coloring layers
+/// it behaves exactly like `get_mandelbrot_tile_precise`, which is kept
+/// positional only because the bench harness replays recorded positional
+/// argument lists against current and archived builds.
 #[wasm_bindgen]
 pub fn render_tile(options: JsValue) -> Result<MandelbrotTile, JsValue> {
     let options: TileRenderOptions =
         serde_wasm_bindgen::from_value(options).map_err(JsValue::from)?;
 
-    let rendered = render_tile_precise(
+    Ok(options.render_layered())
+}
+
+/// Serializes the reference orbit the tiles of a `TileRenderOptions`
+/// payload's view render against (computing it if this worker has not), for
+/// another worker to `import_reference_orbit` instead of recomputing it.
+#[wasm_bindgen]
+pub fn export_reference_orbit(options: JsValue) -> Result<Vec<u8>, JsValue> {
+    let options: TileRenderOptions =
+        serde_wasm_bindgen::from_value(options).map_err(JsValue::from)?;
+    if options.polynomial().is_some() {
+        return Err(JsValue::from_str(
+            "formula orbits can't be exported; each worker computes its own",
+        ));
+    }
+    perturbation::reference_orbit_buffer(
         &options.origin_re,
         &options.origin_im,
-        options.bounds.x_min,
-        options.bounds.x_max,
-        options.bounds.y_min,
-        options.bounds.y_max,
-        options.bounds.zoom,
-        options.zoom_offset,
+        options.bounds.zoom as i64 + options.zoom_offset as i64,
         options.max_iterations,
         options.power,
-        options.image_width,
-        options.image_height,
-        &options.coloring.palette,
-        options.coloring.reverse_colors,
-        options.coloring.shift_hue_amount,
-        options.coloring.saturate_amount,
-        options.coloring.lighten_amount,
-        options.coloring.color_space(),
-        options.smooth_coloring,
-        options.coloring.palette_min_iter,
-        options.coloring.palette_max_iter,
-        options.coloring.color_density.max(1),
-        options.distance_estimate(),
-        options.atom_domain(),
-        options.coloring.effective_palette_cdf(),
-        options.coloring.effective_palette_offset(),
-    );
+    )
+    .map_err(|error| JsValue::from_str(&error))
+}
 
-    Ok(MandelbrotTile::from_rendered(
-        rendered,
-        options.include_values,
-    ))
+/// Installs an `export_reference_orbit` buffer into this worker's orbit
+/// cache, so its next tiles of that view skip the orbit computation.
+#[wasm_bindgen]
+pub fn import_reference_orbit(buffer: &[u8]) -> Result<(), JsValue> {
+    perturbation::install_reference_orbit(buffer).map_err(|error| JsValue::from_str(&error))
 }
 
 /// Everything a Julia thumbnail render needs (issue #12), as one deserializable
@@ -3824,8 +5641,77 @@ pub fn render_julia(options: JsValue) -> Result<MandelbrotTile, JsValue> {
         options.power,
         options.image_width,
         options.image_height,
-        &options.coloring.palette,
-        options.coloring.reverse_colors,
+        &options.coloring.selected_palette(),
+        options.coloring.shift_hue_amount,
+        options.coloring.saturate_amount,
+        options.coloring.lighten_amount,
+        options.coloring.color_space(),
+        options.smooth_coloring,
+        options.coloring.palette_min_iter,
+        options.coloring.palette_max_iter,
+        options.coloring.color_density.max(1),
+        options.coloring.effective_palette_cdf(),
+        options.coloring.effective_palette_offset(),
+        options.coloring.palette_transfer(),
+    );
+
+    Ok(MandelbrotTile::from_rendered_with_output(
+        rendered,
+        options.image_width,
+        options.include_values,
+        OutputFormat::Rgba8,
+        &options.coloring,
+        &[],
+        Vec::new(),
+    ))
+}
+
+/// Everything an exponential-map strip render needs (see
+/// `generate_exponential_map_image`), mirroring the client's camelCase
+/// payload. The strip is centered on the arbitrary-precision origin, which
+/// should be the zoom video's target.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExponentialMapOptions {
+    origin_re: String,
+    origin_im: String,
+    /// Log2 of the radius at the top row. Each row below lowers it by
+    /// `perturbation::exponential_map_row_step_log2(image_width)`, so a strip
+    /// rendered in chunks lowers this by that step times the rows of the
+    /// previous chunks.
+    outer_radius_log2: f64,
+    max_iterations: u32,
+    power: u32,
+    /// Pixels per full turn of the angle.
+    image_width: usize,
+    image_height: usize,
+    smooth_coloring: bool,
+    include_values: bool,
+    coloring: ColoringOptions,
+    /// High-bit-depth buffer to return alongside the 8-bit image, as in
+    /// `TileRenderOptions`.
+    #[serde(default)]
+    output_format: OutputFormat,
+}
+
+/// Renders an exponential-map strip (see `generate_exponential_map_image`)
+/// for producing long zoom videos by reprojection. Returns the RGBA bytes
+/// plus iteration stats like a tile render; `include_values` additionally
+/// returns the per-pixel escape values for recoloring.
+#[wasm_bindgen]
+pub fn render_exponential_map(options: JsValue) -> Result<MandelbrotTile, JsValue> {
+    let options: ExponentialMapOptions =
+        serde_wasm_bindgen::from_value(options).map_err(JsValue::from)?;
+
+    let rendered = generate_exponential_map_image(
+        &options.origin_re,
+        &options.origin_im,
+        options.outer_radius_log2,
+        options.max_iterations,
+        options.power,
+        options.image_width,
+        options.image_height,
+        &options.coloring.selected_palette(),
         options.coloring.shift_hue_amount,
         options.coloring.saturate_amount,
         options.coloring.lighten_amount,
@@ -3836,14 +5722,191 @@ pub fn render_julia(options: JsValue) -> Result<MandelbrotTile, JsValue> {
         options.coloring.color_density.max(1),
         options.coloring.effective_palette_cdf(),
         options.coloring.effective_palette_offset(),
+        options.coloring.palette_transfer(),
     );
 
-    Ok(MandelbrotTile::from_rendered(
+    Ok(MandelbrotTile::from_rendered_with_output(
         rendered,
+        options.image_width,
         options.include_values,
+        options.output_format,
+        &options.coloring,
+        &[],
+        Vec::new(),
     ))
 }
 
+/// Pixels per map tile in the client (see `perturbation::TILE_SPACE_SCALE`).
+/// Zoom-out keyframes use the map's pixel spacing, so a keyframe at zoom `z`
+/// looks exactly like the map at zoom `z`.
+const KEYFRAME_PIXELS_PER_TILE: f64 = 200.0;
+
+/// Effective zoom above which keyframes move the excess into `zoom_offset`,
+/// keeping tile coordinates small as the client does.
+const KEYFRAME_MAX_TILE_ZOOM: i32 = 12;
+
+/// Everything a zoom-out keyframe sequence needs (see `ZoomOutSequence`),
+/// mirroring the client's camelCase payload.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoomOutOptions {
+    /// The deep target every keyframe is centered on.
+    origin_re: String,
+    origin_im: String,
+    /// Effective zoom (`tile_zoom + zoom_offset`) of the deepest keyframe.
+    zoom: i32,
+    /// How many keyframes to emit, each zoomed out 2x from the last.
+    /// Defaults to reaching zoom 0; never continues past it.
+    #[serde(default)]
+    keyframe_count: Option<usize>,
+    /// Index of the first keyframe to render, to resume an interrupted
+    /// sequence after the keyframes already stored. Defaults to 0.
+    #[serde(default)]
+    start_index: usize,
+    max_iterations: u32,
+    power: u32,
+    image_width: usize,
+    image_height: usize,
+    smooth_coloring: bool,
+    include_values: bool,
+    coloring: ColoringOptions,
+}
+
+/// A KF-style "store zoom-out images" sequence: keyframe `i` is an image of
+/// the target at effective zoom `zoom - i`, so consecutive keyframes differ
+/// by exactly 2x and any frame in between is a crop-and-scale of the nearer
+/// keyframe (`keyframe_pixel_spacing_log2` gives each one's scale; all share
+/// the target as their center).
+///
+/// Keyframes render deepest first, so the first perturbation keyframe
+/// computes the reference orbit at the highest precision the sequence needs
+/// and every shallower one reuses it from `ORBIT_CACHE` (same origin, same
+/// budget). The caller pulls one keyframe at a time with `next_keyframe`,
+/// which doubles as per-keyframe progress (`completed_keyframes` of
+/// `keyframe_count`); a sequence interrupted between calls resumes through
+/// `start_index`.
+#[wasm_bindgen]
+pub struct ZoomOutSequence {
+    options: ZoomOutOptions,
+    next_index: usize,
+}
+
+#[wasm_bindgen]
+impl ZoomOutSequence {
+    #[wasm_bindgen(constructor)]
+    pub fn new(options: JsValue) -> Result<ZoomOutSequence, JsValue> {
+        let options: ZoomOutOptions =
+            serde_wasm_bindgen::from_value(options).map_err(JsValue::from)?;
+        Ok(ZoomOutSequence::from_options(options))
+    }
+
+    /// Total number of keyframes in the sequence, including any skipped by
+    /// `start_index`.
+    pub fn keyframe_count(&self) -> usize {
+        let reachable = self.options.zoom.max(0) as usize + 1;
+        self.options
+            .keyframe_count
+            .map_or(reachable, |count| count.min(reachable))
+    }
+
+    /// Index of the next keyframe `next_keyframe` renders; equals
+    /// `keyframe_count` once the sequence is done.
+    pub fn completed_keyframes(&self) -> usize {
+        self.next_index
+    }
+
+    /// Effective zoom of keyframe `index`.
+    pub fn keyframe_zoom(&self, index: usize) -> i32 {
+        self.options.zoom - index as i32
+    }
+
+    /// Log2 of the complex-plane distance between adjacent pixels of
+    /// keyframe `index`. A frame at fractional zoom `z` between two
+    /// keyframes is the nearer-deeper keyframe scaled up by
+    /// `2^(z - keyframe_zoom)`, about the image center.
+    pub fn keyframe_pixel_spacing_log2(&self, index: usize) -> f64 {
+        let (tile_zoom, zoom_offset) = self.keyframe_tile_zoom(index);
+        // The deep scaling is added in log space: 2^-zoom_offset underflows
+        // f64 past zoom ~1070.
+        let tile_pixels = KEYFRAME_PIXELS_PER_TILE as usize;
+        perturbation::pixel_spacing(0.0, 1.0, tile_zoom, 0, tile_pixels).log2()
+            - f64::from(zoom_offset)
+    }
+
+    /// Renders the next keyframe, or returns `None` once every keyframe is
+    /// done.
+    pub fn next_keyframe(&mut self) -> Option<MandelbrotTile> {
+        if self.next_index >= self.keyframe_count() {
+            return None;
+        }
+
+        let (tile_zoom, zoom_offset) = self.keyframe_tile_zoom(self.next_index);
+        let center = perturbation::origin_tile_coordinate(tile_zoom);
+        // Endpoints inclusive, as tiles are: one pixel per 1/200 tile.
+        let half_extent =
+            |pixels: usize| pixels.saturating_sub(1) as f64 / 2.0 / KEYFRAME_PIXELS_PER_TILE;
+
+        let options = &self.options;
+        let view = TileRenderOptions::axis_aligned_view(
+            options.origin_re.clone(),
+            options.origin_im.clone(),
+            TileBounds {
+                x_min: center - half_extent(options.image_width),
+                x_max: center + half_extent(options.image_width),
+                y_min: center - half_extent(options.image_height),
+                y_max: center + half_extent(options.image_height),
+                zoom: tile_zoom,
+            },
+            zoom_offset,
+            options.max_iterations,
+            options.power,
+            options.image_width,
+            options.image_height,
+            options.smooth_coloring,
+        );
+        let rendered = render_tile_precise(
+            &progressive::PixelSampler::with_coloring(&view, &options.coloring),
+            &options.coloring.selected_palette(),
+            options.coloring.shift_hue_amount,
+            options.coloring.saturate_amount,
+            options.coloring.lighten_amount,
+            options.coloring.color_space(),
+            options.coloring.palette_min_iter,
+            options.coloring.palette_max_iter,
+            options.coloring.color_density.max(1),
+            options.coloring.effective_palette_cdf(),
+            options.coloring.effective_palette_offset(),
+            options.coloring.palette_transfer(),
+            1,
+            false,
+        );
+
+        self.next_index += 1;
+        Some(MandelbrotTile::from_rendered(
+            rendered,
+            self.options.include_values,
+        ))
+    }
+}
+
+impl ZoomOutSequence {
+    fn from_options(options: ZoomOutOptions) -> ZoomOutSequence {
+        let next_index = options.start_index;
+        ZoomOutSequence {
+            options,
+            next_index,
+        }
+    }
+
+    /// Keyframe `index`'s effective zoom split into the tile zoom and the
+    /// deep-zoom `zoom_offset`, as the client splits its own.
+    fn keyframe_tile_zoom(&self, index: usize) -> (i32, u32) {
+        let zoom = self.keyframe_zoom(index);
+        let zoom_offset = (zoom - KEYFRAME_MAX_TILE_ZOOM).max(0);
+        (zoom - zoom_offset, zoom_offset as u32)
+    }
+}
+
 /// Renders a Mandelbrot tile at any zoom depth (see `render_tile_precise`
 /// for the view geometry) and reports the tile's escaped-pixel iteration
 /// range alongside the image. When `include_values` is set, the per-pixel
@@ -3883,36 +5946,42 @@ pub fn get_mandelbrot_tile_precise(
     // both this build and archived ones. Omitted or 0 means a single pass.
     color_cycles: Option<u32>,
 ) -> MandelbrotTile {
-    let rendered = render_tile_precise(
-        &origin_re,
-        &origin_im,
-        tile_x_min,
-        tile_x_max,
-        tile_y_min,
-        tile_y_max,
-        tile_zoom,
+    // Frozen positional signature (bench harness): axis-aligned views only,
+    // escape-time only.
+    let view = TileRenderOptions::axis_aligned_view(
+        origin_re,
+        origin_im,
+        TileBounds {
+            x_min: tile_x_min,
+            x_max: tile_x_max,
+            y_min: tile_y_min,
+            y_max: tile_y_max,
+            zoom: tile_zoom,
+        },
         zoom_offset,
         max_iterations,
         exponent,
         image_width,
         image_height,
-        &color_scheme,
-        reverse_colors,
+        smooth_coloring,
+    );
+    let rendered = render_tile_precise(
+        &progressive::PixelSampler::new(&view),
+        &SelectedPalette::named(&color_scheme, reverse_colors),
         shift_hue_amount,
         saturate_amount,
         lighten_amount,
         color_space,
-        smooth_coloring,
         palette_min_iter,
         palette_max_iter,
         color_cycles.unwrap_or(1).max(1),
-        // Frozen positional signature (bench harness): escape-time only.
-        false,
-        false,
         // Frozen positional signature: no histogram equalization, no
-        // palette offset.
+        // palette offset, one sample per pixel.
         None,
         0.0,
+        PaletteTransfer::Linear,
+        1,
+        false,
     );
 
     MandelbrotTile::from_rendered(rendered, include_values)
@@ -3935,6 +6004,12 @@ pub struct PointQueryOptions {
     zoom_offset: u32,
     max_iterations: u32,
     power: u32,
+    /// The view's rotation and skew, as in `TileRenderOptions`, so a point
+    /// under the cursor addresses what the rotated tiles show.
+    #[serde(default)]
+    rotation: f64,
+    #[serde(default)]
+    transform_matrix: Option<[f64; 4]>,
 }
 
 impl PointQueryOptions {
@@ -3946,15 +6021,15 @@ impl PointQueryOptions {
     fn c(&self) -> Complex64 {
         let origin_re: f64 = self.origin_re.parse().unwrap_or(0.0);
         let origin_im: f64 = self.origin_im.parse().unwrap_or(0.0);
-        let scaled_offset = |tile_coordinate: f64| {
-            float_exp::ldexp(
-                perturbation::tile_coordinate_offset(tile_coordinate, self.tile_zoom),
-                -(self.zoom_offset as i64),
-            )
-        };
+        let (re_offset, im_offset) =
+            perturbation::ViewTransform::new(self.rotation, self.transform_matrix).apply(
+                perturbation::tile_coordinate_offset(self.tile_x, self.tile_zoom),
+                -perturbation::tile_coordinate_offset(self.tile_y, self.tile_zoom),
+            );
+        let scaled_offset = |offset: f64| float_exp::ldexp(offset, -(self.zoom_offset as i64));
         Complex64::new(
-            origin_re + scaled_offset(self.tile_x),
-            origin_im - scaled_offset(self.tile_y),
+            origin_re + scaled_offset(re_offset),
+            origin_im + scaled_offset(im_offset),
         )
     }
 }
@@ -4009,26 +6084,45 @@ pub fn period_at_point(options: JsValue) -> Result<u32, JsValue> {
 pub fn recolor_tile(values: &[f32], options: JsValue) -> Result<Vec<u8>, JsValue> {
     let options: ColoringOptions =
         serde_wasm_bindgen::from_value(options).map_err(JsValue::from)?;
+    options
+        .check_dither_width(values.len())
+        .map_err(|error| JsValue::from_str(&error))?;
     Ok(recolor_values(values, &options))
 }
 
 /// Typed core of `recolor_tile`, callable from native tests and examples
 /// (which cannot build a `JsValue`).
 pub fn recolor_values(values: &[f32], options: &ColoringOptions) -> Vec<u8> {
-    let (palette, should_reverse_colors, palette_is_cyclic) =
-        get_color_palette(&options.palette, options.reverse_colors);
+    let mut image = recolor_values_opaque(values, options);
+    alpha::apply_alpha_rgba8(&mut image, values, options);
+    image
+}
+
+/// `recolor_values` before its alpha (see `alpha`): opaque 8-bit RGBA, as
+/// layers composite it.
+pub(crate) fn recolor_values_opaque(values: &[f32], options: &ColoringOptions) -> Vec<u8> {
+    if let (Some(pattern), Ok(row_length)) =
+        (options.dither, options.dither_row_length(values.len()))
+    {
+        let colors = layers::layered_precise_colors(values, &[], options, &[]);
+        return dither::dithered_rgba8(&colors, row_length, pattern);
+    }
+
+    let selected_palette = options.selected_palette();
+    let (palette, should_reverse_colors, palette_is_cyclic) = selected_palette.parts();
     let color_cycles = options.color_density.max(1);
     let color_space = options.color_space();
 
     let (min_iterations_threshold, max_iterations_threshold) = options.palette_thresholds();
     let palette_cdf = options.effective_palette_cdf();
     let palette_offset = options.effective_palette_offset();
+    let palette_transfer = options.palette_transfer();
 
     let mut img: Vec<u8> = vec![0; values.len() * NUM_COLOR_CHANNELS];
 
     for (pixel_index, &value) in values.iter().enumerate() {
         let pixel = color_from_smoothed_value(
-            f64::from(value),
+            options.palette_value(value),
             palette,
             should_reverse_colors,
             palette_is_cyclic,
@@ -4041,6 +6135,7 @@ pub fn recolor_values(values: &[f32], options: &ColoringOptions) -> Vec<u8> {
             max_iterations_threshold,
             palette_cdf,
             palette_offset,
+            palette_transfer,
         );
 
         let index = pixel_index * NUM_COLOR_CHANNELS;
@@ -4053,6 +6148,105 @@ pub fn recolor_values(values: &[f32], options: &ColoringOptions) -> Vec<u8> {
     img
 }
 
+/// Recolors cached values like `recolor_tile`, but as 16-bit-per-channel RGBA
+/// (`u16` per channel, row-major, alpha 65535). The palette is interpolated
+/// in f64 and quantized once at the end, so smooth gradients keep 256 times
+/// the tonal resolution of the 8-bit output — headroom for grading exports.
+#[wasm_bindgen]
+pub fn recolor_tile_rgba16(values: &[f32], options: JsValue) -> Result<Vec<u16>, JsValue> {
+    let options: ColoringOptions =
+        serde_wasm_bindgen::from_value(options).map_err(JsValue::from)?;
+    Ok(recolor_values_rgba16(values, &options))
+}
+
+/// Recolors cached values like `recolor_tile`, but as linear-light RGB
+/// floats (three `f32` per pixel, no alpha) for floating-point formats like
+/// PFM and OpenEXR.
+#[wasm_bindgen]
+pub fn recolor_tile_linear_rgb(values: &[f32], options: JsValue) -> Result<Vec<f32>, JsValue> {
+    let options: ColoringOptions =
+        serde_wasm_bindgen::from_value(options).map_err(JsValue::from)?;
+    Ok(recolor_values_linear_rgb(values, &options))
+}
+
+/// Typed core of `recolor_tile_rgba16`.
+pub fn recolor_values_rgba16(values: &[f32], options: &ColoringOptions) -> Vec<u16> {
+    let mut image =
+        rgba16_from_precise_colors(&layers::layered_precise_colors(values, &[], options, &[]));
+    alpha::apply_alpha_rgba16(&mut image, values, options);
+    image
+}
+
+/// Typed core of `recolor_tile_linear_rgb`.
+pub fn recolor_values_linear_rgb(values: &[f32], options: &ColoringOptions) -> Vec<f32> {
+    linear_rgb_from_precise_colors(&layers::layered_precise_colors(values, &[], options, &[]))
+}
+
+/// Quantizes unquantized colors to 16-bit-per-channel RGBA.
+fn rgba16_from_precise_colors(colors: &[PreciseColor]) -> Vec<u16> {
+    colors
+        .iter()
+        .flat_map(|color| {
+            let [r, g, b] = color.map(|channel| (channel * 65535.0).round() as u16);
+            [r, g, b, u16::MAX]
+        })
+        .collect()
+}
+
+/// Decodes unquantized colors to linear-light RGB floats.
+fn linear_rgb_from_precise_colors(colors: &[PreciseColor]) -> Vec<f32> {
+    colors
+        .iter()
+        .flat_map(|color| color.map(|channel| srgb_to_linear(channel) as f32))
+        .collect()
+}
+
+/// Resolves `options` once and hands each value's unquantized color (see
+/// `precise_color_from_smoothed_value`) to `write_pixel` with its index.
+fn for_each_precise_color(
+    values: &[f32],
+    options: &ColoringOptions,
+    mut write_pixel: impl FnMut(usize, PreciseColor),
+) {
+    let color_of = precise_color_fn(options);
+    for (pixel_index, &value) in values.iter().enumerate() {
+        write_pixel(pixel_index, color_of(options.palette_value(value)));
+    }
+}
+
+/// Resolves `options` once into a map from a palette value (see
+/// `ColoringOptions::palette_value`) to its unquantized color.
+fn precise_color_fn(options: &ColoringOptions) -> impl Fn(f64) -> PreciseColor + '_ {
+    let selected_palette = options.selected_palette();
+    let color_cycles = options.color_density.max(1);
+    let color_space = options.color_space();
+
+    let (min_iterations_threshold, max_iterations_threshold) = options.palette_thresholds();
+    let palette_cdf = options.effective_palette_cdf();
+    let palette_offset = options.effective_palette_offset();
+    let palette_transfer = options.palette_transfer();
+
+    move |value| {
+        let (palette, should_reverse_colors, palette_is_cyclic) = selected_palette.parts();
+        precise_color_from_smoothed_value(
+            value,
+            palette,
+            should_reverse_colors,
+            palette_is_cyclic,
+            color_cycles,
+            &color_space,
+            options.shift_hue_amount,
+            options.saturate_amount,
+            options.lighten_amount,
+            min_iterations_threshold,
+            max_iterations_threshold,
+            palette_cdf,
+            palette_offset,
+            palette_transfer,
+        )
+    }
+}
+
 /// Initializes the module. This function is specifically designed to be called
 /// from WebAssembly to perform necessary initializations.
 #[wasm_bindgen]
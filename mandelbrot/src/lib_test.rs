@@ -738,6 +738,7 @@ mod lib_test {
             palette_end,
             None,
             0.0,
+            super::PaletteTransfer::Linear,
         );
 
         assert_eq!(
@@ -811,14 +812,14 @@ mod lib_test {
             2,
             image_width,
             image_height,
-            "greys",
-            false,
+            &super::SelectedPalette::named("greys", false),
             0.0,
             0.0,
             0.0,
             crate::ValidColorSpace::Hsl,
             1,
             0.0,
+            super::PaletteTransfer::Linear,
         );
 
         assert_eq!(
@@ -983,14 +984,14 @@ mod lib_test {
             500,
             image_width,
             image_height,
-            "turbo",
-            false,
+            &super::SelectedPalette::named("turbo", false),
             0.0,
             0.0,
             0.0,
             crate::ValidColorSpace::Hsl,
             1,
             0.0,
+            super::PaletteTransfer::Linear,
         );
 
         assert_eq!(
@@ -1076,8 +1077,7 @@ mod lib_test {
             2,
             image_width,
             image_height,
-            "turbo",
-            false,
+            &super::SelectedPalette::named("turbo", false),
             0.0,
             0.0,
             0.0,
@@ -1088,6 +1088,7 @@ mod lib_test {
             1,
             None,
             0.0,
+            super::PaletteTransfer::Linear,
         );
 
         assert_eq!(
@@ -1145,8 +1146,7 @@ mod lib_test {
             2,
             size,
             size,
-            "greys",
-            false,
+            &super::SelectedPalette::named("greys", false),
             0.0,
             0.0,
             0.0,
@@ -1157,6 +1157,7 @@ mod lib_test {
             1,
             None,
             0.0,
+            super::PaletteTransfer::Linear,
         );
 
         for row in 0..size {
@@ -1777,6 +1778,227 @@ mod lib_test {
         assert_eq!(palette.eval_continuous(0.0).as_array(), [34, 23, 27]); // turbo start color
     }
 
+    #[test]
+    fn test_inline_gradient_replaces_named_palette() {
+        use crate::{GradientEasing, GradientSpec, GradientStop, InterpolationSpace};
+
+        let stop = |position: f64, color: &str| GradientStop {
+            position,
+            color: color.to_string(),
+            easing: GradientEasing::Linear,
+        };
+        let values = [0.0f32, 50.0, 100.0];
+        let mut options = coloring_options("turbo", 0, 100);
+        options.gradient = Some(GradientSpec {
+            stops: vec![stop(0.0, "#000000"), stop(1.0, "#ff8000")],
+            interpolation: InterpolationSpace::Srgb,
+            cyclic: false,
+        });
+
+        let image = super::recolor_values(&values, &options);
+        assert_eq!(&image[0..4], &[0, 0, 0, 255]);
+        assert_eq!(&image[8..12], &[255, 128, 0, 255]);
+
+        // The gradient resolves in place of the named palette.
+        let selected = options.selected_palette();
+        let (palette, should_reverse, _) = selected.parts();
+        assert!(!should_reverse);
+        assert_eq!(palette.eval_continuous(1.0).as_array(), [255, 128, 0]);
+
+        // An invalid gradient falls back to the named palette.
+        options.gradient.as_mut().unwrap().stops.truncate(1);
+        options.gradient.as_mut().unwrap().stops[0].position = 0.0;
+        assert_eq!(
+            super::recolor_values(&values, &options),
+            super::recolor_values(&values, &coloring_options("turbo", 0, 100))
+        );
+    }
+
+    fn two_color_palette(
+        from: [u8; 3],
+        to: [u8; 3],
+        interpolation: crate::InterpolationSpace,
+    ) -> super::Palette {
+        let color = |rgb: [u8; 3]| rgb.map(|channel| f64::from(channel) / 255.0);
+        super::palette_from_control_points(interpolation, &[(0.0, color(from)), (1.0, color(to))])
+    }
+
+    #[test]
+    fn test_palette_interpolation_spaces() {
+        use crate::InterpolationSpace;
+
+        let midpoint = |interpolation| {
+            two_color_palette([255, 0, 0], [0, 255, 0], interpolation)
+                .eval_continuous(0.5)
+                .as_array()
+        };
+
+        // sRGB blends channel by channel: the familiar dark olive midpoint.
+        assert_eq!(midpoint(InterpolationSpace::Srgb), [128, 128, 0]);
+        // Linear light and OKLab both keep the midpoint bright instead.
+        for interpolation in [InterpolationSpace::LinearRgb, InterpolationSpace::Oklab] {
+            let [r, g, b] = midpoint(interpolation);
+            assert!(
+                r > 160 && g > 160 && b < 40,
+                "{interpolation:?}: {r} {g} {b}"
+            );
+        }
+        // The polar spaces sweep hue the short way, through orange/yellow.
+        for interpolation in [InterpolationSpace::Lch, InterpolationSpace::Oklch] {
+            let [r, g, b] = midpoint(interpolation);
+            assert!(
+                r > 150 && g > 120 && b < 80,
+                "{interpolation:?}: {r} {g} {b}"
+            );
+        }
+
+        // Every space reproduces the entries themselves.
+        for interpolation in [
+            InterpolationSpace::Srgb,
+            InterpolationSpace::LinearRgb,
+            InterpolationSpace::Lch,
+            InterpolationSpace::Oklab,
+            InterpolationSpace::Oklch,
+        ] {
+            let palette = two_color_palette([12, 34, 56], [200, 150, 100], interpolation);
+            assert_eq!(palette.eval_continuous(0.0).as_array(), [12, 34, 56]);
+            assert_eq!(palette.eval_continuous(1.0).as_array(), [200, 150, 100]);
+            let precise = palette.eval_continuous_precise(0.3);
+            let quantized = palette.eval_continuous(0.3).as_array();
+            for channel in 0..3 {
+                assert_eq!((precise[channel] * 255.0).round() as u8, quantized[channel]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_built_in_palettes_blend_the_same_entries_at_both_depths() {
+        for name in ["inferno", "turbo", "rainbow", "greys"] {
+            let (palette, _, _) = super::get_color_palette(name, false);
+            for step in 0..=1000 {
+                let t = f64::from(step) / 1000.0;
+                let precise = palette.eval_continuous_precise(t);
+                let quantized = palette.eval_continuous(t).as_array();
+                // Only the rounding of the blend differs.
+                for channel in 0..3 {
+                    let error = precise[channel] * 255.0 - f64::from(quantized[channel]);
+                    assert!(error.abs() <= 0.5 + 1e-9, "{name} at {t}: {error}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dense_color_density_follows_the_interpolation_space() {
+        use crate::gradient::{GradientEasing, GradientSpec, GradientStop};
+        use crate::InterpolationSpace;
+
+        let (blue, yellow) = ([0.0, 0.0, 1.0], [1.0, 1.0, 0.0]);
+        let stop = |position: f64, color: &str| GradientStop {
+            position,
+            color: color.to_string(),
+            easing: GradientEasing::Linear,
+        };
+        // In 8-bit steps: the entries' own rounding, plus the sRGB blend
+        // between them, which strays furthest where OKLCh's hue turns.
+        for (interpolation, bound) in [
+            (InterpolationSpace::Oklab, 1.0),
+            (InterpolationSpace::Oklch, 3.0),
+        ] {
+            let options = super::ColoringOptions {
+                gradient: Some(GradientSpec {
+                    stops: vec![stop(0.0, "#0000ff"), stop(1.0, "#ffff00")],
+                    interpolation,
+                    cyclic: false,
+                }),
+                color_density: 64,
+                ..coloring_options("turbo", 0, 1000)
+            };
+            // Every pixel lands at its own fraction between two entries.
+            let values: Vec<f32> = (0..20_000).map(|i| i as f32 / 20.0).collect();
+            let image = super::recolor_values_rgba16(&values, &options);
+
+            let selected = options.selected_palette();
+            let (_, reverse, cyclic) = selected.parts();
+            let (min, max) = options.palette_thresholds();
+            let (from, to) = (
+                interpolation.coordinates_from_srgb(blue),
+                interpolation.coordinates_from_srgb(yellow),
+            );
+            let mut worst: f64 = 0.0;
+            for (&value, pixel) in values.iter().zip(image.chunks_exact(4)) {
+                let position = super::palette_position(
+                    f64::from(value),
+                    reverse,
+                    cyclic,
+                    options.color_density,
+                    min,
+                    max,
+                    None,
+                    0.0,
+                    options.palette_transfer(),
+                )
+                .unwrap();
+                let exact =
+                    interpolation.coordinates_to_srgb(interpolation.mix(from, to, position));
+                for channel in 0..3 {
+                    let rendered = f64::from(pixel[channel]) / 65535.0;
+                    worst = worst.max((rendered - exact[channel].clamp(0.0, 1.0)).abs());
+                }
+            }
+            assert!(worst * 255.0 <= bound, "{interpolation:?}: {worst}");
+        }
+    }
+
+    #[test]
+    fn test_palette_interpolation_option() {
+        use crate::InterpolationSpace;
+
+        let values = [0.0f32, 25.0, 50.0, 75.0, 100.0];
+        let mut options = coloring_options("jet", 0, 100);
+        let default = super::recolor_values(&values, &options);
+
+        // Naming the palette's own space is a no-op.
+        options.palette_interpolation = Some(InterpolationSpace::Srgb);
+        assert_eq!(super::recolor_values(&values, &options), default);
+
+        // Jet re-blends between its own control points: the ends stay put,
+        // the cyan-to-yellow midpoint moves.
+        options.palette_interpolation = Some(InterpolationSpace::Oklab);
+        let jet_oklab = super::recolor_values(&values, &options);
+        for pixel in [0..4, 16..20] {
+            let round_trip = jet_oklab[pixel.clone()].iter().zip(&default[pixel]);
+            assert!(
+                round_trip.clone().all(|(a, b)| a.abs_diff(*b) <= 1),
+                "{round_trip:?}"
+            );
+        }
+        assert_ne!(&jet_oklab[8..12], &default[8..12]);
+
+        // Re-blending a reverse-ordered palette keeps its orientation.
+        let mut blues = coloring_options("blues", 0, 100);
+        let blues_default = super::recolor_values(&values, &blues);
+        blues.palette_interpolation = Some(InterpolationSpace::Oklab);
+        let blues_oklab = super::recolor_values(&values, &blues);
+        assert_eq!(&blues_oklab[0..4], &blues_default[0..4]);
+        assert_eq!(&blues_oklab[16..20], &blues_default[16..20]);
+
+        // A sparse palette shows the difference between its entries.
+        let imported =
+            crate::import_palette_file(crate::PaletteFileFormat::Map, "255 0 0\n0 255 0\n")
+                .unwrap();
+        let mut sparse = coloring_options(&imported[0].name, 0, 100);
+        assert_eq!(
+            &super::recolor_values(&values, &sparse)[8..12],
+            &[128, 128, 0, 255]
+        );
+        sparse.palette_interpolation = Some(InterpolationSpace::Oklab);
+        let oklab = super::recolor_values(&values, &sparse);
+        assert_eq!(&oklab[0..4], &[255, 0, 0, 255]);
+        assert!(oklab[8] > 160 && oklab[9] > 160, "{:?}", &oklab[8..12]);
+        assert_eq!(&oklab[16..20], &[0, 255, 0, 255]);
+    }
+
     #[test]
     fn test_cyclical_palettes_wrap_seamlessly() {
         for name in ["rainbow", "sinebow"] {
@@ -1959,7 +2181,7 @@ mod lib_test {
         let color = |value: f64, cycles: u32, table: Option<&[f32]>| {
             super::color_from_smoothed_value(
                 value,
-                palette,
+                &palette,
                 reverse,
                 cyclic,
                 cycles,
@@ -1971,6 +2193,7 @@ mod lib_test {
                 100.0,
                 table,
                 0.0,
+                super::PaletteTransfer::Linear,
             )
         };
 
@@ -2594,18 +2817,9 @@ mod lib_test {
     ) -> super::ColoringOptions {
         super::ColoringOptions {
             palette: scheme.to_string(),
-            reverse_colors: false,
-            shift_hue_amount: 0.0,
-            saturate_amount: 0.0,
-            lighten_amount: 0.0,
-            color_space: 0, // Hsl
             palette_min_iter: palette_min,
             palette_max_iter: palette_max,
-            color_density: 1,
-            distance_estimate: false,
-            atom_domain: false,
-            palette_cdf: None,
-            palette_offset: 0.0,
+            ..Default::default()
         }
     }
 
@@ -2710,6 +2924,557 @@ mod lib_test {
         assert!(tile.min_iter >= 0, "Stats are still reported");
     }
 
+    /// A 32x32 turbo tile through `render_tile_precise` with the given
+    /// anti-aliasing settings (escape-time, smooth coloring).
+    fn antialiased_tile(
+        origin: (&str, &str),
+        tile: (f64, f64, f64, f64),
+        tile_zoom: i32,
+        zoom_offset: u32,
+        max_iterations: u32,
+        supersampling: u32,
+        adaptive: bool,
+    ) -> super::RenderedTile {
+        let view = super::TileRenderOptions::axis_aligned_view(
+            origin.0.to_string(),
+            origin.1.to_string(),
+            super::TileBounds {
+                x_min: tile.0,
+                x_max: tile.1,
+                y_min: tile.2,
+                y_max: tile.3,
+                zoom: tile_zoom,
+            },
+            zoom_offset,
+            max_iterations,
+            2,
+            32,
+            32,
+            true,
+        );
+        super::render_tile_precise(
+            &crate::progressive::PixelSampler::new(&view),
+            &super::SelectedPalette::named("turbo", false),
+            0.0,
+            0.0,
+            0.0,
+            crate::ValidColorSpace::Hsl,
+            0,
+            as_i32(max_iterations),
+            1,
+            None,
+            0.0,
+            super::PaletteTransfer::Linear,
+            supersampling,
+            adaptive,
+        )
+    }
+
+    /// Indices of the pixels whose RGB differs between two RGBA images.
+    fn changed_pixels(a: &[u8], b: &[u8]) -> Vec<usize> {
+        a.chunks_exact(super::NUM_COLOR_CHANNELS)
+            .zip(b.chunks_exact(super::NUM_COLOR_CHANNELS))
+            .enumerate()
+            .filter(|(_, (a, b))| a[..3] != b[..3])
+            .map(|(pixel, _)| pixel)
+            .collect()
+    }
+
+    #[test]
+    fn test_supersampling_factor_one_is_aliased_render() {
+        let view = (1.3, 3.2, 1.8, 3.3);
+        let aliased = tile_precise_image("0", "0", view, 2, 0, 200);
+        for factor in [0, 1] {
+            let tile = antialiased_tile(("0", "0"), view, 2, 0, 200, factor, false);
+            assert_eq!(tile.image, aliased, "factor {factor}");
+        }
+    }
+
+    #[test]
+    fn test_supersampling_smooths_edges_and_keeps_values() {
+        let view = (1.3, 3.2, 1.8, 3.3);
+        let aliased = antialiased_tile(("0", "0"), view, 2, 0, 200, 1, false);
+        let smoothed = antialiased_tile(("0", "0"), view, 2, 0, 200, 3, false);
+
+        assert_eq!(
+            smoothed.values, aliased.values,
+            "Cached values keep the center sample"
+        );
+        assert_eq!(smoothed.stats.range, aliased.stats.range);
+        assert!(!changed_pixels(&smoothed.image, &aliased.image).is_empty());
+        assert!(smoothed
+            .image
+            .iter()
+            .skip(3)
+            .step_by(super::NUM_COLOR_CHANNELS)
+            .all(|&alpha| alpha == 255));
+
+        // Jitter is a fixed hash, so re-rendering is reproducible.
+        let again = antialiased_tile(("0", "0"), view, 2, 0, 200, 3, false);
+        assert_eq!(again.image, smoothed.image);
+
+        // A boundary pixel blends interior black with exterior color, so
+        // its brightness falls between the two.
+        let boundary = (0..32 * 32)
+            .find(|&pixel| {
+                let value = aliased.values[pixel];
+                !value.is_finite()
+                    && smoothed.image[pixel * super::NUM_COLOR_CHANNELS..][..3] != [0, 0, 0]
+            })
+            .expect("an interior pixel on the boundary picks up exterior color");
+        assert!(aliased.image[boundary * super::NUM_COLOR_CHANNELS..][..3] == [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_supersampling_skips_interior_tiles() {
+        let view = (655.0, 656.0, 655.0, 656.0);
+        let tile = antialiased_tile(("0", "0"), view, 10, 0, 200, 4, true);
+        assert_eq!(tile.image, super::create_solid_black_image(32, 32));
+    }
+
+    #[test]
+    fn test_adaptive_antialiasing_resamples_only_edges() {
+        let view = (1.3, 3.2, 1.8, 3.3);
+        let aliased = antialiased_tile(("0", "0"), view, 2, 0, 200, 1, false);
+        let full = antialiased_tile(("0", "0"), view, 2, 0, 200, 3, false);
+        let adaptive = antialiased_tile(("0", "0"), view, 2, 0, 200, 3, true);
+
+        let adaptive_changes = changed_pixels(&adaptive.image, &aliased.image);
+        let full_changes = changed_pixels(&full.image, &aliased.image);
+        assert!(!adaptive_changes.is_empty());
+        assert!(adaptive_changes.len() < full_changes.len());
+
+        // Resampled pixels use the same jittered grid as full supersampling;
+        // every other pixel keeps its single sample.
+        let channels = super::NUM_COLOR_CHANNELS;
+        for pixel in 0..32 * 32 {
+            let adaptive_pixel = &adaptive.image[pixel * channels..][..channels];
+            assert!(
+                adaptive_pixel == &aliased.image[pixel * channels..][..channels]
+                    || adaptive_pixel == &full.image[pixel * channels..][..channels],
+                "pixel {pixel}"
+            );
+        }
+
+        // Adaptive mode resamples at least 2x2 even at factor 0 or 1.
+        let minimal = antialiased_tile(("0", "0"), view, 2, 0, 200, 0, true);
+        assert_ne!(minimal.image, aliased.image);
+    }
+
+    #[test]
+    fn test_supersampling_feeds_the_high_bit_depth_outputs() {
+        let tile = |supersampling, output_format| super::TileRenderOptions {
+            supersampling,
+            output_format,
+            ..crate::test_fixtures::full_set_tile()
+        };
+        let aliased = tile(1, super::OutputFormat::Rgba16).render_layered();
+        let smoothed = tile(3, super::OutputFormat::Rgba16).render_layered();
+        assert_ne!(smoothed.image16, aliased.image16);
+        // The 8-bit image is the 16-bit one quantized, resampled pixels too.
+        for (&wide, &narrow) in smoothed.image16.iter().zip(&smoothed.image) {
+            let narrowed = (f64::from(wide) / 257.0).round();
+            assert!(
+                (narrowed - f64::from(narrow)).abs() <= 1.0,
+                "{wide} vs {narrow}"
+            );
+        }
+
+        let linear = tile(3, super::OutputFormat::LinearRgbF32).render_layered();
+        let aliased_linear = tile(1, super::OutputFormat::LinearRgbF32).render_layered();
+        assert_ne!(linear.linear_rgb, aliased_linear.linear_rgb);
+        for (pixel, rgb) in linear.linear_rgb.chunks_exact(3).enumerate() {
+            let narrow = &smoothed.image[pixel * super::NUM_COLOR_CHANNELS..][..3];
+            for channel in 0..3 {
+                let encoded = super::linear_to_srgb(f64::from(rgb[channel]));
+                let narrowed = (encoded * 255.0).round();
+                assert!((narrowed - f64::from(narrow[channel])).abs() <= 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_supersampling_perturbation_path() {
+        // Same deep view as test_get_mandelbrot_tile_precise_deep_zoom.
+        let view = (2621.0, 2622.0, 2621.0, 2622.0);
+        let origin = ("-0.7436438870371587", "0.1318259042053119");
+        let aliased = antialiased_tile(origin, view, 12, 40, 100_000, 1, false);
+        let smoothed = antialiased_tile(origin, view, 12, 40, 100_000, 2, false);
+
+        assert_eq!(smoothed.tier as u8, super::RenderTier::Perturbation as u8);
+        assert_eq!(smoothed.values, aliased.values);
+        assert_ne!(smoothed.image, aliased.image);
+    }
+
+    #[test]
+    fn test_supersampling_fixed_palette_modes() {
+        // Distance-estimate and atom-domain tiles resample through their own
+        // kernels and keep their cached values.
+        let view = (1.3, 3.2, 1.8, 3.3);
+        for (distance_estimate, atom_domain) in [(true, false), (false, true)] {
+            let options = super::TileRenderOptions::axis_aligned_view(
+                "0".to_string(),
+                "0".to_string(),
+                super::TileBounds {
+                    x_min: view.0,
+                    x_max: view.1,
+                    y_min: view.2,
+                    y_max: view.3,
+                    zoom: 2,
+                },
+                0,
+                200,
+                2,
+                32,
+                32,
+                true,
+            );
+            let coloring = super::ColoringOptions {
+                distance_estimate,
+                atom_domain,
+                ..super::ColoringOptions::default()
+            };
+            let render = |supersampling| {
+                super::render_tile_precise(
+                    &crate::progressive::PixelSampler::with_coloring(&options, &coloring),
+                    &super::SelectedPalette::named("turbo", false),
+                    0.0,
+                    0.0,
+                    0.0,
+                    crate::ValidColorSpace::Hsl,
+                    0,
+                    200,
+                    1,
+                    None,
+                    0.0,
+                    super::PaletteTransfer::Linear,
+                    supersampling,
+                    false,
+                )
+            };
+            let aliased = render(1);
+            let smoothed = render(2);
+            assert_eq!(smoothed.values, aliased.values);
+            assert_ne!(smoothed.image, aliased.image);
+        }
+    }
+
+    /// A 32x32 turbo tile over the given view, rendered through
+    /// `render_tile_precise` with a view transform (a rotation in degrees
+    /// and an optional matrix, as `TileRenderOptions` takes them;
+    /// escape-time unless a fixed-palette mode is selected, smooth coloring
+    /// off).
+    fn transformed_tile(
+        tile: (f64, f64, f64, f64),
+        tile_zoom: i32,
+        (rotation, transform_matrix): (f64, Option<[f64; 4]>),
+        distance_estimate: bool,
+        atom_domain: bool,
+    ) -> super::RenderedTile {
+        let options = super::TileRenderOptions {
+            rotation,
+            transform_matrix,
+            ..super::TileRenderOptions::axis_aligned_view(
+                "0".to_string(),
+                "0".to_string(),
+                super::TileBounds {
+                    x_min: tile.0,
+                    x_max: tile.1,
+                    y_min: tile.2,
+                    y_max: tile.3,
+                    zoom: tile_zoom,
+                },
+                0,
+                200,
+                2,
+                32,
+                32,
+                false,
+            )
+        };
+        let coloring = super::ColoringOptions {
+            distance_estimate,
+            atom_domain,
+            ..super::ColoringOptions::default()
+        };
+        super::render_tile_precise(
+            &crate::progressive::PixelSampler::with_coloring(&options, &coloring),
+            &super::SelectedPalette::named("turbo", false),
+            0.0,
+            0.0,
+            0.0,
+            crate::ValidColorSpace::Hsl,
+            0,
+            200,
+            1,
+            None,
+            0.0,
+            super::PaletteTransfer::Linear,
+            1,
+            false,
+        )
+    }
+
+    #[test]
+    fn test_view_rotation_matches_reflection() {
+        // A half turn negates both offsets; the set is symmetric about the
+        // real axis, so that matches negating only the real offset. Quarter
+        // turns are exact, so the two renders agree bit-for-bit.
+        use crate::perturbation::ViewTransform;
+        let view = (1.3, 3.2, 1.8, 3.3);
+        let rotated = transformed_tile(view, 2, (180.0, None), false, false);
+        let reflected = transformed_tile(view, 2, (0.0, Some([-1.0, 0.0, 0.0, 1.0])), false, false);
+        let axis_aligned = transformed_tile(view, 2, (0.0, None), false, false);
+
+        assert_eq!(rotated.values, reflected.values);
+        assert_eq!(rotated.image, reflected.image);
+        assert_eq!(rotated.stats.range, reflected.stats.range);
+        assert_ne!(rotated.values, axis_aligned.values);
+
+        // Recoloring a transformed tile's values reproduces its image.
+        assert_eq!(
+            super::recolor_values(&rotated.values, &coloring_options("turbo", 0, 200)),
+            rotated.image
+        );
+
+        // A half turn composed with a point reflection is the identity and
+        // takes the axis-aligned kernels.
+        let undone = transformed_tile(view, 2, (180.0, Some([-1.0, 0.0, 0.0, -1.0])), false, false);
+        assert!(ViewTransform::new(180.0, Some([-1.0, 0.0, 0.0, -1.0])).is_identity());
+        assert_eq!(undone.image, axis_aligned.image);
+    }
+
+    #[test]
+    fn test_view_rotation_fixed_palette_modes() {
+        let view = (1.3, 3.2, 1.8, 3.3);
+        let rotation = (180.0, None);
+        let reflection = (0.0, Some([-1.0, 0.0, 0.0, 1.0]));
+        for (distance_estimate, atom_domain) in [(true, false), (false, true)] {
+            let rotated = transformed_tile(view, 2, rotation, distance_estimate, atom_domain);
+            let reflected = transformed_tile(view, 2, reflection, distance_estimate, atom_domain);
+            assert_eq!(rotated.values, reflected.values);
+            assert!(rotated.stats.range.is_none(), "Fixed palette: no range");
+        }
+    }
+
+    #[test]
+    fn test_rotated_interior_tile_is_black() {
+        // A small tile around the origin stays inside the set at any angle.
+        let tile = transformed_tile((655.0, 656.0, 655.0, 656.0), 10, (45.0, None), false, false);
+        assert_eq!(tile.image, super::create_solid_black_image(32, 32));
+        assert!(tile.values.iter().all(|value| value.is_infinite()));
+    }
+
+    fn exponential_map_strip(
+        outer_radius_log2: f64,
+        exponent: u32,
+        image_height: usize,
+    ) -> super::RenderedTile {
+        super::generate_exponential_map_image(
+            "-0.743643887037158",
+            "0.131825904205311",
+            outer_radius_log2,
+            500,
+            exponent,
+            32,
+            image_height,
+            &super::SelectedPalette::named("turbo", false),
+            0.0,
+            0.0,
+            0.0,
+            crate::ValidColorSpace::Hsl,
+            false,
+            0,
+            500,
+            1,
+            None,
+            0.0,
+            super::PaletteTransfer::Linear,
+        )
+    }
+
+    #[test]
+    fn test_generate_exponential_map_image() {
+        let strip = exponential_map_strip(-2.0, 2, 48);
+        assert_eq!(strip.image.len(), 32 * 48 * super::NUM_COLOR_CHANNELS);
+        assert_eq!(strip.tier as u8, super::RenderTier::FloatExp as u8);
+        let (min_iter, max_iter) = strip.stats.range.expect("the strip escapes");
+        assert!(min_iter < max_iter);
+        assert_eq!(
+            super::recolor_values(&strip.values, &coloring_options("turbo", 0, 500)),
+            strip.image,
+            "Strips recolor like tiles"
+        );
+
+        // A strip rendered in two chunks lines up with the single strip.
+        let step = crate::perturbation::exponential_map_row_step_log2(32);
+        let top = exponential_map_strip(-2.0, 2, 24);
+        let bottom = exponential_map_strip(-2.0 - 24.0 * step, 2, 24);
+        let chunked: Vec<f32> = top.values.into_iter().chain(bottom.values).collect();
+        let matching = chunked
+            .iter()
+            .zip(&strip.values)
+            .filter(|(chunk, whole)| chunk == whole)
+            .count();
+        assert!(matching as f64 / strip.values.len() as f64 > 0.99);
+
+        // No perturbation formula for exponent 1: a black strip, like a tile
+        // whose frame fails.
+        let unsupported = exponential_map_strip(-2.0, 1, 8);
+        assert_eq!(unsupported.image, super::create_solid_black_image(32, 8));
+    }
+
+    fn zoom_out_sequence(
+        zoom: i32,
+        keyframe_count: Option<usize>,
+        start_index: usize,
+        max_iterations: u32,
+    ) -> super::ZoomOutSequence {
+        super::ZoomOutSequence::from_options(super::ZoomOutOptions {
+            origin_re: "-0.7436438870371587".to_string(),
+            origin_im: "0.1318259042053119".to_string(),
+            zoom,
+            keyframe_count,
+            start_index,
+            max_iterations,
+            power: 2,
+            image_width: 33,
+            image_height: 17,
+            smooth_coloring: false,
+            include_values: true,
+            coloring: coloring_options("turbo", 0, as_i32(max_iterations)),
+        })
+    }
+
+    #[test]
+    fn test_zoom_out_sequence_metadata() {
+        let sequence = zoom_out_sequence(20, None, 0, 2000);
+        assert_eq!(sequence.keyframe_count(), 21, "Down to zoom 0");
+        assert_eq!(zoom_out_sequence(20, Some(5), 0, 2000).keyframe_count(), 5);
+        assert_eq!(zoom_out_sequence(3, Some(10), 0, 2000).keyframe_count(), 4);
+
+        for index in [0, 7, 20] {
+            let zoom = sequence.keyframe_zoom(index);
+            assert_eq!(zoom, 20 - index as i32);
+            // The map's pixel spacing at this zoom: a 200-pixel tile spans
+            // 1.5625 * 2^-(zoom - 2).
+            assert_eq!(
+                sequence.keyframe_pixel_spacing_log2(index),
+                -f64::from(zoom + 5)
+            );
+        }
+        assert_eq!(
+            zoom_out_sequence(3000, None, 0, 2000).keyframe_pixel_spacing_log2(0),
+            -3005.0,
+            "Spacing stays finite past f64's range"
+        );
+    }
+
+    #[test]
+    fn test_zoom_out_sequence_keyframes() {
+        let mut sequence = zoom_out_sequence(8, Some(3), 0, 2000);
+        let mut keyframes = Vec::new();
+        while let Some(keyframe) = sequence.next_keyframe() {
+            keyframes.push(keyframe);
+            assert_eq!(sequence.completed_keyframes(), keyframes.len());
+        }
+        assert_eq!(keyframes.len(), 3);
+        assert!(sequence.next_keyframe().is_none());
+
+        // Each keyframe zooms out 2x about the shared center, so a pixel
+        // `d` from the center of one keyframe shows the point `2d` from the
+        // center of the previous one.
+        let (width, center_column, center_row) = (33, 16, 8);
+        for pair in keyframes.windows(2) {
+            let (deeper, shallower) = (&pair[0], &pair[1]);
+            let mut matching = 0;
+            let mut total = 0;
+            for dy in -4..=4_i32 {
+                for dx in -8..=8_i32 {
+                    let at = |row: i32, column: i32| (row as usize) * width + column as usize;
+                    let deep = deeper.values[at(center_row + 2 * dy, center_column + 2 * dx)];
+                    let shallow = shallower.values[at(center_row + dy, center_column + dx)];
+                    if deep == shallow {
+                        matching += 1;
+                    }
+                    total += 1;
+                }
+            }
+            assert!(matching * 100 >= total * 95, "{matching}/{total}");
+        }
+
+        // Resuming at a later keyframe renders exactly what the full
+        // sequence rendered there.
+        let mut resumed = zoom_out_sequence(8, Some(3), 2, 2000);
+        assert_eq!(resumed.completed_keyframes(), 2);
+        let keyframe = resumed.next_keyframe().unwrap();
+        assert_eq!(keyframe.image, keyframes[2].image);
+        assert!(resumed.next_keyframe().is_none());
+    }
+
+    #[test]
+    fn test_zoom_out_sequence_deep_keyframes() {
+        // Past the direct cutoff every keyframe shares one reference orbit.
+        super::perturbation::clear_orbit_cache();
+        let mut sequence = zoom_out_sequence(60, Some(3), 0, 100_000);
+        let mut keyframes = 0;
+        while let Some(keyframe) = sequence.next_keyframe() {
+            assert_eq!(keyframe.tier, super::RenderTier::Perturbation as u8);
+            assert!(keyframe.min_iter >= 0, "The target's surroundings escape");
+            keyframes += 1;
+        }
+        assert_eq!(keyframes, 3);
+
+        // The `parallel` build's cache is shared with concurrent tests.
+        #[cfg(not(feature = "parallel"))]
+        {
+            let stats = super::perturbation::orbit_cache_stats();
+            assert_eq!((stats.misses, stats.extensions), (1, 0));
+            assert!(stats.hits >= 2, "Later keyframes reuse the first's orbit");
+        }
+        super::perturbation::clear_orbit_cache();
+    }
+
+    #[test]
+    fn test_sample_jitter_range() {
+        let mut jitters: Vec<f64> = (0..64)
+            .map(|sample| super::sample_jitter(3, 7, sample, 0))
+            .collect();
+        assert!(jitters.iter().all(|jitter| (0.0..1.0).contains(jitter)));
+        assert_ne!(
+            super::sample_jitter(3, 7, 0, 0),
+            super::sample_jitter(3, 7, 0, 1),
+            "Axes jitter independently"
+        );
+        jitters.sort_by(f64::total_cmp);
+        jitters.dedup();
+        assert_eq!(jitters.len(), 64, "Samples jitter independently");
+    }
+
+    #[test]
+    fn test_values_differ_strongly() {
+        let thresholds = (0.0, 320.0);
+        // A tenth of an iteration is far below a thirty-second of the palette.
+        assert!(!super::values_differ_strongly(10.0, 10.1, thresholds, 1));
+        assert!(super::values_differ_strongly(10.0, 20.0, thresholds, 1));
+        // Color cycles compress the palette, so smaller steps count.
+        assert!(!super::values_differ_strongly(10.0, 14.0, thresholds, 1));
+        assert!(super::values_differ_strongly(10.0, 14.0, thresholds, 4));
+        // Interior against exterior always differs; interior pairs never do.
+        assert!(super::values_differ_strongly(
+            10.0,
+            f32::INFINITY,
+            thresholds,
+            1
+        ));
+        assert!(!super::values_differ_strongly(
+            f32::INFINITY,
+            f32::INFINITY,
+            thresholds,
+            1
+        ));
+    }
+
     #[test]
     fn test_recolor_tile_matches_render() {
         // Without smooth coloring the cached values are whole iteration
@@ -2789,8 +3554,7 @@ mod lib_test {
                 2,
                 32,
                 32,
-                "inferno",
-                false,
+                &super::SelectedPalette::named("inferno", false),
                 0.0,
                 0.0,
                 0.0,
@@ -2801,6 +3565,7 @@ mod lib_test {
                 1,
                 table,
                 0.0,
+                super::PaletteTransfer::Linear,
             )
         };
 
@@ -2827,6 +3592,124 @@ mod lib_test {
         );
     }
 
+    #[test]
+    fn test_recolor_with_transfer_matches_render() {
+        // Like the CDF, the transfer curve is resolved from ColoringOptions
+        // for both renders and recolors, so they stay byte-identical.
+        let max_iterations = 200;
+        let mut coloring = coloring_options("inferno", 0, as_i32(max_iterations));
+        coloring.transfer_function = super::TransferFunction::Log;
+        let render = |transfer| {
+            super::generate_mandelbrot_set_image(
+                -2.0,
+                1.0,
+                -1.2,
+                1.2,
+                max_iterations,
+                2,
+                32,
+                32,
+                &super::SelectedPalette::named("inferno", false),
+                0.0,
+                0.0,
+                0.0,
+                crate::ValidColorSpace::Hsl,
+                false,
+                0,
+                as_i32(max_iterations),
+                1,
+                None,
+                0.0,
+                transfer,
+            )
+        };
+
+        let rendered = render(coloring.palette_transfer());
+        assert_eq!(
+            super::recolor_values(&rendered.values, &coloring),
+            rendered.image,
+            "Recolor with the render's transfer curve must match the render"
+        );
+        assert_ne!(
+            rendered.image,
+            render(super::PaletteTransfer::Linear).image,
+            "The log curve changes the output"
+        );
+    }
+
+    #[test]
+    fn test_palette_transfer_curves() {
+        use super::PaletteTransfer;
+
+        let curves = [
+            PaletteTransfer::Linear,
+            PaletteTransfer::Sqrt,
+            PaletteTransfer::CubeRoot,
+            PaletteTransfer::Log { scale: 1.0 },
+            PaletteTransfer::Log { scale: 1000.0 },
+            PaletteTransfer::LogLog { scale: 1000.0 },
+            PaletteTransfer::Power { gamma: 0.5 },
+            PaletteTransfer::Power { gamma: 2.2 },
+        ];
+        for curve in curves {
+            // Every curve keeps the palette window's ends and its order.
+            assert_eq!(curve.apply(0.0), 0.0, "{curve:?}");
+            assert!((curve.apply(1.0) - 1.0).abs() < 1e-12, "{curve:?}");
+            let mut previous = 0.0;
+            for step in 1..=100 {
+                let mapped = curve.apply(f64::from(step) / 100.0);
+                assert!(mapped > previous, "{curve:?} is not increasing");
+                previous = mapped;
+            }
+        }
+
+        assert_eq!(PaletteTransfer::Sqrt.apply(0.25), 0.5);
+        assert!((PaletteTransfer::CubeRoot.apply(0.125) - 0.5).abs() < 1e-12);
+        assert_eq!(PaletteTransfer::Power { gamma: 2.0 }.apply(0.5), 0.25);
+        // log(1 + 9x) / log(10): one iteration above the minimum of a
+        // 9-iteration window lands at log10(2).
+        let log = PaletteTransfer::Log { scale: 9.0 }.apply(1.0 / 9.0);
+        assert!((log - 2f64.log10()).abs() < 1e-12);
+        // Log-log compresses harder than log over the same window.
+        let scale = 1000.0;
+        assert!(
+            PaletteTransfer::LogLog { scale }.apply(0.01)
+                > PaletteTransfer::Log { scale }.apply(0.01)
+        );
+    }
+
+    #[test]
+    fn test_palette_transfer_resolution() {
+        use super::{PaletteTransfer, TransferFunction};
+
+        let mut options = coloring_options("turbo", 100, 600);
+        assert_eq!(options.palette_transfer(), PaletteTransfer::Linear);
+
+        // The log curves scale by the palette window's span.
+        options.transfer_function = TransferFunction::Log;
+        assert_eq!(
+            options.palette_transfer(),
+            PaletteTransfer::Log { scale: 500.0 }
+        );
+        options.distance_estimate = true;
+        assert_eq!(
+            options.palette_transfer(),
+            PaletteTransfer::Log { scale: 1.0 }
+        );
+
+        // Power needs a usable gamma; anything else stays linear.
+        options.transfer_function = TransferFunction::Power;
+        for gamma in [None, Some(0.0), Some(-1.0), Some(f64::NAN)] {
+            options.transfer_gamma = gamma;
+            assert_eq!(options.palette_transfer(), PaletteTransfer::Linear);
+        }
+        options.transfer_gamma = Some(0.45);
+        assert_eq!(
+            options.palette_transfer(),
+            PaletteTransfer::Power { gamma: 0.45 }
+        );
+    }
+
     #[test]
     fn test_fixed_palette_modes_ignore_cdf() {
         // Distance-estimate and atom-domain values are already normalized to
@@ -2860,4 +3743,120 @@ mod lib_test {
 
         assert_eq!(img, super::create_solid_black_image(4, 4));
     }
+
+    #[test]
+    fn test_recolor_rgba16_tracks_8bit_output() {
+        // The 16-bit output samples the same palette positions as the 8-bit
+        // one, minus the per-step u8 rounding: scaled down it must land
+        // within one 8-bit level everywhere, on colorous gradients and LUT
+        // palettes alike.
+        let values: Vec<f32> = (0..400)
+            .map(|i| i as f32 * 0.5)
+            .chain([f32::INFINITY])
+            .collect();
+        for scheme in ["turbo", "inferno", "jet", "greys"] {
+            let coloring = coloring_options(scheme, 0, 200);
+
+            let img8 = super::recolor_values(&values, &coloring);
+            let img16 = super::recolor_values_rgba16(&values, &coloring);
+            assert_eq!(img16.len(), img8.len());
+            for (&low, &high) in img8.iter().zip(&img16) {
+                let scaled = f64::from(high) / 257.0;
+                assert!(
+                    (scaled - f64::from(low)).abs() <= 1.0,
+                    "{scheme}: 16-bit {high} strays from 8-bit {low}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_recolor_rgba16_resolves_slow_gradients() {
+        // A narrow slice of a gradient spans only a few 8-bit levels; the
+        // 16-bit output must resolve many more distinct steps across it.
+        let values: Vec<f32> = (0..1000).map(|i| 100.0 + i as f32 * 0.001).collect();
+        let coloring = coloring_options("greys", 0, 200);
+
+        let distinct = |channel: Vec<u32>| {
+            let mut channel = channel;
+            channel.dedup();
+            channel.len()
+        };
+        let img8 = super::recolor_values(&values, &coloring);
+        let img16 = super::recolor_values_rgba16(&values, &coloring);
+        let levels8 = distinct(img8.iter().step_by(4).map(|&v| u32::from(v)).collect());
+        let levels16 = distinct(img16.iter().step_by(4).map(|&v| u32::from(v)).collect());
+        assert!(levels8 <= 3, "8-bit output has {levels8} levels");
+        assert!(levels16 > 50, "16-bit output has only {levels16} levels");
+        assert!(img16
+            .iter()
+            .skip(3)
+            .step_by(4)
+            .all(|&alpha| alpha == u16::MAX));
+    }
+
+    #[test]
+    fn test_recolor_linear_rgb() {
+        let values = [0.0, 100.0, 200.0, f32::INFINITY];
+        let coloring = coloring_options("greys", 0, 200);
+        let linear = super::recolor_values_linear_rgb(&values, &coloring);
+        let img16 = super::recolor_values_rgba16(&values, &coloring);
+
+        assert_eq!(linear.len(), values.len() * 3);
+        assert_eq!(&linear[9..], &[0.0, 0.0, 0.0], "Interior stays black");
+        for (pixel, rgba) in linear.chunks_exact(3).zip(img16.chunks_exact(4)) {
+            for channel in 0..3 {
+                // Encoding the linear value back to sRGB recovers the
+                // 16-bit sample.
+                let linear_value = f64::from(pixel[channel]);
+                let encoded = if linear_value <= 0.0031308 {
+                    linear_value * 12.92
+                } else {
+                    1.055 * linear_value.powf(1.0 / 2.4) - 0.055
+                };
+                assert!((encoded * 65535.0 - f64::from(rgba[channel])).abs() < 2.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_subdivision_fills_enclosed_interior() {
+        // Main cardioid interior, with exterior past the cusp at 0.25.
+        let (width, height) = (64, 64);
+        let re_values: Vec<f64> = (0..width)
+            .map(|x| -0.5 + 0.8 * x as f64 / (width - 1) as f64)
+            .collect();
+        let im_values: Vec<f64> = (0..height)
+            .map(|y| 0.3 - 0.6 * y as f64 / (height - 1) as f64)
+            .collect();
+        let point = |pixel: usize| (re_values[pixel % width], im_values[pixel / width]);
+
+        let mut computed = 0;
+        let mut results =
+            vec![(super::UNCOMPUTED, super::Complex64::new(0.0, 0.0)); width * height];
+        super::subdivide_tile_streamed(
+            width,
+            height,
+            MAX_ITERATIONS,
+            &mut results,
+            |pixels, wave_results| {
+                computed += pixels.len();
+                for (result, &pixel) in wave_results.iter_mut().zip(pixels) {
+                    let (re, im) = point(pixel);
+                    *result = super::calculate_escape_iterations(re, im, MAX_ITERATIONS, 2);
+                }
+            },
+        );
+
+        assert!(
+            computed < width * height / 2,
+            "computed {computed} of {} pixels",
+            width * height
+        );
+        for (pixel, &(escape_iterations, _)) in results.iter().enumerate() {
+            let (re, im) = point(pixel);
+            let expected = super::calculate_escape_iterations(re, im, MAX_ITERATIONS, 2).0;
+            assert_eq!(escape_iterations, expected, "pixel {pixel}");
+        }
+    }
 }
@@ -0,0 +1,671 @@
+//! Importers for palette files from other fractal tools.
+//!
+//! Fractint `.map` files, GIMP `.ggr` gradients and UltraFractal `.ugr`
+//! gradient libraries are parsed into the crate's `Palette` and registered
+//! under names derived from the file content (see `import_palette_file`),
+//! so an imported palette is used by passing its registered name as
+//! `ColoringOptions::palette`. The registry belongs to one wasm instance:
+//! each worker that renders with an imported palette must import the file
+//! itself, and a payload naming a palette its worker has not imported is
+//! rejected (see `deserialize_palette_name`) rather than drawn in the
+//! fallback palette. Features with no equivalent here — GIMP's
+//! foreground/background-colored endpoints, transparency in either gradient
+//! format — are rejected with an error naming them rather than approximated
+//! silently.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+use wasm_bindgen::prelude::*;
+
+use crate::{palette_from_fn, Palette};
+
+#[cfg(test)]
+#[path = "palette_import_test.rs"]
+mod palette_import_test;
+
+/// Prefix of the palette names imported palettes are registered under.
+pub const IMPORTED_PALETTE_PREFIX: &str = "imported:";
+
+/// Number of gradient positions in an UltraFractal gradient; `index` values
+/// address positions 0..400 around the (cyclic) gradient.
+const UGR_GRADIENT_LENGTH: f64 = 400.0;
+
+/// Supported palette file formats.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Hash, PartialEq)]
+pub enum PaletteFileFormat {
+    /// Fractint `.map`: one `r g b` line (0..255) per color.
+    Map,
+    /// GIMP `.ggr` gradient.
+    Ggr,
+    /// UltraFractal `.ugr` gradient library, possibly holding many entries.
+    Ugr,
+}
+
+/// One palette parsed from a file, before registration.
+struct ParsedPalette {
+    title: String,
+    palette: Palette,
+    cyclic: bool,
+}
+
+/// A registered imported palette: its title from the file, and the name to
+/// pass as `ColoringOptions::palette`.
+#[wasm_bindgen]
+pub struct ImportedPalette {
+    #[wasm_bindgen(getter_with_clone)]
+    pub title: String,
+    #[wasm_bindgen(getter_with_clone)]
+    pub name: String,
+}
+
+fn parse_number<T: std::str::FromStr>(field: &str, what: &str, line: usize) -> Result<T, String> {
+    field
+        .parse()
+        .map_err(|_| format!("line {line}: invalid {what} {field:?}"))
+}
+
+/// Parses a Fractint `.map` file. Each non-blank line starts with three
+/// integer channels in 0..=255; anything after them is a comment. The
+/// entries become the lookup table as-is, so a standard 256-line map
+/// samples exactly like a built-in `palette_from_fn` palette.
+fn parse_map(contents: &str) -> Result<Palette, String> {
+    let mut lut = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        let mut fields = line.split_whitespace().peekable();
+        if fields.peek().is_none() {
+            continue;
+        }
+        let mut channel = || -> Result<u8, String> {
+            let field = fields
+                .next()
+                .ok_or_else(|| format!("line {line_number}: expected 3 channels"))?;
+            parse_number(field, "channel (expected 0..255)", line_number)
+        };
+        let (r, g, b) = (channel()?, channel()?, channel()?);
+        lut.push(colorous::Color { r, g, b });
+    }
+
+    if lut.len() < 2 {
+        return Err(format!(
+            "a palette needs at least 2 colors, got {}",
+            lut.len()
+        ));
+    }
+    Ok(Palette::Lut(lut))
+}
+
+/// GIMP's blend function across one segment, applied to the position
+/// within the segment with the midpoint rescaled to the segment too.
+#[derive(Clone, Copy)]
+enum GgrBlending {
+    Linear,
+    Curved,
+    Sine,
+    SphereIncreasing,
+    SphereDecreasing,
+    Step,
+}
+
+/// How GIMP blends a segment's endpoint colors.
+#[derive(Clone, Copy)]
+enum GgrColoring {
+    Rgb,
+    HsvCounterClockwise,
+    HsvClockwise,
+}
+
+#[derive(Clone, Copy)]
+struct GgrSegment {
+    left: f64,
+    middle: f64,
+    right: f64,
+    left_color: [f64; 3],
+    right_color: [f64; 3],
+    blending: GgrBlending,
+    coloring: GgrColoring,
+}
+
+/// Below this, GIMP treats a segment length or midpoint as degenerate.
+const GGR_EPSILON: f64 = 1e-10;
+
+impl GgrBlending {
+    /// Port of GIMP's `gimp_gradient_calc_*_factor` functions.
+    fn factor(self, middle: f64, position: f64) -> f64 {
+        let linear = || {
+            if position <= middle {
+                if middle < GGR_EPSILON {
+                    0.0
+                } else {
+                    0.5 * position / middle
+                }
+            } else if 1.0 - middle < GGR_EPSILON {
+                1.0
+            } else {
+                0.5 + 0.5 * (position - middle) / (1.0 - middle)
+            }
+        };
+        match self {
+            GgrBlending::Linear => linear(),
+            GgrBlending::Curved => {
+                if middle < GGR_EPSILON || 1.0 - position < GGR_EPSILON {
+                    1.0
+                } else {
+                    position.powf(0.5f64.ln() / middle.ln())
+                }
+            }
+            GgrBlending::Sine => {
+                ((-std::f64::consts::FRAC_PI_2 + std::f64::consts::PI * linear()).sin() + 1.0) / 2.0
+            }
+            GgrBlending::SphereIncreasing => {
+                let offset = linear() - 1.0;
+                (1.0 - offset * offset).sqrt()
+            }
+            GgrBlending::SphereDecreasing => {
+                let linear = linear();
+                1.0 - (1.0 - linear * linear).sqrt()
+            }
+            GgrBlending::Step => {
+                if position >= middle {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// RGB channels in [0, 1] to HSV with hue in [0, 1), as GIMP blends them.
+fn rgb_to_hsv([r, g, b]: [f64; 3]) -> [f64; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let hue = if delta <= 0.0 {
+        0.0
+    } else if max == r {
+        ((g - b) / delta).rem_euclid(6.0) / 6.0
+    } else if max == g {
+        ((b - r) / delta + 2.0) / 6.0
+    } else {
+        ((r - g) / delta + 4.0) / 6.0
+    };
+    let saturation = if max > 0.0 { delta / max } else { 0.0 };
+    [hue, saturation, max]
+}
+
+fn hsv_to_rgb([hue, saturation, value]: [f64; 3]) -> [f64; 3] {
+    let sector = hue.rem_euclid(1.0) * 6.0;
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    [r + m, g + m, b + m]
+}
+
+impl GgrSegment {
+    fn color_at(&self, t: f64) -> [f64; 3] {
+        let length = self.right - self.left;
+        let (middle, position) = if length < GGR_EPSILON {
+            (0.5, 0.5)
+        } else {
+            (
+                (self.middle - self.left) / length,
+                ((t - self.left) / length).clamp(0.0, 1.0),
+            )
+        };
+        let factor = self.blending.factor(middle, position);
+        let lerp = |a: f64, b: f64| a + (b - a) * factor;
+
+        match self.coloring {
+            GgrColoring::Rgb => {
+                [0, 1, 2].map(|channel| lerp(self.left_color[channel], self.right_color[channel]))
+            }
+            coloring => {
+                let from = rgb_to_hsv(self.left_color);
+                let to = rgb_to_hsv(self.right_color);
+                // GIMP sweeps hue the long way round when the endpoints are
+                // on the "wrong" side for the requested direction.
+                let hue = match coloring {
+                    GgrColoring::HsvCounterClockwise if from[0] >= to[0] => {
+                        from[0] + (1.0 - (from[0] - to[0])) * factor
+                    }
+                    GgrColoring::HsvClockwise if to[0] >= from[0] => {
+                        from[0] - (1.0 - (to[0] - from[0])) * factor
+                    }
+                    _ => lerp(from[0], to[0]),
+                };
+                hsv_to_rgb([hue, lerp(from[1], to[1]), lerp(from[2], to[2])])
+            }
+        }
+    }
+}
+
+/// Parses a GIMP `.ggr` gradient into its name and palette. Segment colors
+/// are taken as sRGB-encoded, as GIMP writes them.
+fn parse_ggr(contents: &str) -> Result<(String, Palette), String> {
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+
+    match lines.next() {
+        Some((_, "GIMP Gradient")) => {}
+        _ => return Err("not a GIMP gradient: missing \"GIMP Gradient\" header".to_string()),
+    }
+
+    let mut title = String::new();
+    let (mut line_number, mut line) = lines
+        .next()
+        .ok_or_else(|| "missing segment count".to_string())?;
+    if let Some(name) = line.strip_prefix("Name:") {
+        title = name.trim().to_string();
+        (line_number, line) = lines
+            .next()
+            .ok_or_else(|| "missing segment count".to_string())?;
+    }
+    let segment_count: usize = parse_number(line, "segment count", line_number)?;
+    if segment_count == 0 {
+        return Err("a gradient needs at least 1 segment".to_string());
+    }
+
+    let mut segments: Vec<GgrSegment> = Vec::with_capacity(segment_count);
+    for segment_index in 0..segment_count {
+        let (line_number, line) = lines
+            .next()
+            .ok_or_else(|| format!("expected {segment_count} segments, found {segment_index}"))?;
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if !(fields.len() == 13 || fields.len() == 15) {
+            return Err(format!(
+                "line {line_number}: expected 13 or 15 fields, got {}",
+                fields.len()
+            ));
+        }
+        let numbers = fields[..11]
+            .iter()
+            .map(|field| parse_number::<f64>(field, "number", line_number))
+            .collect::<Result<Vec<_>, _>>()?;
+        let codes = fields[11..]
+            .iter()
+            .map(|field| parse_number::<u32>(field, "type code", line_number))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (left, middle, right) = (numbers[0], numbers[1], numbers[2]);
+        if !(0.0 <= left && left <= middle && middle <= right && right <= 1.0) {
+            return Err(format!(
+                "line {line_number}: segment positions {left} {middle} {right} must be ordered within [0, 1]"
+            ));
+        }
+        if let Some(previous) = segments.last() {
+            if (left - previous.right).abs() > 1e-6 {
+                return Err(format!(
+                    "line {line_number}: segment starts at {left}, but the previous one ends at {}",
+                    previous.right
+                ));
+            }
+        }
+        for color in [&numbers[3..7], &numbers[7..11]] {
+            if color.iter().any(|channel| !(0.0..=1.0).contains(channel)) {
+                return Err(format!(
+                    "line {line_number}: color channels must be within [0, 1]"
+                ));
+            }
+            if color[3] < 1.0 {
+                return Err(format!("line {line_number}: transparency is not supported"));
+            }
+        }
+        let blending = match codes[0] {
+            0 => GgrBlending::Linear,
+            1 => GgrBlending::Curved,
+            2 => GgrBlending::Sine,
+            3 => GgrBlending::SphereIncreasing,
+            4 => GgrBlending::SphereDecreasing,
+            5 => GgrBlending::Step,
+            code => {
+                return Err(format!(
+                    "line {line_number}: unsupported blending type {code}"
+                ))
+            }
+        };
+        let coloring = match codes[1] {
+            0 => GgrColoring::Rgb,
+            1 => GgrColoring::HsvCounterClockwise,
+            2 => GgrColoring::HsvClockwise,
+            code => {
+                return Err(format!(
+                    "line {line_number}: unsupported coloring type {code}"
+                ))
+            }
+        };
+        if codes[2..].iter().any(|&code| code != 0) {
+            return Err(format!(
+                "line {line_number}: foreground/background-colored endpoints are not supported"
+            ));
+        }
+
+        segments.push(GgrSegment {
+            left,
+            middle,
+            right,
+            left_color: [numbers[3], numbers[4], numbers[5]],
+            right_color: [numbers[7], numbers[8], numbers[9]],
+            blending,
+            coloring,
+        });
+    }
+    if let Some((line_number, _)) = lines.next() {
+        return Err(format!(
+            "line {line_number}: more segments than the declared {segment_count}"
+        ));
+    }
+
+    let palette = palette_from_fn(|t| {
+        let index = segments
+            .partition_point(|segment| segment.right < t)
+            .min(segments.len() - 1);
+        segments[index].color_at(t)
+    });
+    Ok((title, palette))
+}
+
+/// Splits a `.ugr` line into `key=value` pairs, honoring quoted values
+/// (`title="Deep Sea"`).
+fn ugr_pairs(line: &str, line_number: usize) -> Result<Vec<(&str, &str)>, String> {
+    let mut pairs = Vec::new();
+    let mut rest = line.trim();
+    while !rest.is_empty() {
+        let (key, after_key) = rest
+            .split_once('=')
+            .ok_or_else(|| format!("line {line_number}: expected key=value, got {rest:?}"))?;
+        let (value, after_value) = if let Some(quoted) = after_key.strip_prefix('"') {
+            let end = quoted
+                .find('"')
+                .ok_or_else(|| format!("line {line_number}: unterminated quoted value"))?;
+            (&quoted[..end], &quoted[end + 1..])
+        } else {
+            after_key
+                .split_once(char::is_whitespace)
+                .unwrap_or((after_key, ""))
+        };
+        pairs.push((key.trim(), value));
+        rest = after_value.trim_start();
+    }
+    Ok(pairs)
+}
+
+/// Samples a cyclic list of `(position, rgb)` control points at `t`, with
+/// positions in [0, 1) sorted ascending. `smooth` interpolates with a
+/// Catmull-Rom spline through the points — an approximation of
+/// UltraFractal's smooth gradients — otherwise linearly.
+fn sample_cyclic(points: &[(f64, [f64; 3])], smooth: bool, t: f64) -> [f64; 3] {
+    let count = points.len();
+    // Point `i` of the cyclic sequence, with positions unwrapped so the
+    // sequence increases monotonically across the seam.
+    let point = |i: isize| {
+        let wraps = i.div_euclid(count as isize);
+        let (position, color) = points[i.rem_euclid(count as isize) as usize];
+        (position + wraps as f64, color)
+    };
+
+    let next = points.partition_point(|&(position, _)| position <= t) as isize;
+    let (x1, p1) = point(next - 1);
+    let (x2, p2) = point(next);
+    let fraction = if x2 > x1 { (t - x1) / (x2 - x1) } else { 0.0 };
+    if !smooth {
+        return [0, 1, 2].map(|channel| p1[channel] + (p2[channel] - p1[channel]) * fraction);
+    }
+
+    let (_, p0) = point(next - 2);
+    let (_, p3) = point(next + 1);
+    let (f, f2, f3) = (
+        fraction,
+        fraction * fraction,
+        fraction * fraction * fraction,
+    );
+    [0, 1, 2].map(|channel| {
+        0.5 * (2.0 * p1[channel]
+            + (p2[channel] - p0[channel]) * f
+            + (2.0 * p0[channel] - 5.0 * p1[channel] + 4.0 * p2[channel] - p3[channel]) * f2
+            + (3.0 * p1[channel] - p0[channel] - 3.0 * p2[channel] + p3[channel]) * f3)
+    })
+}
+
+/// Parses one `.ugr` entry body (the lines between `name {` and `}`).
+fn parse_ugr_entry(lines: &[(usize, &str)]) -> Result<(Option<String>, Palette), String> {
+    let mut title = None;
+    let mut smooth = false;
+    let mut rotation = 0.0;
+    let mut points: Vec<(f64, [f64; 3])> = Vec::new();
+    let mut pending_index: Option<f64> = None;
+    let mut section = "";
+
+    for &(line_number, line) in lines {
+        if let Some(name) = line.strip_suffix(':') {
+            section = name.trim();
+            continue;
+        }
+        for (key, value) in ugr_pairs(line, line_number)? {
+            match (section, key) {
+                ("gradient", "title") => title = Some(value.to_string()),
+                ("gradient", "smooth") => smooth = value == "yes",
+                ("gradient", "rotation") => {
+                    rotation = parse_number(value, "rotation", line_number)?;
+                }
+                ("gradient", "index") => {
+                    pending_index = Some(parse_number(value, "index", line_number)?);
+                }
+                ("gradient", "color") => {
+                    let index = pending_index
+                        .take()
+                        .ok_or_else(|| format!("line {line_number}: color without an index"))?;
+                    let color: u32 = parse_number(value, "color", line_number)?;
+                    if color > 0xFF_FFFF {
+                        return Err(format!("line {line_number}: color {color} is out of range"));
+                    }
+                    // UltraFractal packs colors as 0xBBGGRR.
+                    let channel = |shift: u32| f64::from((color >> shift) & 0xFF) / 255.0;
+                    points.push((index, [channel(0), channel(8), channel(16)]));
+                }
+                ("opacity", "opacity") => {
+                    if value != "255" {
+                        return Err(format!("line {line_number}: transparency is not supported"));
+                    }
+                }
+                ("opacity", "smooth" | "index") => {}
+                (_, key) => return Err(format!("line {line_number}: unsupported setting {key:?}")),
+            }
+        }
+    }
+
+    if points.is_empty() {
+        return Err("gradient has no colors".to_string());
+    }
+    for point in &mut points {
+        point.0 = ((point.0 + rotation) / UGR_GRADIENT_LENGTH).rem_euclid(1.0);
+    }
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let palette = palette_from_fn(|t| sample_cyclic(&points, smooth, t));
+    Ok((title, palette))
+}
+
+/// Parses an UltraFractal `.ugr` library into its entries' titles and
+/// palettes, in file order. UltraFractal gradients are cyclic.
+fn parse_ugr(contents: &str) -> Result<Vec<(String, Palette)>, String> {
+    let mut entries = Vec::new();
+    let mut current: Option<(String, Vec<(usize, &str)>)> = None;
+
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+        match current.as_mut() {
+            None => {
+                let name = line
+                    .strip_suffix('{')
+                    .ok_or_else(|| format!("line {line_number}: expected \"name {{\""))?;
+                current = Some((name.trim().to_string(), Vec::new()));
+            }
+            Some(_) if line == "}" => {
+                let (name, body) = current.take().unwrap();
+                let (title, palette) = parse_ugr_entry(&body)
+                    .map_err(|error| format!("gradient {name:?}: {error}"))?;
+                entries.push((title.unwrap_or(name), palette));
+            }
+            Some((_, body)) => body.push((line_number, line)),
+        }
+    }
+
+    if let Some((name, _)) = current {
+        return Err(format!("gradient {name:?}: missing closing \"}}\""));
+    }
+    if entries.is_empty() {
+        return Err("no gradients found".to_string());
+    }
+    Ok(entries)
+}
+
+fn parse_palette_file(
+    format: PaletteFileFormat,
+    contents: &str,
+) -> Result<Vec<ParsedPalette>, String> {
+    Ok(match format {
+        PaletteFileFormat::Map => vec![ParsedPalette {
+            title: String::new(),
+            palette: parse_map(contents)?,
+            cyclic: false,
+        }],
+        PaletteFileFormat::Ggr => {
+            let (title, palette) = parse_ggr(contents)?;
+            vec![ParsedPalette {
+                title,
+                palette,
+                cyclic: false,
+            }]
+        }
+        PaletteFileFormat::Ugr => parse_ugr(contents)?
+            .into_iter()
+            .map(|(title, palette)| ParsedPalette {
+                title,
+                palette,
+                cyclic: true,
+            })
+            .collect(),
+    })
+}
+
+/// One imported file: its source, kept to tell apart files whose content
+/// hashes collide, and its palettes with their cyclic flags, in file order.
+struct ImportedFile {
+    format: PaletteFileFormat,
+    contents: String,
+    palettes: Vec<(Arc<Palette>, bool)>,
+}
+
+/// Imported files by the name prefix their palettes are registered under.
+/// Unlike compiled gradients, an imported palette cannot be rebuilt from its
+/// name, so entries are never evicted; the map only grows by explicit
+/// imports, and re-importing a file reuses its entry.
+static IMPORTED_FILES: Lazy<Mutex<HashMap<String, ImportedFile>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Parses a palette file and registers each palette in it, returning their
+/// titles and registered names in file order. Names derive from the file
+/// content, so importing the same file again returns the same names — in
+/// this instance or any other, which is what lets every worker import the
+/// file and agree on the names.
+pub fn import_palette_file(
+    format: PaletteFileFormat,
+    contents: &str,
+) -> Result<Vec<ImportedPalette>, String> {
+    let (titles, palettes): (Vec<String>, Vec<(Arc<Palette>, bool)>) =
+        parse_palette_file(format, contents)?
+            .into_iter()
+            .map(|parsed| (parsed.title, (Arc::new(parsed.palette), parsed.cyclic)))
+            .unzip();
+
+    let mut hasher = DefaultHasher::new();
+    format.hash(&mut hasher);
+    contents.hash(&mut hasher);
+    let file_name = format!("{IMPORTED_PALETTE_PREFIX}{:016x}", hasher.finish());
+
+    let mut files = IMPORTED_FILES.lock().unwrap();
+    match files.get(&file_name) {
+        Some(file) if file.format != format || file.contents != contents => {
+            return Err(format!(
+                "{file_name} is already taken by a different imported file"
+            ));
+        }
+        Some(_) => {}
+        None => {
+            let file = ImportedFile {
+                format,
+                contents: contents.to_string(),
+                palettes,
+            };
+            files.insert(file_name.clone(), file);
+        }
+    }
+
+    Ok(titles
+        .into_iter()
+        .enumerate()
+        .map(|(index, title)| ImportedPalette {
+            title,
+            name: format!("{file_name}:{index}"),
+        })
+        .collect())
+}
+
+/// The palette an `import_palette_file` name refers to, and whether it is
+/// cyclic.
+pub(crate) fn imported_palette(name: &str) -> Option<(Arc<Palette>, bool)> {
+    let (file_name, index) = name.rsplit_once(':')?;
+    let index: usize = index.parse().ok()?;
+    let files = IMPORTED_FILES.lock().unwrap();
+    let (palette, cyclic) = files.get(file_name)?.palettes.get(index)?;
+    Some((palette.clone(), *cyclic))
+}
+
+/// Deserializes `ColoringOptions::palette`, rejecting `imported:` names with
+/// no palette in this instance: a worker the file was never imported into
+/// would otherwise silently draw the fallback palette.
+pub(crate) fn deserialize_palette_name<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    if name.starts_with(IMPORTED_PALETTE_PREFIX) && imported_palette(&name).is_none() {
+        return Err(D::Error::custom(format!(
+            "palette {name:?} has not been imported into this worker; \
+             import_palettes must run in every worker that uses it"
+        )));
+    }
+    Ok(name)
+}
+
+/// Imports a palette file (see `import_palette_file`) into this wasm
+/// instance. Call it in every worker that renders or recolors with the
+/// returned names.
+#[wasm_bindgen]
+pub fn import_palettes(
+    format: PaletteFileFormat,
+    contents: &str,
+) -> Result<Vec<ImportedPalette>, JsValue> {
+    import_palette_file(format, contents).map_err(|error| JsValue::from_str(&error))
+}
@@ -0,0 +1,222 @@
+use std::sync::Arc;
+
+use serde::de::value::StrDeserializer;
+
+use super::*;
+
+const GGR_RED_TO_BLUE: &str = "GIMP Gradient
+Name: Red to blue
+2
+0.000000 0.250000 0.500000 1.000000 0.000000 0.000000 1.000000 0.000000 1.000000 0.000000 1.000000 0 0
+0.500000 0.750000 1.000000 0.000000 1.000000 0.000000 1.000000 0.000000 0.000000 1.000000 1.000000 0 0 0 0
+";
+
+const UGR_LIBRARY: &str = "; exported from UltraFractal
+Sunset {
+gradient:
+  title=\"Sunset Glow\" smooth=no
+  index=0 color=255
+  index=200 color=16711680
+opacity:
+  smooth=no index=0 opacity=255
+}
+
+Plain {
+gradient:
+  smooth=yes
+  index=0 color=0
+  index=100 color=16777215
+  index=300 color=65280
+}
+";
+
+fn colors(palette: &Palette, positions: &[f64]) -> Vec<[u8; 3]> {
+    positions
+        .iter()
+        .map(|&t| palette.eval_continuous(t).as_array())
+        .collect()
+}
+
+/// Positions between lookup-table entries blend two rounded samples, so
+/// they can land one step off the exact color.
+fn assert_near(actual: [u8; 3], expected: [u8; 3]) {
+    assert!(
+        actual
+            .iter()
+            .zip(expected)
+            .all(|(&a, e)| a.abs_diff(e) <= 1),
+        "{actual:?} is not near {expected:?}"
+    );
+}
+
+#[test]
+fn map_entries_become_the_lookup_table() {
+    let palette = parse_map("0 0 0 black\n\n128 64 32\n255 255 255 white   \n").unwrap();
+    assert_eq!(
+        colors(&palette, &[0.0, 0.5, 1.0]),
+        [[0, 0, 0], [128, 64, 32], [255, 255, 255]]
+    );
+}
+
+#[test]
+fn map_errors_name_the_line() {
+    for contents in ["0 0 0\n0 0\n", "0 0 0\n0 0 256\n"] {
+        let error = parse_map(contents).err().unwrap();
+        assert!(error.contains("line 2"), "{error:?}");
+    }
+    assert!(parse_map("0 0 0\n").is_err());
+}
+
+#[test]
+fn ggr_segments_blend_through_their_midpoints() {
+    let (title, palette) = parse_ggr(GGR_RED_TO_BLUE).unwrap();
+    assert_eq!(title, "Red to blue");
+
+    let [start, first_mid, joint, second_mid, end] =
+        colors(&palette, &[0.0, 0.25, 0.5, 0.75, 1.0])[..]
+    else {
+        unreachable!()
+    };
+    assert_eq!(start, [255, 0, 0]);
+    assert_near(first_mid, [128, 128, 0]);
+    assert_near(joint, [0, 255, 0]);
+    assert_near(second_mid, [0, 128, 128]);
+    assert_eq!(end, [0, 0, 255]);
+}
+
+#[test]
+fn ggr_hsv_segments_sweep_hue_in_the_requested_direction() {
+    let segment = |coloring| format!("GIMP Gradient\n1\n0 0.5 1 1 0 0 1 0 0 1 1 0 {coloring}\n");
+    // Red (hue 0) to blue (hue 2/3): counterclockwise passes green,
+    // clockwise passes magenta.
+    let (_, ccw) = parse_ggr(&segment(1)).unwrap();
+    let (_, cw) = parse_ggr(&segment(2)).unwrap();
+    let ccw_mid = ccw.eval_continuous(0.5);
+    let cw_mid = cw.eval_continuous(0.5);
+    assert!(ccw_mid.g > 200 && ccw_mid.r < 50, "{ccw_mid:?}");
+    assert!(
+        cw_mid.r > 200 && cw_mid.b > 200 && cw_mid.g < 50,
+        "{cw_mid:?}"
+    );
+}
+
+#[test]
+fn ggr_rejects_unsupported_features() {
+    let with_segment = |segment: &str| format!("GIMP Gradient\n1\n{segment}\n");
+    let cases = [
+        (
+            with_segment("0 0.5 1 0 0 0 0.5 1 1 1 1 0 0"),
+            "transparency",
+        ),
+        (
+            with_segment("0 0.5 1 0 0 0 1 1 1 1 1 0 0 1 0"),
+            "foreground/background",
+        ),
+        (with_segment("0 0.5 1 0 0 0 1 1 1 1 1 9 0"), "blending type"),
+        (with_segment("0 0.5 1 0 0 0 1 1 1 1 1 0 7"), "coloring type"),
+        (with_segment("0 0.7 0.5 0 0 0 1 1 1 1 1 0 0"), "ordered"),
+        ("GIMP Palette\n".to_string(), "header"),
+        (
+            "GIMP Gradient\n2\n0 0.5 1 0 0 0 1 1 1 1 1 0 0\n".to_string(),
+            "expected 2 segments",
+        ),
+    ];
+    for (contents, expected) in cases {
+        let error = parse_ggr(&contents).err().unwrap();
+        assert!(
+            error.contains(expected),
+            "{error:?} should mention {expected:?}"
+        );
+    }
+}
+
+#[test]
+fn ugr_libraries_yield_every_entry() {
+    let entries = parse_ugr(UGR_LIBRARY).unwrap();
+    let titles: Vec<&str> = entries.iter().map(|(title, _)| title.as_str()).collect();
+    assert_eq!(titles, ["Sunset Glow", "Plain"]);
+
+    // Colors are 0xBBGGRR; the gradient wraps from index 200 back to 0.
+    let sunset = &entries[0].1;
+    assert_eq!(colors(sunset, &[0.0, 1.0]), [[255, 0, 0], [255, 0, 0]]);
+    assert_near(sunset.eval_continuous(0.5).as_array(), [0, 0, 255]);
+
+    // Smooth gradients still pass through their control points.
+    let plain = &entries[1].1;
+    assert_eq!(colors(plain, &[0.0, 1.0]), [[0, 0, 0], [0, 0, 0]]);
+    let at_index_100 = plain.eval_continuous(0.25).as_array();
+    assert!(
+        at_index_100.iter().all(|&channel| channel >= 250),
+        "{at_index_100:?}"
+    );
+}
+
+#[test]
+fn ugr_rejects_unsupported_features() {
+    let entry = |body: &str| format!("Entry {{\ngradient:\n  index=0 color=0\n{body}\n}}\n");
+    let cases = [
+        (entry("opacity:\n  index=0 opacity=128"), "transparency"),
+        (entry("  numnodes=3"), "unsupported setting \"numnodes\""),
+        (entry("  color=5"), "color without an index"),
+        (
+            "Entry {\ngradient:\n  index=0 color=0\n".to_string(),
+            "closing",
+        ),
+        ("; nothing here\n".to_string(), "no gradients"),
+    ];
+    for (contents, expected) in cases {
+        let error = parse_ugr(&contents).err().unwrap();
+        assert!(
+            error.contains(expected),
+            "{error:?} should mention {expected:?}"
+        );
+        if contents.contains("Entry") {
+            assert!(
+                error.contains("\"Entry\""),
+                "{error:?} should name the entry"
+            );
+        }
+    }
+}
+
+#[test]
+fn imported_palettes_resolve_by_name() {
+    let imported = import_palette_file(PaletteFileFormat::Ugr, UGR_LIBRARY).unwrap();
+    assert_eq!(imported.len(), 2);
+    assert_eq!(imported[0].title, "Sunset Glow");
+    assert!(imported[0].name.starts_with(IMPORTED_PALETTE_PREFIX));
+    assert_ne!(imported[0].name, imported[1].name);
+
+    let (palette, cyclic) = imported_palette(&imported[0].name).unwrap();
+    assert!(cyclic);
+    assert_eq!(palette.eval_continuous(0.0).as_array(), [255, 0, 0]);
+
+    // Re-importing the same file reuses the registered palettes.
+    let again = import_palette_file(PaletteFileFormat::Ugr, UGR_LIBRARY).unwrap();
+    assert_eq!(again[0].name, imported[0].name);
+    let (palette_again, _) = imported_palette(&again[0].name).unwrap();
+    assert!(Arc::ptr_eq(&palette, &palette_again));
+    let file_name = imported[0].name.trim_end_matches(":0");
+    assert!(imported_palette(&format!("{file_name}:9")).is_none());
+
+    let (_, cyclic) = imported_palette(
+        &import_palette_file(PaletteFileFormat::Ggr, GGR_RED_TO_BLUE).unwrap()[0].name,
+    )
+    .unwrap();
+    assert!(!cyclic);
+    assert!(import_palette_file(PaletteFileFormat::Map, "").is_err());
+}
+
+#[test]
+fn unimported_palette_names_are_rejected() {
+    let deserialize = |name: &str| {
+        deserialize_palette_name(StrDeserializer::<serde::de::value::Error>::new(name))
+    };
+    let imported = import_palette_file(PaletteFileFormat::Ggr, GGR_RED_TO_BLUE).unwrap();
+    assert_eq!(deserialize(&imported[0].name).unwrap(), imported[0].name);
+    assert_eq!(deserialize("turbo").unwrap(), "turbo");
+
+    // Another worker's import, or a typo: an error, not the fallback palette.
+    let error = deserialize("imported:0123456789abcdef:0").unwrap_err();
+    assert!(error.to_string().contains("import_palettes"), "{error}");
+}
@@ -14,14 +14,19 @@
 //! tile offsets, because callers (JavaScript) cannot represent deep-zoom
 //! coordinates in ordinary floats.
 
+#[cfg(not(feature = "parallel"))]
 use std::cell::RefCell;
-use std::rc::Rc;
+use std::sync::Arc;
+#[cfg(feature = "parallel")]
+use std::sync::{Condvar, Mutex, PoisonError};
 
 use dashu::float::round::mode::Zero;
 use dashu::float::{DBig, FBig};
 use num::complex::Complex64;
+use wasm_bindgen::prelude::*;
 
 use crate::float_exp::{exp_value_less_than, ldexp, ComplexExp};
+use crate::polynomial::{self, Polynomial};
 #[cfg(target_arch = "wasm32")]
 use crate::{f64x2_lane, f64x2_with_lane, i64x2_lane, i64x2_with_lane, IDLE_SLOT};
 use crate::{PERIODICITY_CHECK_STRIDE, PERIODICITY_FIRST_SAVE};
@@ -54,6 +59,13 @@ pub fn uses_float_exp(effective_zoom: i64, exponent: u32) -> bool {
     exponent == 2 && effective_zoom >= FLOAT_EXP_THRESHOLD
 }
 
+/// `uses_float_exp` for a formula render (see `polynomial`): formulas of
+/// every degree take float-exp deltas past the same depth, as their scalar
+/// kernels have no hybrid phase to outgrow.
+pub fn formula_uses_float_exp(effective_zoom: i64) -> bool {
+    effective_zoom >= FLOAT_EXP_THRESHOLD
+}
+
 /// Maximum stored reference orbit length. Pixels needing more iterations wrap
 /// around via rebasing, which stays correct at any iteration count.
 const MAX_ORBIT_LENGTH: usize = 1_000_000;
@@ -73,6 +85,12 @@ pub fn tile_coordinate_offset(tile_coordinate: f64, tile_zoom: i32) -> f64 {
     tile_coordinate * TILE_SPACE_SCALE * ldexp(1.0, -(tile_zoom as i64 - 2)) - 4.0
 }
 
+/// The fractional tile coordinate at `tile_zoom` whose offset from the world
+/// origin is zero (the inverse of `tile_coordinate_offset` at 0).
+pub fn origin_tile_coordinate(tile_zoom: i32) -> f64 {
+    4.0 / (TILE_SPACE_SCALE * ldexp(1.0, -(tile_zoom as i64 - 2)))
+}
+
 /// Complex-plane distance between adjacent pixels of a render request that
 /// maps `tile_max - tile_min` tile units onto `image_len` pixels. Underflows
 /// to zero at extreme depths, which callers should treat as "finer than f64".
@@ -89,6 +107,87 @@ pub fn pixel_spacing(
     )
 }
 
+/// A linear map applied to a view's offsets from the world origin: a
+/// rotation, optionally composed with a skew/stretch matrix. It acts on the
+/// tile-scale offsets, which are O(1), before the `2^-zoom_offset` deep-zoom
+/// scaling, so a rotated or unskewed view loses no precision at any depth and
+/// the reference orbit (at the origin) is unaffected.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewTransform {
+    /// Row-major `[a, b, c, d]`: `(re, im) -> (a re + b im, c re + d im)`.
+    matrix: [f64; 4],
+}
+
+impl ViewTransform {
+    pub const IDENTITY: ViewTransform = ViewTransform {
+        matrix: [1.0, 0.0, 0.0, 1.0],
+    };
+
+    /// Rotation by `rotation_degrees` (counterclockwise in the complex plane)
+    /// applied after the optional row-major `matrix`, which is how the
+    /// "unskew" of a stretched deep location is expressed. The values come
+    /// from an untrusted payload: a non-finite angle drops to 0, and a
+    /// non-finite or singular matrix is ignored rather than collapsing the
+    /// view onto a line.
+    pub fn new(rotation_degrees: f64, matrix: Option<[f64; 4]>) -> ViewTransform {
+        let [a, b, c, d] = matrix
+            .filter(|matrix| {
+                matrix.iter().all(|value| value.is_finite())
+                    && matrix[0] * matrix[3] - matrix[1] * matrix[2] != 0.0
+            })
+            .unwrap_or(ViewTransform::IDENTITY.matrix);
+        // Quarter turns are exact, so they permute and negate offsets
+        // without rounding (and a full turn is the identity).
+        let degrees = if rotation_degrees.is_finite() {
+            rotation_degrees.rem_euclid(360.0)
+        } else {
+            0.0
+        };
+        let (sin, cos) = if degrees == 0.0 {
+            (0.0, 1.0)
+        } else if degrees == 90.0 {
+            (1.0, 0.0)
+        } else if degrees == 180.0 {
+            (0.0, -1.0)
+        } else if degrees == 270.0 {
+            (-1.0, 0.0)
+        } else {
+            degrees.to_radians().sin_cos()
+        };
+        ViewTransform {
+            matrix: [
+                cos * a - sin * c,
+                cos * b - sin * d,
+                sin * a + cos * c,
+                sin * b + cos * d,
+            ],
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == ViewTransform::IDENTITY
+    }
+
+    /// Maps an untransformed (re, im) offset to the view's offset.
+    pub fn apply(&self, re_offset: f64, im_offset: f64) -> (f64, f64) {
+        let [a, b, c, d] = self.matrix;
+        (a * re_offset + b * im_offset, c * re_offset + d * im_offset)
+    }
+
+    /// The smallest factor by which the map shrinks any direction (its
+    /// smaller singular value): scales an untransformed pixel spacing to the
+    /// finest spacing the transformed grid actually has.
+    pub fn min_scale(&self) -> f64 {
+        let [a, b, c, d] = self.matrix;
+        let sum_of_squares = a * a + b * b + c * c + d * d;
+        let determinant = a * d - b * c;
+        let discriminant = (sum_of_squares * sum_of_squares - 4.0 * determinant * determinant)
+            .max(0.0)
+            .sqrt();
+        ((sum_of_squares - discriminant) / 2.0).max(0.0).sqrt()
+    }
+}
+
 /// Parses an arbitrary-precision decimal string into a binary big float.
 pub fn parse_decimal(text: &str, precision_bits: usize) -> Result<BigFloat, String> {
     let decimal: DBig = text
@@ -127,6 +226,7 @@ fn complex_big_pow(base: &(BigFloat, BigFloat), exponent: u32) -> (BigFloat, Big
 /// A reference orbit: `values[n]` is `Z_n` (with `Z_0 = 0`) rounded to f64.
 /// `escaped` records whether the orbit left the escape radius, in which case
 /// it is complete and no longer entries can ever be needed.
+#[derive(Clone)]
 struct ReferenceOrbit {
     values: Vec<(f64, f64)>,
     escaped: bool,
@@ -138,9 +238,10 @@ struct ReferenceOrbit {
     /// per-step Horner loop performs, so consuming the table is bit-identical
     /// to recomputing the terms. Empty for exponent 2 (whose delta step has a
     /// closed form) and for orbits past `COEFF_TABLE_MAX_ENTRIES` (consumers
-    /// fall back to the on-the-fly loop). Only the wasm32 SIMD kernels read
-    /// it; the scalar loops keep recomputing terms (identical results).
-    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    /// fall back to the on-the-fly loop). For `z^power + c` only the wasm32
+    /// SIMD kernels read it; the scalar loops keep recomputing terms
+    /// (identical results). A formula orbit holds `extend_formula_table`'s
+    /// rows instead, which every formula kernel reads.
     coeff_table: Vec<[f64; 2]>,
 }
 
@@ -150,18 +251,22 @@ struct ReferenceOrbit {
 /// high-exponent combinations from ballooning memory.
 const COEFF_TABLE_MAX_ENTRIES: usize = 512 * 1024;
 
-/// Builds the per-orbit-index Horner term table for `delta_step_f64`'s
-/// general-exponent loop. Term `j` of row `n` is `coefficient * Z_n^(e-k)`
-/// for `k = e-1-j`, computed with the same expressions (and therefore the
-/// same IEEE roundings) as the in-loop code it replaces.
-fn compute_coeff_table(values: &[(f64, f64)], exponent: u32) -> Vec<[f64; 2]> {
+/// Appends to `table` the per-orbit-index Horner term rows for
+/// `delta_step_f64`'s general-exponent loop of the `values` it does not
+/// cover yet, or empties it when the table does not apply. Term `j` of row
+/// `n` is `coefficient * Z_n^(e-k)` for `k = e-1-j`, computed with the same
+/// expressions (and therefore the same IEEE roundings) as the in-loop code
+/// it replaces.
+fn extend_coeff_table(table: &mut Vec<[f64; 2]>, values: &[(f64, f64)], exponent: u32) {
     let terms = (exponent - 1) as usize;
     if exponent == 2 || values.len().saturating_mul(terms) > COEFF_TABLE_MAX_ENTRIES {
-        return Vec::new();
+        *table = Vec::new();
+        return;
     }
 
-    let mut table = Vec::with_capacity(values.len() * terms);
-    for &(z_ref_re, z_ref_im) in values {
+    let rows = table.len() / terms;
+    table.reserve((values.len() - rows) * terms);
+    for &(z_ref_re, z_ref_im) in &values[rows..] {
         let mut z_power = Complex64::new(1.0, 0.0);
         let mut coefficient = 1.0_f64;
         for k in (1..exponent).rev() {
@@ -170,21 +275,107 @@ fn compute_coeff_table(values: &[(f64, f64)], exponent: u32) -> Vec<[f64; 2]> {
             table.push([z_power.re * coefficient, z_power.im * coefficient]);
         }
     }
-    table
+}
+
+/// The formula counterpart of `extend_coeff_table`: row `n` holds the
+/// delta-step coefficients `b_1..b_d` at `Z_n` (see `polynomial`), `d` (the
+/// formula's degree) entries per index. Empty past
+/// `COEFF_TABLE_MAX_ENTRIES`, where `FormulaStep` computes rows on the spot
+/// (identical results).
+fn extend_formula_table(table: &mut Vec<[f64; 2]>, values: &[(f64, f64)], formula: &Polynomial) {
+    let degree = formula.degree() as usize;
+    if values.len().saturating_mul(degree) > COEFF_TABLE_MAX_ENTRIES {
+        *table = Vec::new();
+        return;
+    }
+
+    let rows = table.len() / degree;
+    table.resize(values.len() * degree, [0.0; 2]);
+    for (row, &(z_ref_re, z_ref_im)) in table[rows * degree..]
+        .chunks_exact_mut(degree)
+        .zip(&values[rows..])
+    {
+        formula.delta_coefficients_into(Complex64::new(z_ref_re, z_ref_im), row);
+    }
+}
+
+/// Where a reference orbit that has not escaped left off: `z` is the next
+/// value, `Z_n` for `n = values.len()`, and `center` is `c`, both at the
+/// orbit's working precision, so iterating on reproduces exactly the values
+/// a longer computation would have produced.
+#[derive(Clone)]
+struct OrbitTail {
+    z: (BigFloat, BigFloat),
+    center: (BigFloat, BigFloat),
 }
 
 fn compute_reference_orbit(
     center_re: &BigFloat,
     center_im: &BigFloat,
     exponent: u32,
+    formula: Option<&Polynomial>,
     length: usize,
     escape_radius_squared: f64,
-) -> ReferenceOrbit {
-    let mut values = Vec::with_capacity(length.min(MAX_ORBIT_LENGTH) + 1);
-    values.push((0.0, 0.0));
+) -> (ReferenceOrbit, Option<OrbitTail>) {
+    let mut orbit = ReferenceOrbit {
+        values: vec![(0.0, 0.0)],
+        escaped: false,
+        coeff_table: Vec::new(),
+    };
+    let center = (center_re.clone(), center_im.clone());
+    let tail = OrbitTail {
+        z: center.clone(),
+        center,
+    };
+    let tail = extend_reference_orbit(
+        &mut orbit,
+        tail,
+        exponent,
+        formula,
+        length,
+        escape_radius_squared,
+    );
+    (orbit, tail)
+}
 
-    let mut z = (center_re.clone(), center_im.clone());
+/// Continues `orbit` (which ends at `tail`) in place until it holds `Z_0`
+/// through `Z_length` or escapes: `z^exponent + c`, or `formula` plus `c`
+/// when given. Only the new iterations are computed, and only their rows
+/// are appended to the term table. Returns, unless the orbit escaped, the
+/// tail to continue it from.
+fn extend_reference_orbit(
+    orbit: &mut ReferenceOrbit,
+    tail: OrbitTail,
+    exponent: u32,
+    formula: Option<&Polynomial>,
+    length: usize,
+    escape_radius_squared: f64,
+) -> Option<OrbitTail> {
+    let mut values = std::mem::take(&mut orbit.values);
+    let mut coeff_table = std::mem::take(&mut orbit.coeff_table);
+    values.reserve((length.min(MAX_ORBIT_LENGTH) + 1).saturating_sub(values.len()));
+    let OrbitTail {
+        mut z,
+        center: (center_re, center_im),
+    } = tail;
     let mut escaped = false;
+    // The coefficients at the working precision (f64s convert exactly).
+    let formula_terms: Vec<(u32, (BigFloat, BigFloat))> = formula
+        .map(|formula| {
+            let precision_bits = center_re.precision();
+            let big = |value: f64| {
+                BigFloat::try_from(value)
+                    .expect("formula coefficients are finite")
+                    .with_precision(precision_bits)
+                    .value()
+            };
+            formula
+                .terms()
+                .iter()
+                .map(|&(power, coefficient)| (power, (big(coefficient.re), big(coefficient.im))))
+                .collect()
+        })
+        .unwrap_or_default();
 
     while values.len() <= length {
         let z_f64 = (z.0.to_f64().value(), z.1.to_f64().value());
@@ -195,23 +386,38 @@ fn compute_reference_orbit(
             break;
         }
 
-        z = if exponent == 2 {
+        z = if formula.is_some() {
+            formula_terms.iter().fold(
+                (center_re.clone(), center_im.clone()),
+                |sum, (power, coefficient)| {
+                    let term = complex_big_mul(coefficient, &complex_big_pow(&z, *power));
+                    (sum.0 + term.0, sum.1 + term.1)
+                },
+            )
+        } else if exponent == 2 {
             (
-                &z.0 * &z.0 - &z.1 * &z.1 + center_re,
-                (&z.0 * &z.1) * BigFloat::from(2) + center_im,
+                &z.0 * &z.0 - &z.1 * &z.1 + &center_re,
+                (&z.0 * &z.1) * BigFloat::from(2) + &center_im,
             )
         } else {
             let powered = complex_big_pow(&z, exponent);
-            (powered.0 + center_re, powered.1 + center_im)
+            (powered.0 + &center_re, powered.1 + &center_im)
         };
     }
 
-    let coeff_table = compute_coeff_table(&values, exponent);
-    ReferenceOrbit {
+    match formula {
+        Some(formula) => extend_formula_table(&mut coeff_table, &values, formula),
+        None => extend_coeff_table(&mut coeff_table, &values, exponent),
+    }
+    *orbit = ReferenceOrbit {
         values,
         escaped,
         coeff_table,
-    }
+    };
+    (!escaped).then_some(OrbitTail {
+        z,
+        center: (center_re, center_im),
+    })
 }
 
 #[derive(PartialEq, Clone)]
@@ -219,6 +425,8 @@ struct OrbitCacheKey {
     origin_re: String,
     origin_im: String,
     exponent: u32,
+    /// The formula iterated in place of `z^exponent + c`, if any.
+    formula: Option<Polynomial>,
     escape_radius_squared: f64,
 }
 
@@ -230,18 +438,289 @@ struct OrbitCacheEntry {
     /// roundings stable under extra bits, so an orbit computed at higher
     /// precision than a render needs is reused rather than recomputed.
     precision_bits: usize,
-    orbit: Rc<ReferenceOrbit>,
+    orbit: Arc<ReferenceOrbit>,
+    /// Where the orbit continues from, unless it escaped; also `None` for
+    /// orbits installed from a buffer, which carries no big-float state.
+    tail: Option<OrbitTail>,
+}
+
+impl OrbitCacheEntry {
+    /// Whether this orbit serves a render needing `length` entries at
+    /// `precision_bits`.
+    fn serves(&self, key: &OrbitCacheKey, precision_bits: usize, length: usize) -> bool {
+        let orbit_is_sufficient = self.orbit.escaped || self.orbit.values.len() > length;
+        self.key == *key && self.precision_bits >= precision_bits && orbit_is_sufficient
+    }
+
+    /// Bytes the entry holds: the orbit values and term table, the
+    /// big-float tail an extension continues from, and the key with its
+    /// formula.
+    fn size_bytes(&self) -> usize {
+        let tail_bytes = self.tail.as_ref().map_or(0, |tail| {
+            [&tail.z.0, &tail.z.1, &tail.center.0, &tail.center.1]
+                .into_iter()
+                .map(big_float_size_bytes)
+                .sum()
+        });
+        let formula_bytes = self
+            .key
+            .formula
+            .as_ref()
+            .map_or(0, |formula| std::mem::size_of_val(formula.terms()));
+        std::mem::size_of::<(f64, f64)>() * self.orbit.values.len()
+            + std::mem::size_of::<[f64; 2]>() * self.orbit.coeff_table.len()
+            + tail_bytes
+            + self.key.origin_re.len()
+            + self.key.origin_im.len()
+            + formula_bytes
+    }
+}
+
+/// Bytes a big float holds: its inline representation plus the words of
+/// its significand.
+fn big_float_size_bytes(value: &BigFloat) -> usize {
+    let significand_bits = value.repr().digits();
+    std::mem::size_of::<BigFloat>()
+        + significand_bits.div_ceil(usize::BITS as usize) * std::mem::size_of::<usize>()
 }
 
+/// Default `OrbitCache` budget: room for a few views' deep orbits (a full
+/// `MAX_ORBIT_LENGTH` orbit is 16 MiB of values) without holding on to
+/// every view a session visited.
+const DEFAULT_ORBIT_CACHE_BUDGET_BYTES: usize = 64 * 1024 * 1024;
+
+/// Reference orbits by view, least recently used first, within a byte
+/// budget. Tiles of the same view share the same origin, so caching makes
+/// the expensive high-precision computation a once-per-view cost instead of
+/// once-per-tile; zoom-animation frames likewise share one origin across
+/// every depth, so the precision-`>=` reuse above makes it a once-per-run
+/// cost instead of once-per-frame. Holding several entries keeps switching
+/// between views, exponents or an animation and the main map from
+/// recomputing each deep orbit on every switch.
+struct OrbitCache {
+    entries: Vec<OrbitCacheEntry>,
+    /// Views whose orbit some render is computing outside the cache (see
+    /// `get_reference_orbit`).
+    in_flight: Vec<OrbitCacheKey>,
+    budget_bytes: usize,
+    hits: u32,
+    misses: u32,
+    extensions: u32,
+}
+
+impl OrbitCache {
+    const fn new() -> OrbitCache {
+        OrbitCache {
+            entries: Vec::new(),
+            in_flight: Vec::new(),
+            budget_bytes: DEFAULT_ORBIT_CACHE_BUDGET_BYTES,
+            hits: 0,
+            misses: 0,
+            extensions: 0,
+        }
+    }
+
+    /// The cached orbit serving a render, marked most recently used.
+    fn get(
+        &mut self,
+        key: &OrbitCacheKey,
+        precision_bits: usize,
+        length: usize,
+    ) -> Option<Arc<ReferenceOrbit>> {
+        let position = self
+            .entries
+            .iter()
+            .rposition(|entry| entry.serves(key, precision_bits, length))?;
+        let entry = self.entries.remove(position);
+        let orbit = Arc::clone(&entry.orbit);
+        self.entries.push(entry);
+        Some(orbit)
+    }
+
+    /// Removes and returns the orbit, tail and precision of the entry of
+    /// `key` that can be extended to serve a render at `precision_bits`: one
+    /// too short but not escaped, at enough precision. The extension takes
+    /// the entry out so it can append to the orbit in place; renders of the
+    /// view wait for it while it computes (the key is in flight).
+    fn take_extendable(
+        &mut self,
+        key: &OrbitCacheKey,
+        precision_bits: usize,
+    ) -> Option<(Arc<ReferenceOrbit>, OrbitTail, usize)> {
+        let position = self.entries.iter().position(|entry| {
+            entry.tail.is_some() && entry.key == *key && entry.precision_bits >= precision_bits
+        })?;
+        let entry = self.entries.remove(position);
+        Some((entry.orbit, entry.tail?, entry.precision_bits))
+    }
+
+    /// Adds `entry` as the most recently used, replacing any entry of the
+    /// same view, then evicts the least recently used entries down to the
+    /// budget. The newest entry always stays, however large: dropping it
+    /// would recompute it for every tile of its view.
+    fn insert(&mut self, entry: OrbitCacheEntry) {
+        self.entries.retain(|cached| cached.key != entry.key);
+        self.entries.push(entry);
+        self.evict();
+    }
+
+    /// Adds an imported `entry` unless the cached entry of its view is at
+    /// least as useful: one that serves the imported length at the imported
+    /// precision, holds a tail to extend from, or is no shorter.
+    fn install(&mut self, entry: OrbitCacheEntry) {
+        let imported_length = entry.orbit.values.len();
+        let keep_cached = self.entries.iter().any(|cached| {
+            cached.key == entry.key
+                && (cached.tail.is_some()
+                    || cached.serves(&entry.key, entry.precision_bits, imported_length - 1)
+                    || cached.orbit.values.len() >= imported_length)
+        });
+        if !keep_cached {
+            self.insert(entry);
+        }
+    }
+
+    fn evict(&mut self) {
+        let mut size_bytes = self.size_bytes();
+        while size_bytes > self.budget_bytes && self.entries.len() > 1 {
+            size_bytes -= self.entries.remove(0).size_bytes();
+        }
+    }
+
+    fn size_bytes(&self) -> usize {
+        self.entries.iter().map(OrbitCacheEntry::size_bytes).sum()
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
 thread_local! {
-    // One cached orbit per worker thread — a single entry bounds the held
-    // memory to one orbit's f64 values plus its coeff table. Tiles of the
-    // same view share the same origin, so this makes the expensive
-    // high-precision computation a once-per-view cost instead of
-    // once-per-tile; zoom-animation frames likewise share one origin across
-    // every depth, so the precision-`>=` reuse above makes it a once-per-run
-    // cost instead of once-per-frame.
-    static ORBIT_CACHE: RefCell<Option<OrbitCacheEntry>> = const { RefCell::new(None) };
+    // One cache per worker thread.
+    static ORBIT_CACHE: RefCell<OrbitCache> = const { RefCell::new(OrbitCache::new()) };
+}
+
+// With the `parallel` feature the render threads share one cache (and, on
+// wasm32, one linear memory): the first thread to need a view's orbit marks
+// it in flight and computes it outside the lock, other threads needing the
+// same view wait on `ORBIT_COMPUTED` while renders of other views go on,
+// then all of them read the same `Arc`, so the orbit is a once-per-view
+// cost across every thread rather than once per thread.
+#[cfg(feature = "parallel")]
+static ORBIT_CACHE: Mutex<OrbitCache> = Mutex::new(OrbitCache::new());
+
+#[cfg(feature = "parallel")]
+static ORBIT_COMPUTED: Condvar = Condvar::new();
+
+/// Runs `f` on the orbit cache: the calling thread's own under the default
+/// build, the shared one under the `parallel` feature.
+fn with_orbit_cache<R>(f: impl FnOnce(&mut OrbitCache) -> R) -> R {
+    #[cfg(feature = "parallel")]
+    {
+        // The lock is never held across a computation, but a panic in `f`
+        // leaves at worst a stale entry, which the key and length checks
+        // still guard.
+        let mut cache = ORBIT_CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+        f(&mut cache)
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    ORBIT_CACHE.with(|cache| f(&mut cache.borrow_mut()))
+}
+
+/// Blocks until no render is computing the orbit of `key`. A thread's own
+/// cache never has another render's orbit in flight.
+fn wait_for_orbit(key: &OrbitCacheKey) {
+    #[cfg(feature = "parallel")]
+    {
+        let cache = ORBIT_CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+        let _cache = ORBIT_COMPUTED
+            .wait_while(cache, |cache| cache.in_flight.contains(key))
+            .unwrap_or_else(PoisonError::into_inner);
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    let _ = key;
+}
+
+/// A view's orbit marked in flight in the cache, unmarked (and its waiters
+/// woken) when dropped: after the orbit is inserted, or if computing it
+/// panicked.
+struct InFlightOrbit<'a> {
+    key: &'a OrbitCacheKey,
+}
+
+impl Drop for InFlightOrbit<'_> {
+    fn drop(&mut self) {
+        with_orbit_cache(|cache| cache.in_flight.retain(|key| key != self.key));
+        #[cfg(feature = "parallel")]
+        ORBIT_COMPUTED.notify_all();
+    }
+}
+
+/// Orbit cache usage (see `orbit_cache_stats`).
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitCacheStats {
+    /// Renders that reused a cached orbit.
+    pub hits: u32,
+    /// Renders that computed their orbit from scratch.
+    pub misses: u32,
+    /// Renders that extended a cached orbit too short for their iteration
+    /// budget, computing only the new iterations.
+    pub extensions: u32,
+    /// Orbits currently cached.
+    pub entries: u32,
+    /// Bytes the cached orbits hold, saturating at `u32::MAX`.
+    pub size_bytes: u32,
+    /// The cache's byte budget (see `set_orbit_cache_budget`), saturating
+    /// at `u32::MAX`.
+    pub budget_bytes: u32,
+}
+
+/// Usage of the orbit cache: this worker's, or under the `parallel` feature
+/// the one every render thread shares. Counts run from the last
+/// `clear_orbit_cache`.
+#[wasm_bindgen]
+pub fn orbit_cache_stats() -> OrbitCacheStats {
+    let saturating = |count: usize| u32::try_from(count).unwrap_or(u32::MAX);
+    with_orbit_cache(|cache| OrbitCacheStats {
+        hits: cache.hits,
+        misses: cache.misses,
+        extensions: cache.extensions,
+        entries: saturating(cache.entries.len()),
+        size_bytes: saturating(cache.size_bytes()),
+        budget_bytes: saturating(cache.budget_bytes),
+    })
+}
+
+/// Sets the orbit cache's byte budget (64 MiB by default), evicting least
+/// recently used orbits down to it. The most recently used orbit is kept
+/// even past the budget.
+#[wasm_bindgen]
+pub fn set_orbit_cache_budget(budget_bytes: usize) {
+    with_orbit_cache(|cache| {
+        cache.budget_bytes = budget_bytes;
+        cache.evict();
+    });
+}
+
+/// Drops every cached orbit and resets the counts; the budget stays.
+#[wasm_bindgen]
+pub fn clear_orbit_cache() {
+    with_orbit_cache(|cache| {
+        cache.entries.clear();
+        cache.hits = 0;
+        cache.misses = 0;
+        cache.extensions = 0;
+    });
+}
+
+/// What `get_reference_orbit` found in the cache.
+enum OrbitLookup {
+    Cached(Arc<ReferenceOrbit>),
+    /// Another render is computing the orbit.
+    InFlight,
+    /// The caller is to compute the orbit, extending the given one if any.
+    Claimed(Option<(Arc<ReferenceOrbit>, OrbitTail, usize)>),
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -251,41 +730,85 @@ fn get_reference_orbit(
     center_re: &BigFloat,
     center_im: &BigFloat,
     exponent: u32,
+    formula: Option<&Polynomial>,
     precision_bits: usize,
     length: usize,
     escape_radius_squared: f64,
-) -> Rc<ReferenceOrbit> {
+) -> Arc<ReferenceOrbit> {
     let key = OrbitCacheKey {
         origin_re: origin_re_text.to_string(),
         origin_im: origin_im_text.to_string(),
         exponent,
+        formula: formula.cloned(),
         escape_radius_squared,
     };
 
-    ORBIT_CACHE.with(|cache| {
-        let mut cache = cache.borrow_mut();
-
-        if let Some(entry) = cache.as_ref() {
-            let orbit_is_sufficient = entry.orbit.escaped || entry.orbit.values.len() > length;
-            if entry.key == key && entry.precision_bits >= precision_bits && orbit_is_sufficient {
-                return Rc::clone(&entry.orbit);
+    // Look the view up, and unless its orbit is cached or another render is
+    // computing it, claim the computation.
+    let extendable = loop {
+        let claimed = with_orbit_cache(|cache| {
+            if let Some(orbit) = cache.get(&key, precision_bits, length) {
+                cache.hits = cache.hits.saturating_add(1);
+                return OrbitLookup::Cached(orbit);
+            }
+            if cache.in_flight.contains(&key) {
+                return OrbitLookup::InFlight;
             }
+            cache.in_flight.push(key.clone());
+            let extendable = cache.take_extendable(&key, precision_bits);
+            if extendable.is_some() {
+                cache.extensions = cache.extensions.saturating_add(1);
+            } else {
+                cache.misses = cache.misses.saturating_add(1);
+            }
+            OrbitLookup::Claimed(extendable)
+        });
+        match claimed {
+            OrbitLookup::Cached(orbit) => return orbit,
+            OrbitLookup::Claimed(extendable) => break extendable,
+            OrbitLookup::InFlight => wait_for_orbit(&key),
         }
-
-        let orbit = Rc::new(compute_reference_orbit(
-            center_re,
-            center_im,
-            exponent,
-            length,
-            escape_radius_squared,
-        ));
-        *cache = Some(OrbitCacheEntry {
-            key,
+    };
+    let in_flight = InFlightOrbit { key: &key };
+
+    // Raising the iteration budget continues a cached orbit where it stopped
+    // instead of recomputing it from `Z_0`, appending to its buffers unless a
+    // render still holds them. The extended orbit keeps running at its
+    // entry's precision.
+    let (orbit, tail, precision_bits) = match extendable {
+        Some((mut orbit, tail, entry_precision_bits)) => {
+            let tail = extend_reference_orbit(
+                Arc::make_mut(&mut orbit),
+                tail,
+                exponent,
+                formula,
+                length,
+                escape_radius_squared,
+            );
+            (orbit, tail, entry_precision_bits)
+        }
+        None => {
+            let (orbit, tail) = compute_reference_orbit(
+                center_re,
+                center_im,
+                exponent,
+                formula,
+                length,
+                escape_radius_squared,
+            );
+            (Arc::new(orbit), tail, precision_bits)
+        }
+    };
+    with_orbit_cache(|cache| {
+        cache.insert(OrbitCacheEntry {
+            key: key.clone(),
             precision_bits,
-            orbit: Rc::clone(&orbit),
-        });
-        orbit
-    })
+            orbit: Arc::clone(&orbit),
+            tail,
+        })
+    });
+    drop(in_flight);
+    orbit
 }
 
 /// One perturbation step in plain f64: given the reference value `Z` and the
@@ -331,6 +854,46 @@ fn delta_step_float_exp(z_ref: Complex64, dz: ComplexExp, exponent: u32) -> Comp
     sum.mul(&dz)
 }
 
+/// The delta step of a formula orbit (see `polynomial`): index `n`'s row of
+/// the orbit's precomputed table, or the row computed on the spot past the
+/// table's size cap.
+struct FormulaStep<'a> {
+    formula: &'a Polynomial,
+    table: &'a [[f64; 2]],
+    degree: usize,
+}
+
+impl<'a> FormulaStep<'a> {
+    fn new(formula: &'a Polynomial, orbit: &'a ReferenceOrbit) -> FormulaStep<'a> {
+        FormulaStep {
+            formula,
+            table: &orbit.coeff_table,
+            degree: formula.degree() as usize,
+        }
+    }
+
+    fn with_row<R>(&self, index: usize, z_ref: Complex64, f: impl FnOnce(&[[f64; 2]]) -> R) -> R {
+        if self.table.is_empty() {
+            let mut row = [[0.0; 2]; MAX_PERTURBED_EXPONENT as usize];
+            self.formula
+                .delta_coefficients_into(z_ref, &mut row[..self.degree]);
+            f(&row[..self.degree])
+        } else {
+            f(&self.table[index * self.degree..(index + 1) * self.degree])
+        }
+    }
+
+    fn delta_f64(&self, index: usize, z_ref: Complex64, dz: Complex64) -> Complex64 {
+        self.with_row(index, z_ref, |row| polynomial::delta_step_f64(row, dz))
+    }
+
+    fn delta_float_exp(&self, index: usize, z_ref: Complex64, dz: ComplexExp) -> ComplexExp {
+        self.with_row(index, z_ref, |row| {
+            polynomial::delta_step_float_exp(row, dz)
+        })
+    }
+}
+
 /// Escape iterations for one pixel using f64 deltas with rebasing.
 fn perturbed_escape_iterations_f64(
     orbit: &[(f64, f64)],
@@ -338,6 +901,24 @@ fn perturbed_escape_iterations_f64(
     max_iterations: u32,
     exponent: u32,
     escape_radius_squared: f64,
+) -> (u32, Complex64) {
+    perturbed_escape_iterations_f64_by(
+        orbit,
+        dc,
+        max_iterations,
+        escape_radius_squared,
+        |_, z_ref, dz| delta_step_f64(z_ref, dz, exponent),
+    )
+}
+
+/// `perturbed_escape_iterations_f64` with the delta step
+/// `delta_step(n, Z_n, dz)`.
+fn perturbed_escape_iterations_f64_by(
+    orbit: &[(f64, f64)],
+    dc: Complex64,
+    max_iterations: u32,
+    escape_radius_squared: f64,
+    delta_step: impl Fn(usize, Complex64, Complex64) -> Complex64,
 ) -> (u32, Complex64) {
     let last_index = orbit.len() - 1;
     let mut reference_index: usize = 0;
@@ -346,7 +927,7 @@ fn perturbed_escape_iterations_f64(
 
     let advance = |reference_index: &mut usize, dz: &mut Complex64, z: &mut Complex64| {
         let z_ref = orbit[*reference_index];
-        *dz = delta_step_f64(Complex64::new(z_ref.0, z_ref.1), *dz, exponent) + dc;
+        *dz = delta_step(*reference_index, Complex64::new(z_ref.0, z_ref.1), *dz) + dc;
         *reference_index += 1;
 
         let z_ref_next = orbit[*reference_index];
@@ -1265,6 +1846,24 @@ fn perturbed_escape_iterations_float_exp(
         );
     }
 
+    perturbed_escape_iterations_float_exp_by(
+        orbit,
+        dc,
+        max_iterations,
+        escape_radius_squared,
+        |_, z_ref, dz| delta_step_float_exp(z_ref, dz, exponent),
+    )
+}
+
+/// The pure (non-hybrid) loop of `perturbed_escape_iterations_float_exp`,
+/// with the delta step `delta_step(n, Z_n, dz)`.
+fn perturbed_escape_iterations_float_exp_by(
+    orbit: &[(f64, f64)],
+    dc: ComplexExp,
+    max_iterations: u32,
+    escape_radius_squared: f64,
+    delta_step: impl Fn(usize, Complex64, ComplexExp) -> ComplexExp,
+) -> (u32, Complex64) {
     let last_index = orbit.len() - 1;
     let mut reference_index: usize = 0;
     let mut dz = ComplexExp::ZERO;
@@ -1272,7 +1871,7 @@ fn perturbed_escape_iterations_float_exp(
 
     let advance = |reference_index: &mut usize, dz: &mut ComplexExp, z: &mut Complex64| {
         let z_ref = orbit[*reference_index];
-        *dz = delta_step_float_exp(Complex64::new(z_ref.0, z_ref.1), *dz, exponent).add(&dc);
+        *dz = delta_step(*reference_index, Complex64::new(z_ref.0, z_ref.1), *dz).add(&dc);
         *reference_index += 1;
 
         let z_ref_next = orbit[*reference_index];
@@ -1630,10 +2229,249 @@ fn stream_hybrid_escape<const CHAINS: usize>(
     }
 }
 
+/// The (cached) reference orbit at the world origin, computed with enough
+/// precision for sub-pixel accuracy at `effective_zoom`, with headroom. It
+/// iterates `formula` plus `c` when given, `z^exponent + c` otherwise.
+fn reference_orbit_for_zoom(
+    origin_re: &str,
+    origin_im: &str,
+    effective_zoom: i64,
+    max_iterations: u32,
+    exponent: u32,
+    formula: Option<&Polynomial>,
+    escape_radius_squared: f64,
+) -> Result<Arc<ReferenceOrbit>, String> {
+    let precision_bits = orbit_precision_bits(effective_zoom);
+
+    let center_re = parse_decimal(origin_re, precision_bits)?;
+    let center_im = parse_decimal(origin_im, precision_bits)?;
+
+    let orbit_length = (max_iterations as usize).min(MAX_ORBIT_LENGTH) + 1;
+    Ok(get_reference_orbit(
+        origin_re,
+        origin_im,
+        &center_re,
+        &center_im,
+        exponent,
+        formula,
+        precision_bits,
+        orbit_length,
+        escape_radius_squared,
+    ))
+}
+
+/// Working precision of the reference orbit for `effective_zoom`.
+fn orbit_precision_bits(effective_zoom: i64) -> usize {
+    (effective_zoom.max(0) as usize + 64).div_ceil(32) * 32
+}
+
+/// Leading bytes of a serialized orbit, ending in a format version.
+const ORBIT_BUFFER_MAGIC: &[u8; 5] = b"MBRO\x01";
+
+/// The reference orbit tiles at `effective_zoom` around the origin render
+/// against, serialized for `install_reference_orbit` in another worker (or
+/// a later session): computed here, or taken from the orbit cache.
+///
+/// Layout, little-endian: `ORBIT_BUFFER_MAGIC`; the origin's real and
+/// imaginary decimal texts, each as a u32 byte length and UTF-8 bytes; the
+/// exponent (u32), the squared escape radius (f64), the working precision in
+/// bits (u64), the escaped flag (u8); the orbit values as a u64 count and
+/// `[re, im]` f64 pairs; the Horner-term table the same way.
+pub fn reference_orbit_buffer(
+    origin_re: &str,
+    origin_im: &str,
+    effective_zoom: i64,
+    max_iterations: u32,
+    exponent: u32,
+) -> Result<Vec<u8>, String> {
+    if !(2..=MAX_PERTURBED_EXPONENT).contains(&exponent) {
+        return Err(format!(
+            "exponent {exponent} does not render by perturbation"
+        ));
+    }
+    let escape_radius_squared = crate::ESCAPE_RADIUS.powi(2);
+    let orbit = reference_orbit_for_zoom(
+        origin_re,
+        origin_im,
+        effective_zoom,
+        max_iterations,
+        exponent,
+        None,
+        escape_radius_squared,
+    )?;
+    let entry = OrbitCacheEntry {
+        key: OrbitCacheKey {
+            origin_re: origin_re.to_string(),
+            origin_im: origin_im.to_string(),
+            exponent,
+            formula: None,
+            escape_radius_squared,
+        },
+        // A cached orbit may have run at more bits; claiming fewer only
+        // makes receivers recompute sooner.
+        precision_bits: orbit_precision_bits(effective_zoom),
+        orbit,
+        tail: None,
+    };
+    Ok(entry.to_bytes())
+}
+
+/// Installs a `reference_orbit_buffer` into the orbit cache, so renders of
+/// its view reuse the orbit instead of computing it. A cached orbit of the
+/// view that already serves the buffer's length, or can be extended, is
+/// kept; only a strictly longer buffer replaces it. Fails, leaving the
+/// cache untouched, on a buffer that is malformed or inconsistent.
+pub fn install_reference_orbit(buffer: &[u8]) -> Result<(), String> {
+    let entry = OrbitCacheEntry::from_bytes(buffer)?;
+    with_orbit_cache(|cache| cache.install(entry));
+    Ok(())
+}
+
+impl OrbitCacheEntry {
+    fn to_bytes(&self) -> Vec<u8> {
+        let orbit = &self.orbit;
+        let mut bytes = Vec::with_capacity(
+            64 + self.key.origin_re.len()
+                + self.key.origin_im.len()
+                + 16 * (orbit.values.len() + orbit.coeff_table.len()),
+        );
+        bytes.extend_from_slice(ORBIT_BUFFER_MAGIC);
+        for text in [&self.key.origin_re, &self.key.origin_im] {
+            bytes.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(text.as_bytes());
+        }
+        bytes.extend_from_slice(&self.key.exponent.to_le_bytes());
+        bytes.extend_from_slice(&self.key.escape_radius_squared.to_le_bytes());
+        bytes.extend_from_slice(&(self.precision_bits as u64).to_le_bytes());
+        bytes.push(orbit.escaped as u8);
+        bytes.extend_from_slice(&(orbit.values.len() as u64).to_le_bytes());
+        for &(re, im) in &orbit.values {
+            bytes.extend_from_slice(&re.to_le_bytes());
+            bytes.extend_from_slice(&im.to_le_bytes());
+        }
+        bytes.extend_from_slice(&(orbit.coeff_table.len() as u64).to_le_bytes());
+        for &[re, im] in &orbit.coeff_table {
+            bytes.extend_from_slice(&re.to_le_bytes());
+            bytes.extend_from_slice(&im.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Parses `to_bytes` output. The SIMD kernels index the orbit and its
+    /// term table unchecked, so lengths are validated against what
+    /// `compute_reference_orbit` could have produced.
+    fn from_bytes(bytes: &[u8]) -> Result<OrbitCacheEntry, String> {
+        let mut reader = ByteReader { bytes };
+        if reader.take(ORBIT_BUFFER_MAGIC.len())? != ORBIT_BUFFER_MAGIC {
+            return Err("not a reference orbit buffer".to_string());
+        }
+        let origin_re = reader.text()?;
+        let origin_im = reader.text()?;
+        let exponent = reader.u32()?;
+        let escape_radius_squared = reader.f64()?;
+        let precision_bits = reader.u64()? as usize;
+        let escaped = match reader.take(1)?[0] {
+            0 => false,
+            1 => true,
+            _ => return Err("invalid escaped flag".to_string()),
+        };
+        if !(2..=MAX_PERTURBED_EXPONENT).contains(&exponent) {
+            return Err(format!("unsupported exponent {exponent}"));
+        }
+
+        let values = reader.pairs(MAX_ORBIT_LENGTH + 1)?;
+        if values.first() != Some(&(0.0, 0.0)) {
+            return Err("orbit must start at zero".to_string());
+        }
+        let coeff_table: Vec<[f64; 2]> = reader
+            .pairs(COEFF_TABLE_MAX_ENTRIES)?
+            .into_iter()
+            .map(|(re, im)| [re, im])
+            .collect();
+        let terms = (exponent - 1) as usize;
+        let expected_table_len =
+            if exponent == 2 || values.len().saturating_mul(terms) > COEFF_TABLE_MAX_ENTRIES {
+                0
+            } else {
+                values.len() * terms
+            };
+        if coeff_table.len() != expected_table_len {
+            return Err("term table does not match the orbit".to_string());
+        }
+        if !reader.bytes.is_empty() {
+            return Err("trailing bytes after the orbit".to_string());
+        }
+
+        Ok(OrbitCacheEntry {
+            key: OrbitCacheKey {
+                origin_re,
+                origin_im,
+                exponent,
+                formula: None,
+                escape_radius_squared,
+            },
+            precision_bits,
+            orbit: Arc::new(ReferenceOrbit {
+                values,
+                escaped,
+                coeff_table,
+            }),
+            tail: None,
+        })
+    }
+}
+
+/// Reads `OrbitCacheEntry::to_bytes` fields from the front of a buffer.
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], String> {
+        if count > self.bytes.len() {
+            return Err("truncated reference orbit buffer".to_string());
+        }
+        let (taken, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        Ok(self.take(N)?.try_into().expect("took N bytes"))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn f64(&mut self) -> Result<f64, String> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+
+    fn text(&mut self) -> Result<String, String> {
+        let length = self.u32()? as usize;
+        String::from_utf8(self.take(length)?.to_vec())
+            .map_err(|_| "origin is not UTF-8".to_string())
+    }
+
+    /// A u64 count (at most `max_count`) of f64 pairs.
+    fn pairs(&mut self, max_count: usize) -> Result<Vec<(f64, f64)>, String> {
+        let count = self.u64()?;
+        if count > max_count as u64 || count * 16 > self.bytes.len() as u64 {
+            return Err("truncated reference orbit buffer".to_string());
+        }
+        (0..count).map(|_| Ok((self.f64()?, self.f64()?))).collect()
+    }
+}
+
 /// The geometry of a render target in tile space, plus everything needed to
 /// turn pixel indices into perturbation deltas.
 pub struct PerturbedFrame {
-    orbit: Rc<ReferenceOrbit>,
+    orbit: Arc<ReferenceOrbit>,
     /// Offsets from the world origin (before deep-zoom scaling) of the first
     /// column/row, and the per-pixel steps, all at `tile_zoom` scale.
     first_column_offset: f64,
@@ -1642,9 +2480,15 @@ pub struct PerturbedFrame {
     row_step: f64,
     /// The deep-zoom scaling: deltas are `offset * 2^-zoom_offset`.
     zoom_offset: i64,
+    /// Rotation/skew applied to each pixel's offsets before that scaling.
+    transform: ViewTransform,
     use_float_exp: bool,
     max_iterations: u32,
+    /// For a formula frame, the formula's degree.
     exponent: u32,
+    /// The formula iterated in place of `z^exponent + c`, if any. Formula
+    /// frames run the scalar kernels with `FormulaStep`.
+    formula: Option<Polynomial>,
     escape_radius_squared: f64,
 }
 
@@ -1659,32 +2503,98 @@ impl PerturbedFrame {
         tile_y_max: f64,
         tile_zoom: i32,
         zoom_offset: u32,
+        transform: ViewTransform,
         image_width: usize,
         image_height: usize,
         max_iterations: u32,
         exponent: u32,
         escape_radius: f64,
     ) -> Result<PerturbedFrame, String> {
-        let effective_zoom = tile_zoom as i64 + zoom_offset as i64;
-
-        // Enough precision for sub-pixel accuracy, with headroom.
-        let precision_bits = (effective_zoom.max(0) as usize + 64).div_ceil(32) * 32;
+        PerturbedFrame::iterating(
+            origin_re,
+            origin_im,
+            tile_x_min,
+            tile_x_max,
+            tile_y_min,
+            tile_y_max,
+            tile_zoom,
+            zoom_offset,
+            transform,
+            image_width,
+            image_height,
+            max_iterations,
+            exponent,
+            None,
+            escape_radius,
+        )
+    }
 
-        let center_re = parse_decimal(origin_re, precision_bits)?;
-        let center_im = parse_decimal(origin_im, precision_bits)?;
+    /// A frame iterating `formula` plus `c` (see `polynomial`), escaping at
+    /// the formula's own radius.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new_polynomial(
+        origin_re: &str,
+        origin_im: &str,
+        tile_x_min: f64,
+        tile_x_max: f64,
+        tile_y_min: f64,
+        tile_y_max: f64,
+        tile_zoom: i32,
+        zoom_offset: u32,
+        transform: ViewTransform,
+        image_width: usize,
+        image_height: usize,
+        max_iterations: u32,
+        formula: &Polynomial,
+    ) -> Result<PerturbedFrame, String> {
+        PerturbedFrame::iterating(
+            origin_re,
+            origin_im,
+            tile_x_min,
+            tile_x_max,
+            tile_y_min,
+            tile_y_max,
+            tile_zoom,
+            zoom_offset,
+            transform,
+            image_width,
+            image_height,
+            max_iterations,
+            formula.degree(),
+            Some(formula),
+            formula.escape_radius(),
+        )
+    }
 
+    #[allow(clippy::too_many_arguments)]
+    fn iterating(
+        origin_re: &str,
+        origin_im: &str,
+        tile_x_min: f64,
+        tile_x_max: f64,
+        tile_y_min: f64,
+        tile_y_max: f64,
+        tile_zoom: i32,
+        zoom_offset: u32,
+        transform: ViewTransform,
+        image_width: usize,
+        image_height: usize,
+        max_iterations: u32,
+        exponent: u32,
+        formula: Option<&Polynomial>,
+        escape_radius: f64,
+    ) -> Result<PerturbedFrame, String> {
+        let effective_zoom = tile_zoom as i64 + zoom_offset as i64;
         let escape_radius_squared = escape_radius * escape_radius;
-        let orbit_length = (max_iterations as usize).min(MAX_ORBIT_LENGTH) + 1;
-        let orbit = get_reference_orbit(
+        let orbit = reference_orbit_for_zoom(
             origin_re,
             origin_im,
-            &center_re,
-            &center_im,
+            effective_zoom,
+            max_iterations,
             exponent,
-            precision_bits,
-            orbit_length,
+            formula,
             escape_radius_squared,
-        );
+        )?;
 
         // Match linspace semantics of the direct renderer: endpoints
         // inclusive, so the step divides by (count - 1).
@@ -1712,22 +2622,31 @@ impl PerturbedFrame {
             column_step,
             row_step,
             zoom_offset: zoom_offset as i64,
+            transform,
             // The `exponent == 2` gate lives in `uses_float_exp` (the getter);
             // this raw flag stays keyed on depth alone, as the compute paths
             // expect (they combine it with `self.exponent == 2` themselves).
             use_float_exp: effective_zoom >= FLOAT_EXP_THRESHOLD,
             max_iterations,
             exponent,
+            formula: formula.cloned(),
             escape_radius_squared,
         })
     }
 
+    /// The exponent escape values smooth by: the formula's degree for a
+    /// formula frame.
+    pub(crate) fn exponent(&self) -> u32 {
+        self.exponent
+    }
+
     /// Whether this frame runs the hybrid float-exp kernel rather than the
     /// plain f64-delta one. Matches the `compute_all` dispatch: float-exp is
-    /// only taken for the quadratic set (other exponents stay on f64 deltas),
-    /// so the client's diagnostics overlay can report the true tier.
+    /// only taken for the quadratic set (other exponents stay on f64 deltas)
+    /// and for formulas (see `formula_uses_float_exp`), so the client's
+    /// diagnostics overlay can report the true tier.
     pub fn uses_float_exp(&self) -> bool {
-        self.use_float_exp && self.exponent == 2
+        self.use_float_exp && (self.exponent == 2 || self.formula.is_some())
     }
 
     /// The iteration budget the kernels actually run: the requested budget
@@ -1771,125 +2690,180 @@ impl PerturbedFrame {
 
     /// The pixel's perturbation delta from the reference point as a plain
     /// f64 (only meaningful on the f64-delta path).
+    #[cfg(any(target_arch = "wasm32", test))]
     fn pixel_dc_f64(&self, column: usize, row: usize) -> Complex64 {
-        let re_offset = self.first_column_offset + self.column_step * column as f64;
-        let im_offset = self.first_row_offset + self.row_step * row as f64;
+        self.sample_dc_f64(column as f64, row as f64)
+    }
+
+    /// Tile-scale offsets (before the `2^-zoom_offset` deep-zoom scaling) of
+    /// a possibly fractional pixel position, with the view transform
+    /// applied. Kept at tile scale, where they are O(1), so every caller
+    /// applies the deep scaling last.
+    fn sample_offsets(&self, column: f64, row: f64) -> (f64, f64) {
+        self.transform.apply(
+            self.first_column_offset + self.column_step * column,
+            self.first_row_offset + self.row_step * row,
+        )
+    }
+
+    /// `pixel_dc_f64` at a possibly fractional pixel position.
+    fn sample_dc_f64(&self, column: f64, row: f64) -> Complex64 {
+        let (re_offset, im_offset) = self.sample_offsets(column, row);
         Complex64::new(
             ldexp(re_offset, -self.zoom_offset),
             ldexp(im_offset, -self.zoom_offset),
         )
     }
 
-    /// Escape results for every pixel in row-major order. The f64-delta path
-    /// streams all pixels through the lane-refilling kernel on wasm32; the
-    /// float-exp path falls back to the per-pixel loops in pairs.
+    /// Escape results for every pixel in row-major order. Quadratic tiles
+    /// render by Mariani–Silver subdivision on every tier: interior pixels
+    /// at these depths never rebase, so periodicity can't retire them and
+    /// only the ring-fill saves their full budget — deep minibrot interiors
+    /// most of all. Multibrot and formula tiles compute every pixel: the one
+    /// real multibrot pf64 view has scattered interior that never fills, so
+    /// it would pay only the wave overhead. Each row band (see
+    /// `for_each_row_band`) renders on its own, sharing this frame's orbit.
     pub fn compute_all(&self, image_width: usize, image_height: usize) -> Vec<(u32, Complex64)> {
-        let pixel_count = image_width * image_height;
-        let mut results = vec![(0u32, Complex64::new(0.0, 0.0)); pixel_count];
+        let mut results =
+            vec![(crate::UNCOMPUTED, Complex64::new(0.0, 0.0)); image_width * image_height];
+        self.complete(image_width, 0, &mut results);
+        results
+    }
 
+    /// `compute_all` for the `UNCOMPUTED` slots of `results`, which holds
+    /// the tile's rows from `first_row` on. Slots already filled are kept,
+    /// and the subdivision's ring tests read them like computed ones, so a
+    /// render can be seeded with results it already has.
+    pub fn complete(&self, image_width: usize, first_row: usize, results: &mut [(u32, Complex64)]) {
+        crate::for_each_row_band(image_width, results, |band_first_row, band_results| {
+            let first_pixel = (first_row + band_first_row) * image_width;
+            if self.exponent != 2 || self.formula.is_some() {
+                let pixels: Vec<usize> = (0..band_results.len())
+                    .filter(|&position| band_results[position].0 == crate::UNCOMPUTED)
+                    .map(|position| first_pixel + position)
+                    .collect();
+                let computed = self.compute_pixels(&pixels, image_width);
+                for (&pixel, result) in pixels.iter().zip(computed) {
+                    band_results[pixel - first_pixel] = result;
+                }
+                return;
+            }
+
+            // The wave driver numbers pixels within the band.
+            let mut pixels: Vec<usize> = Vec::new();
+            crate::subdivide_tile_streamed(
+                image_width,
+                band_results.len() / image_width,
+                self.max_iterations,
+                band_results,
+                |band_pixels, wave_results| {
+                    pixels.clear();
+                    pixels.extend(band_pixels.iter().map(|&pixel| first_pixel + pixel));
+                    self.compute_pixels_into(&pixels, image_width, wave_results);
+                },
+            );
+        });
+    }
+
+    /// Escape results for the listed pixels (row-major indices into an
+    /// `image_width`-wide grid), in list order: `compute_all` for a subset
+    /// (see `progressive`), without its Mariani–Silver fill, which needs
+    /// whole rectangles.
+    pub fn compute_pixels(&self, pixels: &[usize], image_width: usize) -> Vec<(u32, Complex64)> {
+        let mut results = vec![(0u32, Complex64::new(0.0, 0.0)); pixels.len()];
+        self.compute_pixels_into(pixels, image_width, &mut results);
+        results
+    }
+
+    /// `compute_pixels` into the matching slots of `results`. Budget-clamped
+    /// survivors are reported as interior (see `report`) before the wave
+    /// driver's ring-fill decisions read them. The f64-delta and quadratic
+    /// float-exp tiers of `z^power + c` stream the list through the
+    /// lane-refilling kernels on wasm32; everything else, formulas included,
+    /// computes pixel by pixel.
+    fn compute_pixels_into(
+        &self,
+        pixels: &[usize],
+        image_width: usize,
+        results: &mut [(u32, Complex64)],
+    ) {
         #[cfg(target_arch = "wasm32")]
-        if self.use_float_exp && self.exponent == 2 {
-            let dc_of = |pixel: usize| {
-                let column = pixel % image_width;
-                let row = pixel / image_width;
-                let re_offset = self.first_column_offset + self.column_step * column as f64;
-                let im_offset = self.first_row_offset + self.row_step * row as f64;
+        if self.use_float_exp && self.exponent == 2 && self.formula.is_none() {
+            let dc_of = |position: usize| {
+                let pixel = pixels[position];
+                let (re_offset, im_offset) =
+                    self.sample_offsets((pixel % image_width) as f64, (pixel / image_width) as f64);
                 ComplexExp::new(re_offset, im_offset, -self.zoom_offset)
             };
             stream_hybrid_escape::<PERTURB_STREAM_CHAINS>(
                 &self.orbit.values,
                 dc_of,
-                pixel_count,
+                pixels.len(),
                 self.kernel_budget(),
                 self.escape_radius_squared,
-                &mut results,
+                results,
             );
             for result in results.iter_mut() {
                 *result = self.report(*result);
             }
-            return results;
+            return;
         }
 
         #[cfg(target_arch = "wasm32")]
-        if !self.use_float_exp {
+        if !self.use_float_exp && self.formula.is_none() {
+            let dc_of = |position: usize| {
+                let pixel = pixels[position];
+                self.pixel_dc_f64(pixel % image_width, pixel / image_width)
+            };
             if self.exponent == 2 {
-                // Mariani–Silver subdivision over the stream kernel: interior
-                // pixels at these depths never rebase, so periodicity can't
-                // retire them and only the ring-fill saves their full budget.
-                // Multibrot tiles stay on the plain stream call: the one real
-                // multibrot pf64 view has scattered interior that never fills,
-                // so it pays only the wave overhead.
-                results.fill((crate::UNCOMPUTED, Complex64::new(0.0, 0.0)));
-                crate::subdivide_tile_streamed(
-                    image_width,
-                    image_height,
-                    self.max_iterations,
-                    &mut results,
-                    |pixels, wave_results| {
-                        let dc_of = |position: usize| {
-                            let pixel = pixels[position];
-                            self.pixel_dc_f64(pixel % image_width, pixel / image_width)
-                        };
-                        stream_perturbed_escape_f64::<PERTURB_STREAM_CHAINS, false>(
-                            &self.orbit.values,
-                            &self.orbit.coeff_table,
-                            dc_of,
-                            pixels.len(),
-                            self.kernel_budget(),
-                            self.exponent,
-                            self.escape_radius_squared,
-                            wave_results,
-                        );
-                        // Report budget-clamped survivors as interior before
-                        // the wave driver's ring-fill decisions read them.
-                        for result in wave_results.iter_mut() {
-                            *result = self.report(*result);
-                        }
-                    },
+                stream_perturbed_escape_f64::<PERTURB_STREAM_CHAINS, false>(
+                    &self.orbit.values,
+                    &self.orbit.coeff_table,
+                    dc_of,
+                    pixels.len(),
+                    self.kernel_budget(),
+                    self.exponent,
+                    self.escape_radius_squared,
+                    results,
                 );
             } else {
-                let dc_of =
-                    |pixel: usize| self.pixel_dc_f64(pixel % image_width, pixel / image_width);
                 stream_perturbed_escape_f64::<PERTURB_STREAM_CHAINS, true>(
                     &self.orbit.values,
                     &self.orbit.coeff_table,
                     dc_of,
-                    pixel_count,
+                    pixels.len(),
                     self.kernel_budget(),
                     self.exponent,
                     self.escape_radius_squared,
-                    &mut results,
+                    results,
                 );
-                for result in results.iter_mut() {
-                    *result = self.report(*result);
-                }
             }
-            return results;
+            for result in results.iter_mut() {
+                *result = self.report(*result);
+            }
+            return;
         }
 
-        for row in 0..image_height {
-            let mut column = 0;
-            while column < image_width {
-                // Batch pairs of pixels into SIMD lanes where a batched
-                // implementation exists; a trailing odd pixel is paired with
-                // itself.
-                let second_column = (column + 1).min(image_width - 1);
-                let pair = self.escape_iterations_pair((column, row), (second_column, row));
-                results[row * image_width + column] = pair[0];
-                if second_column != column {
-                    results[row * image_width + second_column] = pair[1];
-                }
-                column += 2;
-            }
+        for (result, &pixel) in results.iter_mut().zip(pixels) {
+            *result = self.escape_iterations(pixel % image_width, pixel / image_width);
         }
-        results
     }
 
     /// Escape iterations and final value for the pixel at (column, row).
     pub fn escape_iterations(&self, column: usize, row: usize) -> (u32, Complex64) {
+        self.escape_iterations_at(column as f64, row as f64)
+    }
+
+    /// Escape iterations and final value at a possibly fractional pixel
+    /// position, e.g. a supersampling point inside the pixel at
+    /// (column, row). Whole positions match `escape_iterations` exactly.
+    pub fn escape_iterations_at(&self, column: f64, row: f64) -> (u32, Complex64) {
+        if let Some(formula) = &self.formula {
+            return self.formula_escape_iterations_at(formula, column, row);
+        }
+
         let result = if self.use_float_exp {
-            let re_offset = self.first_column_offset + self.column_step * column as f64;
-            let im_offset = self.first_row_offset + self.row_step * row as f64;
+            let (re_offset, im_offset) = self.sample_offsets(column, row);
             let dc = ComplexExp::new(re_offset, im_offset, -self.zoom_offset);
             perturbed_escape_iterations_float_exp(
                 &self.orbit.values,
@@ -1899,7 +2873,7 @@ impl PerturbedFrame {
                 self.escape_radius_squared,
             )
         } else {
-            let dc = self.pixel_dc_f64(column, row);
+            let dc = self.sample_dc_f64(column, row);
             perturbed_escape_iterations_f64(
                 &self.orbit.values,
                 dc,
@@ -1911,6 +2885,35 @@ impl PerturbedFrame {
         self.report(result)
     }
 
+    /// `escape_iterations_at` for a formula frame.
+    fn formula_escape_iterations_at(
+        &self,
+        formula: &Polynomial,
+        column: f64,
+        row: f64,
+    ) -> (u32, Complex64) {
+        let step = FormulaStep::new(formula, &self.orbit);
+        let result = if self.use_float_exp {
+            let (re_offset, im_offset) = self.sample_offsets(column, row);
+            perturbed_escape_iterations_float_exp_by(
+                &self.orbit.values,
+                ComplexExp::new(re_offset, im_offset, -self.zoom_offset),
+                self.kernel_budget(),
+                self.escape_radius_squared,
+                |index, z_ref, dz| step.delta_float_exp(index, z_ref, dz),
+            )
+        } else {
+            perturbed_escape_iterations_f64_by(
+                &self.orbit.values,
+                self.sample_dc_f64(column, row),
+                self.kernel_budget(),
+                self.escape_radius_squared,
+                |index, z_ref, dz| step.delta_f64(index, z_ref, dz),
+            )
+        };
+        self.report(result)
+    }
+
     /// Escape iterations for two pixels sharing one call, batched into SIMD
     /// lanes where a batched implementation exists (wasm32, f64 deltas).
     #[cfg(target_arch = "wasm32")]
@@ -1919,7 +2922,7 @@ impl PerturbedFrame {
         first: (usize, usize),
         second: (usize, usize),
     ) -> [(u32, Complex64); 2] {
-        if self.use_float_exp {
+        if self.use_float_exp || self.formula.is_some() {
             return [
                 self.escape_iterations(first.0, first.1),
                 self.escape_iterations(second.0, second.1),
@@ -1986,3 +2989,113 @@ impl PerturbedFrame {
         true
     }
 }
+
+/// Log2 of the radius ratio between adjacent rows of an exponential map
+/// `image_width` pixels wide. The columns span one full turn, so shrinking
+/// the radius by `e^(2 pi / width)` per row keeps pixels square.
+pub fn exponential_map_row_step_log2(image_width: usize) -> f64 {
+    std::f64::consts::TAU / image_width.max(1) as f64 / std::f64::consts::LN_2
+}
+
+/// An exponential-map (log-polar) render target around the world origin:
+/// column `x` is the angle `2 pi x / width` (counterclockwise from the
+/// positive real axis) and row `y` the radius
+/// `2^(outer_radius_log2 - y * exponential_map_row_step_log2(width))`, so a
+/// tall strip covers many zoom decades with square pixels. A strip rendered
+/// in chunks continues seamlessly when each chunk's `outer_radius_log2`
+/// steps down by its height in rows.
+///
+/// Every pixel runs the float-exp kernel: row deltas span far more than the
+/// f64 exponent range within one strip, and the kernel's hybrid loop keeps
+/// the rows where they fit at f64 speed. The origin should be the zoom
+/// target, as the reference orbit sits there.
+pub struct ExponentialMapFrame {
+    orbit: Arc<ReferenceOrbit>,
+    outer_radius_log2: f64,
+    row_step_log2: f64,
+    column_angle: f64,
+    max_iterations: u32,
+    exponent: u32,
+    escape_radius_squared: f64,
+}
+
+impl ExponentialMapFrame {
+    pub fn new(
+        origin_re: &str,
+        origin_im: &str,
+        outer_radius_log2: f64,
+        image_width: usize,
+        image_height: usize,
+        max_iterations: u32,
+        exponent: u32,
+        escape_radius: f64,
+    ) -> Result<ExponentialMapFrame, String> {
+        if !(2..=MAX_PERTURBED_EXPONENT).contains(&exponent) {
+            return Err(format!("unsupported exponent for perturbation: {exponent}"));
+        }
+        if !outer_radius_log2.is_finite() {
+            return Err("non-finite exponential map radius".to_string());
+        }
+
+        let row_step_log2 = exponential_map_row_step_log2(image_width);
+        let column_angle = std::f64::consts::TAU / image_width.max(1) as f64;
+
+        // The innermost row sets the precision: its pixel spacing is the
+        // radius times the angle between columns.
+        let inner_radius_log2 =
+            outer_radius_log2 - image_height.saturating_sub(1) as f64 * row_step_log2;
+        let effective_zoom = (-(inner_radius_log2 + column_angle.log2())).ceil() as i64;
+
+        let escape_radius_squared = escape_radius * escape_radius;
+        let orbit = reference_orbit_for_zoom(
+            origin_re,
+            origin_im,
+            effective_zoom,
+            max_iterations,
+            exponent,
+            None,
+            escape_radius_squared,
+        )?;
+
+        Ok(ExponentialMapFrame {
+            orbit,
+            outer_radius_log2,
+            row_step_log2,
+            column_angle,
+            max_iterations,
+            exponent,
+            escape_radius_squared,
+        })
+    }
+
+    /// The pixel's delta from the origin. The radius's integer power of two
+    /// goes straight into the exponent, so the mantissa stays O(1) at any
+    /// depth.
+    fn pixel_dc(&self, column: usize, row: usize) -> ComplexExp {
+        let radius_log2 = self.outer_radius_log2 - row as f64 * self.row_step_log2;
+        let whole = radius_log2.floor();
+        let scale = (radius_log2 - whole).exp2();
+        let (sin, cos) = (column as f64 * self.column_angle).sin_cos();
+        ComplexExp::new(cos * scale, sin * scale, whole as i64)
+    }
+
+    /// Escape iterations and final value for the pixel at (column, row),
+    /// with the budget clamped to the stored orbit as in
+    /// `PerturbedFrame::kernel_budget` (pixels alive at the clamp report
+    /// `max_iterations`).
+    pub fn escape_iterations(&self, column: usize, row: usize) -> (u32, Complex64) {
+        let budget = self.max_iterations.min(MAX_ORBIT_LENGTH as u32);
+        let result = perturbed_escape_iterations_float_exp(
+            &self.orbit.values,
+            self.pixel_dc(column, row),
+            budget,
+            self.exponent,
+            self.escape_radius_squared,
+        );
+        if result.0 >= budget {
+            (self.max_iterations, result.1)
+        } else {
+            result
+        }
+    }
+}
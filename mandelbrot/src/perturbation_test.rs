@@ -54,6 +54,7 @@ fn make_frame(
         center + 1.0,
         tile_zoom,
         zoom_offset,
+        ViewTransform::IDENTITY,
         image_size,
         image_size,
         max_iterations,
@@ -122,6 +123,107 @@ fn perturbed_matches_direct_f64_at_moderate_zoom() {
     );
 }
 
+#[test]
+fn escape_iterations_at_fractional_positions() {
+    // Supersampling goes through the fractional entry point; at whole
+    // positions it must reproduce `escape_iterations` exactly on both delta
+    // paths, so a 1x1 grid renders the aliased tile.
+    for (tile_zoom, zoom_offset) in [(33, 2), (10, 290)] {
+        let image_size = 16;
+        let frame = make_frame(DEEP_RE, DEEP_IM, tile_zoom, zoom_offset, image_size, 1200);
+        assert_eq!(frame.uses_float_exp(), zoom_offset > 100);
+
+        for row in 0..image_size {
+            for column in 0..image_size {
+                assert_eq!(
+                    frame.escape_iterations_at(column as f64, row as f64),
+                    frame.escape_iterations(column, row),
+                    "pixel ({column}, {row}) at zoom offset {zoom_offset}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn view_transform_rotation_and_matrix() {
+    let quarter = ViewTransform::new(90.0, None);
+    assert_eq!(quarter.apply(1.0, 0.0), (0.0, 1.0));
+    assert_eq!(quarter.apply(0.0, 1.0), (-1.0, 0.0));
+    assert_eq!(ViewTransform::new(-270.0, None), quarter);
+    assert!(ViewTransform::new(360.0, None).is_identity());
+
+    // The skew applies first, then the rotation.
+    let stretched = ViewTransform::new(90.0, Some([2.0, 0.0, 0.0, 0.5]));
+    assert_eq!(stretched.apply(1.0, 1.0), (-0.5, 2.0));
+    assert!((stretched.min_scale() - 0.5).abs() < 1e-12);
+    assert!((ViewTransform::new(30.0, None).min_scale() - 1.0).abs() < 1e-12);
+
+    // Untrusted payload values never collapse or poison the view.
+    assert!(ViewTransform::new(f64::NAN, Some([1.0, 2.0, 2.0, 4.0])).is_identity());
+    assert!(ViewTransform::new(0.0, Some([1.0, f64::INFINITY, 0.0, 1.0])).is_identity());
+}
+
+#[test]
+fn rotated_perturbed_matches_direct_f64() {
+    // The transform rotates tile-scale offsets before the deep scaling, so
+    // a rotated frame must agree with direct f64 iteration at the rotated
+    // points, as the unrotated frame does in
+    // `perturbed_matches_direct_f64_at_moderate_zoom`.
+    let image_size = 40;
+    let max_iterations = 800;
+    let tile_zoom = 33;
+    let zoom_offset = 2;
+    let transform = ViewTransform::new(30.0, Some([1.0, 0.25, 0.0, 1.0]));
+    let center = centered_tile_coordinate(tile_zoom).floor();
+    let frame = PerturbedFrame::new(
+        DEEP_RE,
+        DEEP_IM,
+        center,
+        center + 1.0,
+        center,
+        center + 1.0,
+        tile_zoom,
+        zoom_offset,
+        transform,
+        image_size,
+        image_size,
+        max_iterations,
+        2,
+        3.0,
+    )
+    .unwrap();
+
+    let origin_re: f64 = DEEP_RE.parse().unwrap();
+    let origin_im: f64 = DEEP_IM.parse().unwrap();
+    let mut matching = 0;
+    for row in 0..image_size {
+        for column in 0..image_size {
+            let x = center + column as f64 / (image_size - 1) as f64;
+            let y = center + row as f64 / (image_size - 1) as f64;
+            let (re_offset, im_offset) = transform.apply(
+                tile_coordinate_offset(x, tile_zoom),
+                -tile_coordinate_offset(y, tile_zoom),
+            );
+            let re = origin_re + crate::float_exp::ldexp(re_offset, -(zoom_offset as i64));
+            let im = origin_im + crate::float_exp::ldexp(im_offset, -(zoom_offset as i64));
+
+            let (direct_iterations, _) =
+                crate::calculate_escape_iterations(re, im, max_iterations, 2);
+            let (perturbed_iterations, _) = frame.escape_iterations(column, row);
+            let difference = direct_iterations.abs_diff(perturbed_iterations);
+            assert!(
+                difference <= 5,
+                "pixel ({column}, {row}): direct {direct_iterations} vs perturbed {perturbed_iterations}"
+            );
+            if difference == 0 {
+                matching += 1;
+            }
+        }
+    }
+    assert!(matching as f64 / (image_size * image_size) as f64 > 0.98);
+}
+
 #[test]
 fn perturbed_matches_full_precision_direct() {
     // One depth on the f64-delta path and one on the extended-exponent path.
@@ -531,6 +633,7 @@ fn probe_frame(view: &ProbeView) -> PerturbedFrame {
         v + 1.0,
         tile_zoom,
         zoom_offset,
+        ViewTransform::IDENTITY,
         PROBE_TILE_SIZE,
         PROBE_TILE_SIZE,
         view.iterations,
@@ -579,7 +682,7 @@ fn exact_escape_counted(
         *advances += 1;
         iterations += 1;
 
-        if iterations % PERIODICITY_CHECK_STRIDE == 0 {
+        if iterations.is_multiple_of(PERIODICITY_CHECK_STRIDE) {
             if dz == saved_dz && reference_index == saved_index {
                 return (max_iterations, z);
             }
@@ -744,7 +847,7 @@ fn bla_escape_counted(
         iterations += 1;
         *advances += 1;
 
-        if iterations % PERIODICITY_CHECK_STRIDE == 0 {
+        if iterations.is_multiple_of(PERIODICITY_CHECK_STRIDE) {
             if dz == saved_dz && index == saved_index {
                 return (max_iterations, z);
             }
@@ -1060,6 +1163,7 @@ fn clamp_probe_frame(max_iterations: u32) -> PerturbedFrame {
         v + 1.0,
         tile_zoom,
         zoom_offset,
+        ViewTransform::IDENTITY,
         101,
         101,
         max_iterations,
@@ -1670,3 +1774,671 @@ fn misiurewicz_m221_tile_renders_structure_at_e1000_depth() {
     );
     assert!(tile.max_iter < max_iterations as i32);
 }
+
+/// A seahorse-valley point short enough to parse exactly enough as an f64,
+/// for comparing exponential-map pixels against direct f64 iteration.
+const SHALLOW_RE: &str = "-0.743643887037158";
+const SHALLOW_IM: &str = "0.131825904205311";
+
+#[test]
+fn exponential_map_pixels_are_square() {
+    let width = 64;
+    let frame =
+        ExponentialMapFrame::new(SHALLOW_RE, SHALLOW_IM, -2.0, width, 8, 100, 2, 3.0).unwrap();
+    let magnitude = |dc: ComplexExp| (dc.re.hypot(dc.im)).log2() + dc.exp as f64;
+
+    // One row down shrinks the radius by the same factor one column turns
+    // the angle (in radians), so pixels stay square at every depth.
+    let row_ratio = magnitude(frame.pixel_dc(0, 3)) - magnitude(frame.pixel_dc(0, 4));
+    let turn = std::f64::consts::TAU / width as f64;
+    assert!((row_ratio * std::f64::consts::LN_2 - turn).abs() < 1e-12);
+    assert!((magnitude(frame.pixel_dc(0, 0)) + 2.0).abs() < 1e-12);
+
+    let quarter = frame.pixel_dc(width / 4, 0);
+    assert!(
+        quarter.re.abs() < 1e-12 && quarter.im > 0.0,
+        "x is the angle"
+    );
+}
+
+#[test]
+fn exponential_map_matches_direct_f64() {
+    let (width, height) = (64, 48);
+    let max_iterations = 1000;
+    let outer_radius_log2 = -2.0;
+    let frame = ExponentialMapFrame::new(
+        SHALLOW_RE,
+        SHALLOW_IM,
+        outer_radius_log2,
+        width,
+        height,
+        max_iterations,
+        2,
+        3.0,
+    )
+    .unwrap();
+
+    let origin_re: f64 = SHALLOW_RE.parse().unwrap();
+    let origin_im: f64 = SHALLOW_IM.parse().unwrap();
+    let row_step = exponential_map_row_step_log2(width);
+    let mut matching = 0;
+    for row in 0..height {
+        let radius = (outer_radius_log2 - row as f64 * row_step).exp2();
+        for column in 0..width {
+            let angle = std::f64::consts::TAU * column as f64 / width as f64;
+            let (direct_iterations, _) = crate::calculate_escape_iterations(
+                origin_re + radius * angle.cos(),
+                origin_im + radius * angle.sin(),
+                max_iterations,
+                2,
+            );
+            let (perturbed_iterations, _) = frame.escape_iterations(column, row);
+            if direct_iterations == perturbed_iterations {
+                matching += 1;
+            }
+        }
+    }
+    // A handful of near-boundary pixels are chaotic enough that the last-bit
+    // differences between the two ways of forming `c` move their escape
+    // counts, so the bound is on the share of exact matches.
+    assert!(
+        matching as f64 / (width * height) as f64 > 0.99,
+        "only {matching}/{} pixels matched exactly",
+        width * height
+    );
+}
+
+#[test]
+fn exponential_map_matches_full_precision_direct() {
+    // Rows on the f64-delta and extended-exponent sides of the float-exp
+    // threshold: a strip spans both without any per-depth setup.
+    let max_iterations = 1200;
+    for outer_radius_log2 in [-140.0, -290.0] {
+        let frame = ExponentialMapFrame::new(
+            DEEP_RE,
+            DEEP_IM,
+            outer_radius_log2,
+            16,
+            4,
+            max_iterations,
+            2,
+            3.0,
+        )
+        .unwrap();
+
+        let precision_bits = 384;
+        let origin_re = parse_decimal(DEEP_RE, precision_bits).unwrap();
+        let origin_im = parse_decimal(DEEP_IM, precision_bits).unwrap();
+        let to_big = |mantissa: f64, exp: i64| {
+            BigFloat::try_from(mantissa)
+                .unwrap()
+                .with_precision(precision_bits)
+                .value()
+                * BigFloat::from_parts(IBig::from(1), exp as isize)
+                    .with_precision(precision_bits)
+                    .value()
+        };
+
+        let samples = [(0, 0), (5, 1), (9, 2), (15, 3)];
+        let mut exact_matches = 0;
+        for &(column, row) in &samples {
+            let dc = frame.pixel_dc(column, row);
+            let c_re = &origin_re + to_big(dc.re, dc.exp);
+            let c_im = &origin_im + to_big(dc.im, dc.exp);
+
+            let direct_iterations = direct_escape_iterations_big(&c_re, &c_im, max_iterations);
+            let (perturbed_iterations, _) = frame.escape_iterations(column, row);
+            assert!(
+                direct_iterations.abs_diff(perturbed_iterations) <= 5,
+                "radius 2^{outer_radius_log2}, pixel ({column}, {row}): \
+                 direct {direct_iterations} vs perturbed {perturbed_iterations}"
+            );
+            if direct_iterations == perturbed_iterations {
+                exact_matches += 1;
+            }
+        }
+        assert!(exact_matches >= samples.len() - 1);
+    }
+}
+
+#[test]
+fn exponential_map_rejects_unsupported_exponents() {
+    for exponent in [1, MAX_PERTURBED_EXPONENT + 1] {
+        assert!(
+            ExponentialMapFrame::new(SHALLOW_RE, SHALLOW_IM, 0.0, 8, 8, 100, exponent, 3.0)
+                .is_err()
+        );
+    }
+}
+
+/// A 32x32 frame of the tile containing a published coordinate, as
+/// `published_coordinate_tile` renders it.
+fn published_coordinate_frame(
+    origin_re: &str,
+    origin_im: &str,
+    zoom: i32,
+    max_iterations: u32,
+) -> PerturbedFrame {
+    let zoom_offset = (zoom - 12).max(0) as u32;
+    let tile_zoom = zoom - zoom_offset as i32;
+    let v = (0.64 * f64::powi(2.0, tile_zoom)).floor();
+    PerturbedFrame::new(
+        origin_re,
+        origin_im,
+        v,
+        v + 1.0,
+        v,
+        v + 1.0,
+        tile_zoom,
+        zoom_offset,
+        ViewTransform::IDENTITY,
+        32,
+        32,
+        max_iterations,
+        2,
+        3.0,
+    )
+    .unwrap()
+}
+
+#[test]
+fn float_exp_subdivision_matches_per_pixel_results() {
+    let all_pixels: Vec<usize> = (0..32 * 32).collect();
+
+    // Escaping structure: the subdivision computes every pixel.
+    let frame = published_coordinate_frame(M41_RE, M41_IM, M41_ZOOM, 5_000);
+    assert!(frame.uses_float_exp());
+    assert_eq!(
+        frame.compute_all(32, 32),
+        frame.compute_pixels(&all_pixels, 32)
+    );
+
+    // A minibrot interior: the border ring fills the rest as interior.
+    let frame = published_coordinate_frame(
+        HARDEST_2017_E99_RE,
+        HARDEST_2017_E99_IM,
+        HARDEST_2017_E99_ZOOM,
+        2_000,
+    );
+    assert!(frame.uses_float_exp());
+    assert!(frame
+        .compute_all(32, 32)
+        .iter()
+        .all(|&(iterations, _)| iterations == 2_000));
+}
+
+#[test]
+fn row_bands_match_per_pixel_results() {
+    // Tall enough to split into several row bands under the `parallel`
+    // feature; each band subdivides on its own and must see the tile's
+    // pixel numbering.
+    let frame = make_frame(
+        "-0.7436438870371587",
+        "0.1318259042053119",
+        12,
+        40,
+        80,
+        2_000,
+    );
+    let all_pixels: Vec<usize> = (0..80 * 80).collect();
+    // Filled interior pixels carry no final `z`, so compare iterations.
+    let iterations =
+        |results: Vec<(u32, Complex64)>| -> Vec<u32> { results.into_iter().map(|r| r.0).collect() };
+    assert_eq!(
+        iterations(frame.compute_all(80, 80)),
+        iterations(frame.compute_pixels(&all_pixels, 80))
+    );
+}
+
+fn orbit_entry(buffer: &[u8]) -> OrbitCacheEntry {
+    OrbitCacheEntry::from_bytes(buffer).unwrap()
+}
+
+#[test]
+fn orbit_buffers_round_trip() {
+    // A cubic orbit carries a Horner-term table; a quadratic one does not.
+    for exponent in [2, 3] {
+        let buffer = reference_orbit_buffer("0.1", "0.1", 60, 500, exponent).unwrap();
+        let entry = orbit_entry(&buffer);
+        assert_eq!(entry.key.origin_re, "0.1");
+        assert!(!entry.orbit.escaped);
+        assert_eq!(entry.key.exponent, exponent);
+        assert_eq!(entry.precision_bits, 128);
+        assert_eq!(entry.orbit.values.len(), 502);
+        assert_eq!(entry.orbit.coeff_table.is_empty(), exponent == 2);
+        assert_eq!(entry.to_bytes(), buffer);
+    }
+    assert!(reference_orbit_buffer("0", "0", 60, 500, 1).is_err());
+}
+
+#[test]
+fn malformed_orbit_buffers_are_rejected() {
+    let buffer = reference_orbit_buffer("-2", "0", 60, 100, 3).unwrap();
+    assert!(install_reference_orbit(&buffer[..buffer.len() - 1]).is_err());
+    assert!(install_reference_orbit(&[buffer.as_slice(), &[0]].concat()).is_err());
+    assert!(install_reference_orbit(b"MBRO").is_err());
+
+    // A term table that no longer matches the orbit would be read out of
+    // bounds by the kernels.
+    let mut entry = orbit_entry(&buffer);
+    Arc::get_mut(&mut entry.orbit).unwrap().coeff_table.pop();
+    assert!(install_reference_orbit(&entry.to_bytes()).is_err());
+}
+
+// The `parallel` build shares one cache with every concurrently running
+// test, which may replace the installed entry before it is read back.
+#[cfg(not(feature = "parallel"))]
+#[test]
+fn installed_orbits_are_reused() {
+    let buffer = reference_orbit_buffer("-1.25", "0.01", 80, 300, 2).unwrap();
+    // Computing the buffer cached the orbit, which an install keeps.
+    clear_orbit_cache();
+    // Mark the orbit so a recomputation would show.
+    let mut entry = orbit_entry(&buffer);
+    Arc::get_mut(&mut entry.orbit).unwrap().values[1] = (42.0, 0.0);
+    install_reference_orbit(&entry.to_bytes()).unwrap();
+
+    let orbit = reference_orbit_for_zoom("-1.25", "0.01", 80, 300, 2, None, 9.0).unwrap();
+    assert_eq!(orbit.values[1], (42.0, 0.0));
+    // Deeper views need more precision than the buffer claims.
+    let orbit = reference_orbit_for_zoom("-1.25", "0.01", 200, 300, 2, None, 9.0).unwrap();
+    assert_ne!(orbit.values[1], (42.0, 0.0));
+}
+
+#[cfg(not(feature = "parallel"))]
+#[test]
+fn installs_keep_cached_orbits_that_serve_as_well() {
+    // Buffers with `Z_1` marked as `mark`, so the installed orbit shows.
+    let marked = |max_iterations, mark| {
+        let buffer = reference_orbit_buffer("-1.25", "0.01", 80, max_iterations, 2).unwrap();
+        let mut entry = orbit_entry(&buffer);
+        Arc::get_mut(&mut entry.orbit).unwrap().values[1] = (mark, 0.0);
+        entry.to_bytes()
+    };
+    let (short, long) = (marked(300, 7.0), marked(600, 42.0));
+    let cached = |max_iterations| {
+        reference_orbit_for_zoom("-1.25", "0.01", 80, max_iterations, 2, None, 9.0).unwrap()
+    };
+
+    // A computed orbit holds a tail, so it stays.
+    clear_orbit_cache();
+    cached(300);
+    install_reference_orbit(&long).unwrap();
+    assert_ne!(cached(300).values[1], (42.0, 0.0));
+
+    // Without a tail, an import no longer than the cached orbit is ignored,
+    // and a longer one replaces it.
+    clear_orbit_cache();
+    install_reference_orbit(&long).unwrap();
+    install_reference_orbit(&short).unwrap();
+    assert_eq!(cached(600).values[1], (42.0, 0.0));
+    clear_orbit_cache();
+    install_reference_orbit(&short).unwrap();
+    assert_eq!(cached(300).values[1], (7.0, 0.0));
+    install_reference_orbit(&long).unwrap();
+    assert_eq!(cached(600).values[1], (42.0, 0.0));
+    assert_eq!(orbit_cache_stats().misses, 0);
+    clear_orbit_cache();
+}
+
+/// A cache entry for origin `origin_re` whose orbit holds `length` values.
+fn sized_entry(origin_re: &str, length: usize) -> OrbitCacheEntry {
+    OrbitCacheEntry {
+        key: OrbitCacheKey {
+            origin_re: origin_re.to_string(),
+            origin_im: "0".to_string(),
+            exponent: 2,
+            formula: None,
+            escape_radius_squared: 9.0,
+        },
+        precision_bits: 128,
+        orbit: Arc::new(ReferenceOrbit {
+            values: vec![(0.0, 0.0); length],
+            escaped: false,
+            coeff_table: Vec::new(),
+        }),
+        tail: None,
+    }
+}
+
+#[test]
+fn orbit_cache_evicts_least_recently_used_views() {
+    let mut cache = OrbitCache::new();
+    // Three 1,600-byte orbits (plus their 3-byte keys) in a budget for two.
+    cache.budget_bytes = 2 * 1_603;
+    cache.insert(sized_entry("-1", 100));
+    cache.insert(sized_entry("-2", 100));
+    let key = |origin_re: &str| sized_entry(origin_re, 0).key;
+
+    // Touching the older view makes the newer one least recently used.
+    assert!(cache.get(&key("-1"), 128, 50).is_some());
+    cache.insert(sized_entry("-3", 100));
+    assert!(cache.get(&key("-2"), 128, 50).is_none());
+    assert!(cache.get(&key("-1"), 128, 50).is_some());
+    assert!(cache.get(&key("-3"), 128, 50).is_some());
+    assert_eq!(cache.size_bytes(), 2 * 1_603);
+
+    // Entries serve only renders within their precision and length.
+    assert!(cache.get(&key("-3"), 160, 50).is_none());
+    assert!(cache.get(&key("-3"), 128, 100).is_none());
+
+    // A recomputed view replaces its entry; an orbit past the whole budget
+    // still stays cached on its own.
+    cache.insert(sized_entry("-3", 1_000));
+    assert_eq!(cache.entries.len(), 1);
+    assert!(cache.get(&key("-3"), 128, 500).is_some());
+}
+
+#[test]
+fn orbit_cache_sizes_count_the_tail_and_formula() {
+    // A point inside the main cardioid: its orbit never escapes, so the
+    // entry keeps a tail to extend from.
+    let (center_re, center_im) = (
+        parse_decimal("-0.1", 128).unwrap(),
+        parse_decimal("0.1", 128).unwrap(),
+    );
+    let (orbit, tail) = compute_reference_orbit(&center_re, &center_im, 2, None, 100, 9.0);
+    let mut entry = sized_entry("-0.1", 0);
+    entry.orbit = Arc::new(orbit);
+    let orbit_bytes = entry.size_bytes();
+    entry.tail = tail;
+    let tail_bytes = entry.size_bytes() - orbit_bytes;
+    assert!(
+        tail_bytes >= 4 * std::mem::size_of::<BigFloat>(),
+        "{tail_bytes}"
+    );
+
+    let cubic = crate::PolynomialTerm {
+        power: 3,
+        coefficient_re: 1.0,
+        coefficient_im: 0.0,
+    };
+    let tail_and_orbit_bytes = entry.size_bytes();
+    entry.key.formula = Some(Polynomial::new(&[cubic]).unwrap());
+    assert!(entry.size_bytes() > tail_and_orbit_bytes);
+}
+
+// As above, the `parallel` build's cache is shared with concurrent tests.
+#[cfg(not(feature = "parallel"))]
+#[test]
+fn orbit_cache_stats_count_hits_and_misses() {
+    clear_orbit_cache();
+    let orbit = || reference_orbit_for_zoom("-0.75", "0.1", 60, 200, 2, None, 9.0).unwrap();
+    orbit();
+    orbit();
+    reference_orbit_for_zoom("-0.75", "0.1", 60, 200, 3, None, 9.0).unwrap();
+    let stats = orbit_cache_stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 2));
+    assert!(stats.size_bytes > 0);
+    assert_eq!(
+        stats.budget_bytes as usize,
+        DEFAULT_ORBIT_CACHE_BUDGET_BYTES
+    );
+
+    // A zero budget keeps only the most recent orbit.
+    set_orbit_cache_budget(0);
+    assert_eq!(orbit_cache_stats().entries, 1);
+    set_orbit_cache_budget(DEFAULT_ORBIT_CACHE_BUDGET_BYTES);
+
+    clear_orbit_cache();
+    let stats = orbit_cache_stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (0, 0, 0));
+}
+
+// Threads of the `parallel` build that need the same uncached orbit at once
+// compute it once: the rest wait for the first and share its `Arc`.
+#[cfg(feature = "parallel")]
+#[test]
+fn concurrent_renders_share_one_orbit_computation() {
+    let orbits: Vec<Arc<ReferenceOrbit>> = std::thread::scope(|scope| {
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                scope.spawn(|| {
+                    reference_orbit_for_zoom("-1.7548", "0.0123", 300, 20_000, 2, None, 9.0)
+                        .unwrap()
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .map(|thread| thread.join().unwrap())
+            .collect()
+    });
+    assert!(orbits.iter().all(|orbit| Arc::ptr_eq(orbit, &orbits[0])));
+}
+
+#[test]
+fn extended_orbits_match_fresh_computations() {
+    // Points whose orbits stay bounded past the short orbit's length.
+    for (exponent, origin_re, origin_im) in [
+        (2, "-0.7436438870371587", "0.1318259042053119"),
+        (3, "0.1", "0.3"),
+    ] {
+        let center_re = parse_decimal(origin_re, 128).unwrap();
+        let center_im = parse_decimal(origin_im, 128).unwrap();
+        let (mut extended, tail) =
+            compute_reference_orbit(&center_re, &center_im, exponent, None, 100, 9.0);
+        let extended_tail =
+            extend_reference_orbit(&mut extended, tail.unwrap(), exponent, None, 400, 9.0);
+        let (fresh, fresh_tail) =
+            compute_reference_orbit(&center_re, &center_im, exponent, None, 400, 9.0);
+        assert_eq!(extended.values, fresh.values);
+        assert_eq!(extended.coeff_table, fresh.coeff_table);
+        assert_eq!(extended.escaped, fresh.escaped);
+        assert_eq!(extended_tail.is_some(), fresh_tail.is_some());
+    }
+
+    // Formula orbits append only the new rows of their coefficient table.
+    let formula = cubic_formula();
+    let center_re = parse_decimal(CUBIC_FORMULA_RE, 420).unwrap();
+    let center_im = parse_decimal(CUBIC_FORMULA_IM, 420).unwrap();
+    let (mut extended, tail) =
+        compute_reference_orbit(&center_re, &center_im, 3, Some(&formula), 100, 9.0);
+    extend_reference_orbit(&mut extended, tail.unwrap(), 3, Some(&formula), 400, 9.0);
+    let (fresh, _) = compute_reference_orbit(&center_re, &center_im, 3, Some(&formula), 400, 9.0);
+    assert_eq!(extended.values, fresh.values);
+    assert_eq!(extended.coeff_table, fresh.coeff_table);
+    assert_eq!(extended.coeff_table.len(), 3 * 401);
+}
+
+// As above, the `parallel` build's cache is shared with concurrent tests.
+#[cfg(not(feature = "parallel"))]
+#[test]
+fn raising_the_iteration_budget_extends_the_cached_orbit() {
+    clear_orbit_cache();
+    let orbit = |max_iterations| {
+        reference_orbit_for_zoom("-1.25", "0.02", 70, max_iterations, 2, None, 9.0).unwrap()
+    };
+    orbit(200);
+    let extended = orbit(600);
+    let stats = orbit_cache_stats();
+    assert_eq!(
+        (stats.hits, stats.misses, stats.extensions, stats.entries),
+        (0, 1, 1, 1)
+    );
+    assert_eq!(extended.values.len(), 602);
+
+    // The extension replaced the short orbit and now serves both budgets.
+    assert!(Arc::ptr_eq(&orbit(200), &extended));
+    assert_eq!(orbit_cache_stats().hits, 1);
+
+    clear_orbit_cache();
+    assert_eq!(orbit(600).values, extended.values);
+    clear_orbit_cache();
+}
+
+/// `z^3 + (0.5 - 0.25i) z^2 + c`.
+fn cubic_formula() -> Polynomial {
+    Polynomial::new(&[
+        crate::PolynomialTerm {
+            power: 3,
+            coefficient_re: 1.0,
+            coefficient_im: 0.0,
+        },
+        crate::PolynomialTerm {
+            power: 2,
+            coefficient_re: 0.5,
+            coefficient_im: -0.25,
+        },
+    ])
+    .unwrap()
+}
+
+/// A point on the boundary of the `cubic_formula` set, bisected in 420-bit
+/// arithmetic along a ray from the interior point 0.
+const CUBIC_FORMULA_RE: &str = "-0.5576598585106857270087863480612931978524698081521581840672059868455331503898835165753542822505336782";
+const CUBIC_FORMULA_IM: &str = "0.7435464780142477048035834449450992577255491828447602179437309044517591669716092207197773971025760849";
+
+fn cubic_formula_frame(tile_zoom: i32, zoom_offset: u32, image_size: usize) -> PerturbedFrame {
+    let center = centered_tile_coordinate(tile_zoom).floor();
+    PerturbedFrame::new_polynomial(
+        CUBIC_FORMULA_RE,
+        CUBIC_FORMULA_IM,
+        center,
+        center + 1.0,
+        center,
+        center + 1.0,
+        tile_zoom,
+        zoom_offset,
+        ViewTransform::IDENTITY,
+        image_size,
+        image_size,
+        1200,
+        &cubic_formula(),
+    )
+    .unwrap()
+}
+
+/// `direct_escape_iterations_big` for a formula.
+fn formula_escape_iterations_big(
+    formula: &Polynomial,
+    c: &(BigFloat, BigFloat),
+    max_iterations: u32,
+) -> u32 {
+    let precision_bits = c.0.precision();
+    let big = |value: f64| {
+        BigFloat::try_from(value)
+            .unwrap()
+            .with_precision(precision_bits)
+            .value()
+    };
+    let escape_radius_squared = formula.escape_radius().powi(2);
+    let mut z = c.clone();
+    let mut iterations = 0;
+
+    loop {
+        let re = z.0.to_f64().value();
+        let im = z.1.to_f64().value();
+        if re * re + im * im >= escape_radius_squared || iterations >= max_iterations {
+            return iterations;
+        }
+        z = formula
+            .terms()
+            .iter()
+            .fold(c.clone(), |sum, &(power, coefficient)| {
+                let term = complex_big_mul(
+                    &(big(coefficient.re), big(coefficient.im)),
+                    &complex_big_pow(&z, power),
+                );
+                (sum.0 + term.0, sum.1 + term.1)
+            });
+        iterations += 1;
+    }
+}
+
+#[test]
+fn formula_frames_match_full_precision_direct() {
+    // One depth on the f64-delta path and one on the extended-exponent path.
+    for (tile_zoom, zoom_offset) in [(10, 140), (10, 290)] {
+        let image_size = 16;
+        let frame = cubic_formula_frame(tile_zoom, zoom_offset, image_size);
+        assert_eq!(frame.uses_float_exp(), zoom_offset == 290);
+
+        let effective_zoom = tile_zoom as i64 + zoom_offset as i64;
+        let precision_bits = (effective_zoom as usize + 64).div_ceil(32) * 32;
+        let origin_re = parse_decimal(CUBIC_FORMULA_RE, precision_bits).unwrap();
+        let origin_im = parse_decimal(CUBIC_FORMULA_IM, precision_bits).unwrap();
+        let center = centered_tile_coordinate(tile_zoom).floor();
+        let scale = BigFloat::from_parts(IBig::from(1), -(zoom_offset as isize))
+            .with_precision(precision_bits)
+            .value();
+        let offset = |coordinate: f64| {
+            BigFloat::try_from(tile_coordinate_offset(coordinate, tile_zoom))
+                .unwrap()
+                .with_precision(precision_bits)
+                .value()
+        };
+
+        let mut escaped = 0;
+        for (column, row) in [
+            (0, 0),
+            (image_size - 1, 0),
+            (0, image_size - 1),
+            (7, 9),
+            (12, 3),
+        ] {
+            let x = center + column as f64 / (image_size - 1) as f64;
+            let y = center + row as f64 / (image_size - 1) as f64;
+            let c = (
+                &origin_re + offset(x) * &scale,
+                &origin_im - offset(y) * &scale,
+            );
+
+            let direct_iterations = formula_escape_iterations_big(&cubic_formula(), &c, 1200);
+            let (perturbed_iterations, _) = frame.escape_iterations(column, row);
+            assert_eq!(
+                direct_iterations, perturbed_iterations,
+                "zoom offset {zoom_offset}, pixel ({column}, {row})"
+            );
+            if perturbed_iterations < 1200 {
+                escaped += 1;
+            }
+        }
+        assert!(escaped > 0, "zoom offset {zoom_offset}: no sample escaped");
+    }
+}
+
+#[test]
+fn formula_table_rows_match_rows_computed_on_the_spot() {
+    let frame = cubic_formula_frame(10, 60, 8);
+    let formula = cubic_formula();
+    let tabled = FormulaStep::new(&formula, &frame.orbit);
+    assert_eq!(tabled.table.len(), frame.orbit.values.len() * 3);
+    let on_the_spot = FormulaStep {
+        table: &[],
+        ..FormulaStep::new(&formula, &frame.orbit)
+    };
+
+    for pixel in 0..64 {
+        let dc = frame.pixel_dc_f64(pixel % 8, pixel / 8);
+        let escape = |step: &FormulaStep| {
+            perturbed_escape_iterations_f64_by(
+                &frame.orbit.values,
+                dc,
+                1200,
+                frame.escape_radius_squared,
+                |index, z_ref, dz| step.delta_f64(index, z_ref, dz),
+            )
+        };
+        assert_eq!(escape(&tabled), escape(&on_the_spot), "pixel {pixel}");
+    }
+}
+
+#[test]
+fn formula_orbits_are_cached_apart_from_power_orbits() {
+    let quadratic = Polynomial::new(&[crate::PolynomialTerm {
+        power: 2,
+        coefficient_re: 1.0,
+        coefficient_im: 0.0,
+    }])
+    .unwrap();
+    let power = reference_orbit_for_zoom("-0.75", "0.1", 60, 200, 2, None, 9.0).unwrap();
+    let formula =
+        reference_orbit_for_zoom("-0.75", "0.1", 60, 200, 2, Some(&quadratic), 9.0).unwrap();
+
+    // Same iteration, different delta-step tables.
+    assert!(!Arc::ptr_eq(&power, &formula));
+    assert_eq!(power.values, formula.values);
+    assert!(power.coeff_table.is_empty());
+    assert_eq!(formula.coeff_table.len(), formula.values.len() * 2);
+}
@@ -0,0 +1,198 @@
+//! Polynomial formulas: iterating `z -> a_1 z^n_1 + ... + a_k z^n_k + c`
+//! (e.g. `z^3 + a z^2 + c`) instead of `z^power + c`.
+//!
+//! Unlike a free-form expression, a polynomial's perturbation step has a
+//! closed form: `f(Z + dz) - f(Z)` expands binomially, term by term, into
+//! `b_1 dz + ... + b_d dz^d` with `b_j = sum of a C(n, j) Z^(n - j)` over the
+//! terms `a z^n`. The `b_j` depend only on the reference value `Z`, so the
+//! reference orbit precomputes them for every orbit index (as it does the
+//! Horner terms of `z^power + c`) and each pixel step is one Horner
+//! evaluation in `dz`, in plain f64 or extended-exponent deltas alike:
+//! formula tiles zoom as deep as the power family, through `PerturbedFrame`.
+//!
+//! Iteration starts from `z = 0` as for the Mandelbrot set, so powers start
+//! at 1: a constant term would only shift `c`.
+
+use num::complex::Complex64;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use wasm_bindgen::prelude::*;
+
+use crate::float_exp::ComplexExp;
+use crate::perturbation::MAX_PERTURBED_EXPONENT;
+use crate::{iterate_to_escape, ESCAPE_RADIUS};
+
+#[cfg(test)]
+#[path = "polynomial_test.rs"]
+mod polynomial_test;
+
+/// One monomial `coefficient * z^power` of a formula (see
+/// `TileRenderOptions::formula`). Field names mirror the client's camelCase
+/// payload.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PolynomialTerm {
+    /// From 1 to `MAX_PERTURBED_EXPONENT`.
+    pub power: u32,
+    /// Defaults to 1, so `{ power: 3 }` is `z^3`.
+    #[serde(default = "PolynomialTerm::unit_coefficient")]
+    pub coefficient_re: f64,
+    #[serde(default)]
+    pub coefficient_im: f64,
+}
+
+impl PolynomialTerm {
+    fn unit_coefficient() -> f64 {
+        1.0
+    }
+}
+
+/// A validated formula: its terms' distinct powers in descending order, each
+/// with a nonzero coefficient, led by a power of at least 2.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Polynomial {
+    terms: Vec<(u32, Complex64)>,
+}
+
+impl Polynomial {
+    /// The formula summing `terms`; terms of the same power add up.
+    pub(crate) fn new(terms: &[PolynomialTerm]) -> Result<Polynomial, String> {
+        let mut merged: Vec<(u32, Complex64)> = Vec::new();
+        for term in terms {
+            if !(1..=MAX_PERTURBED_EXPONENT).contains(&term.power) {
+                return Err(format!(
+                    "term power {} is outside 1..={MAX_PERTURBED_EXPONENT}",
+                    term.power
+                ));
+            }
+            if !term.coefficient_re.is_finite() || !term.coefficient_im.is_finite() {
+                return Err(format!("the z^{} coefficient is not finite", term.power));
+            }
+            let coefficient = Complex64::new(term.coefficient_re, term.coefficient_im);
+            match merged.iter_mut().find(|(power, _)| *power == term.power) {
+                Some((_, sum)) => *sum += coefficient,
+                None => merged.push((term.power, coefficient)),
+            }
+        }
+        merged.retain(|&(_, coefficient)| coefficient != Complex64::new(0.0, 0.0));
+        merged.sort_by_key(|&(power, _)| std::cmp::Reverse(power));
+
+        match merged.first() {
+            Some(&(degree, _)) if degree >= 2 => Ok(Polynomial { terms: merged }),
+            _ => Err("a formula needs a term of power 2 or more".to_string()),
+        }
+    }
+
+    /// `(power, coefficient)` of each term, highest power first.
+    pub(crate) fn terms(&self) -> &[(u32, Complex64)] {
+        &self.terms
+    }
+
+    /// The highest power: the exponent smoothing and the perturbation
+    /// cutoffs treat the formula by.
+    pub(crate) fn degree(&self) -> u32 {
+        self.terms[0].0
+    }
+
+    /// A radius past which every orbit escapes. With `A` the leading
+    /// coefficient's modulus and `S` the sum of the others', `|f(z)| >= 3|z|`
+    /// once `|z| >= (S + 3) / A` (and `|z| >= 1`), so an orbit past that
+    /// radius and past `|c|` at least doubles each step. `z^2 + c` keeps the
+    /// usual `ESCAPE_RADIUS`.
+    pub(crate) fn escape_radius(&self) -> f64 {
+        let leading = self.terms[0].1.norm();
+        let others: f64 = self.terms[1..]
+            .iter()
+            .map(|(_, coefficient)| coefficient.norm())
+            .sum();
+        ESCAPE_RADIUS.max((others + 3.0) / leading)
+    }
+
+    /// `f(z) + c` in f64.
+    fn step(&self, z: Complex64, c: Complex64) -> Complex64 {
+        self.terms.iter().fold(c, |sum, &(power, coefficient)| {
+            sum + coefficient * z.powu(power)
+        })
+    }
+
+    /// Escape iterations and final value at `c`, directly in f64, through
+    /// the loop (and periodicity check) of the `z^power + c` kernels.
+    pub(crate) fn escape_iterations(&self, c: Complex64, max_iterations: u32) -> (u32, Complex64) {
+        let escape_radius_squared = self.escape_radius().powi(2);
+        iterate_to_escape(c, max_iterations, escape_radius_squared, |z| {
+            self.step(z, c)
+        })
+    }
+
+    /// Fills `row` (`degree` entries) with the delta-step coefficients
+    /// `b_1..b_d` at the reference value `z_ref` (see the module docs), as
+    /// `[re, im]` pairs.
+    pub(crate) fn delta_coefficients_into(&self, z_ref: Complex64, row: &mut [[f64; 2]]) {
+        const MAX_DEGREE: usize = MAX_PERTURBED_EXPONENT as usize;
+        let degree = self.degree() as usize;
+
+        // Z^0 through Z^(d-1).
+        let mut z_powers = [Complex64::new(1.0, 0.0); MAX_DEGREE];
+        for index in 1..degree {
+            z_powers[index] = z_powers[index - 1] * z_ref;
+        }
+
+        let mut sums = [Complex64::new(0.0, 0.0); MAX_DEGREE];
+        for &(power, coefficient) in &self.terms {
+            let power = power as usize;
+            let mut binomial = 1.0_f64;
+            for j in 1..=power {
+                binomial = binomial * (power - j + 1) as f64 / j as f64;
+                sums[j - 1] += coefficient * binomial * z_powers[power - j];
+            }
+        }
+
+        for (entry, sum) in row.iter_mut().zip(&sums[..degree]) {
+            *entry = [sum.re, sum.im];
+        }
+    }
+}
+
+/// `f(Z + dz) - f(Z)` by Horner evaluation of the delta-step coefficients
+/// `row` at `Z` (see `Polynomial::delta_coefficients_into`).
+pub(crate) fn delta_step_f64(row: &[[f64; 2]], dz: Complex64) -> Complex64 {
+    row.iter()
+        .rev()
+        .fold(Complex64::new(0.0, 0.0), |sum, &[re, im]| {
+            (sum + Complex64::new(re, im)) * dz
+        })
+}
+
+/// Same as `delta_step_f64` but with extended-exponent deltas.
+pub(crate) fn delta_step_float_exp(row: &[[f64; 2]], dz: ComplexExp) -> ComplexExp {
+    row.iter().rev().fold(ComplexExp::ZERO, |sum, &[re, im]| {
+        sum.add(&ComplexExp::from_f64s(re, im)).mul(&dz)
+    })
+}
+
+/// Deserializes `TileRenderOptions::formula`: `None` for an empty list, and
+/// an error, with `Polynomial::new`'s message, for an invalid one, which a
+/// render would otherwise have to either fail on or silently replace.
+pub(crate) fn deserialize_formula<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Polynomial>, D::Error> {
+    let terms = Vec::<PolynomialTerm>::deserialize(deserializer)?;
+    if terms.is_empty() {
+        return Ok(None);
+    }
+    Polynomial::new(&terms)
+        .map(Some)
+        .map_err(|error| D::Error::custom(format!("invalid formula: {error}")))
+}
+
+/// Validates a formula payload (a list of `PolynomialTerm`s), returning the
+/// first problem as the error message so the client's formula editor can
+/// show it before a render rejects the payload.
+#[wasm_bindgen]
+pub fn validate_formula(formula: JsValue) -> Result<(), JsValue> {
+    let terms: Vec<PolynomialTerm> =
+        serde_wasm_bindgen::from_value(formula).map_err(JsValue::from)?;
+    Polynomial::new(&terms)
+        .map(|_| ())
+        .map_err(|error| JsValue::from_str(&error))
+}
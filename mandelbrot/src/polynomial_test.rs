@@ -0,0 +1,216 @@
+use super::*;
+use crate::test_fixtures::{self, FULL_SET_VIEW, SEAHORSE_ORIGIN};
+use crate::{RenderTier, TileRenderOptions};
+
+fn term(power: u32, coefficient_re: f64, coefficient_im: f64) -> PolynomialTerm {
+    PolynomialTerm {
+        power,
+        coefficient_re,
+        coefficient_im,
+    }
+}
+
+/// A tile of `view` at `zoom + zoom_offset` around `origin`, at 500
+/// iterations of `formula` (or `z^2 + c` when empty).
+fn tile(
+    origin: (&str, &str),
+    view: (f64, f64, f64, f64),
+    zoom: i32,
+    zoom_offset: u32,
+    formula: Vec<PolynomialTerm>,
+) -> TileRenderOptions {
+    TileRenderOptions {
+        formula: (!formula.is_empty()).then(|| Polynomial::new(&formula).unwrap()),
+        ..test_fixtures::tile(origin, view, zoom, zoom_offset, 500)
+    }
+}
+
+/// A view around the seahorse valley origin, at `2^-(12 + zoom_offset)`.
+fn seahorse_tile(zoom_offset: u32, formula: Vec<PolynomialTerm>) -> TileRenderOptions {
+    let center = (0.64 * f64::powi(2.0, 12)).floor();
+    tile(
+        SEAHORSE_ORIGIN,
+        (center, center + 1.0, center, center + 1.0),
+        12,
+        zoom_offset,
+        formula,
+    )
+}
+
+#[test]
+fn formulas_merge_terms_and_reject_invalid_ones() {
+    let formula = Polynomial::new(&[
+        term(2, 0.5, 0.0),
+        term(5, 1.0, 0.0),
+        term(2, 0.5, 1.0),
+        term(3, 1.0, 0.0),
+        term(3, -1.0, 0.0),
+    ])
+    .unwrap();
+    assert_eq!(
+        formula.terms(),
+        [(5, Complex64::new(1.0, 0.0)), (2, Complex64::new(1.0, 1.0))]
+    );
+    assert_eq!(formula.degree(), 5);
+
+    for terms in [
+        vec![],
+        vec![term(1, 1.0, 0.0)],
+        vec![term(2, 1.0, 0.0), term(2, -1.0, 0.0)],
+        vec![term(0, 1.0, 0.0), term(2, 1.0, 0.0)],
+        vec![term(MAX_PERTURBED_EXPONENT + 1, 1.0, 0.0)],
+        vec![term(2, f64::NAN, 0.0)],
+    ] {
+        assert!(Polynomial::new(&terms).is_err(), "{terms:?}");
+    }
+}
+
+#[test]
+fn escape_radius_covers_the_lower_terms() {
+    let radius = |terms: &[PolynomialTerm]| Polynomial::new(terms).unwrap().escape_radius();
+    assert_eq!(radius(&[term(2, 1.0, 0.0)]), ESCAPE_RADIUS);
+    assert_eq!(radius(&[term(3, 1.0, 0.0), term(2, 0.0, 4.0)]), 7.0);
+    assert_eq!(radius(&[term(3, 0.5, 0.0), term(1, 1.0, 0.0)]), 8.0);
+
+    // Past the radius (and `|c|`), orbits keep growing.
+    let formula = Polynomial::new(&[term(3, 0.5, 0.0), term(1, 1.0, 0.0)]).unwrap();
+    let c = Complex64::new(-2.0, 1.0);
+    let mut z = Complex64::new(0.0, -8.0);
+    for _ in 0..5 {
+        let next = formula.step(z, c);
+        assert!(next.norm() >= 2.0 * z.norm());
+        z = next;
+    }
+}
+
+#[test]
+fn delta_steps_expand_the_difference() {
+    let formula =
+        Polynomial::new(&[term(4, 1.0, 0.0), term(3, 0.5, -0.25), term(1, 0.0, 0.3)]).unwrap();
+    let z_ref = Complex64::new(-0.4, 0.7);
+    let dz = Complex64::new(0.03, -0.02);
+    let mut row = [[0.0; 2]; 4];
+    formula.delta_coefficients_into(z_ref, &mut row);
+
+    let zero = Complex64::new(0.0, 0.0);
+    let expected = formula.step(z_ref + dz, zero) - formula.step(z_ref, zero);
+    assert!((delta_step_f64(&row, dz) - expected).norm() < 1e-14);
+
+    let (re, im) = delta_step_float_exp(&row, ComplexExp::from_f64s(dz.re, dz.im)).to_f64s();
+    assert!((Complex64::new(re, im) - expected).norm() < 1e-14);
+}
+
+#[test]
+fn direct_iteration_matches_the_power_family() {
+    let cubic = Polynomial::new(&[term(3, 1.0, 0.0)]).unwrap();
+    for (re, im) in [(0.1, 0.3), (-0.5, 0.6), (0.4, 0.5), (1.5, -1.0), (0.0, 0.0)] {
+        assert_eq!(
+            cubic.escape_iterations(Complex64::new(re, im), 300),
+            crate::calculate_escape_iterations(re, im, 300, 3),
+            "c = {re} + {im}i"
+        );
+    }
+}
+
+#[test]
+fn single_term_formula_tiles_match_power_tiles() {
+    let quadratic = || vec![term(2, 1.0, 0.0)];
+    // Shallow, deep and float-exp-deep tiles.
+    for (power_tile, formula_tile) in [
+        (
+            tile(("0", "0"), FULL_SET_VIEW, 2, 0, Vec::new()),
+            tile(("0", "0"), FULL_SET_VIEW, 2, 0, quadratic()),
+        ),
+        (
+            seahorse_tile(40, Vec::new()),
+            seahorse_tile(40, quadratic()),
+        ),
+        (
+            seahorse_tile(260, Vec::new()),
+            seahorse_tile(260, quadratic()),
+        ),
+    ] {
+        let (power, formula) = (power_tile.render_layered(), formula_tile.render_layered());
+        assert_eq!(formula.tier, power.tier);
+        assert_eq!(formula.values, power.values);
+    }
+}
+
+#[test]
+fn deep_formula_tiles_render_by_perturbation() {
+    let formula = || vec![term(3, 1.0, 0.0), term(2, 0.5, -0.25)];
+    let origin = (
+        "-0.5576598585106857270087863480612931978524698081521581840672059868455331503898835165753542822505336782",
+        "0.7435464780142477048035834449450992577255491828447602179437309044517591669716092207197773971025760849",
+    );
+    let center = (0.64 * f64::powi(2.0, 10)).floor();
+    let view = (center, center + 1.0, center, center + 1.0);
+
+    for (zoom_offset, tier) in [(140, RenderTier::Perturbation), (290, RenderTier::FloatExp)] {
+        let mut options = tile(origin, view, 10, zoom_offset, formula());
+        options.max_iterations = 1200;
+        let rendered = options.render_layered();
+        assert_eq!(rendered.tier, tier as u8);
+        // The boundary runs through the tile: some pixels escape, at
+        // varying counts.
+        let escaped: Vec<f32> = rendered
+            .values
+            .iter()
+            .copied()
+            .filter(|value| value.is_finite())
+            .collect();
+        assert!(!escaped.is_empty());
+        assert!(escaped.iter().any(|&value| value != escaped[0]));
+    }
+}
+
+#[test]
+fn invalid_formulas_are_rejected_as_they_deserialize() {
+    use serde::de::value::{Error, MapDeserializer, SeqDeserializer};
+
+    // A payload of `{ power }` terms, coefficients left at their defaults.
+    let deserialize = |powers: &[u32]| {
+        let terms = powers
+            .iter()
+            .map(|&power| MapDeserializer::<_, Error>::new([("power", power)].into_iter()));
+        deserialize_formula(SeqDeserializer::<_, Error>::new(terms))
+    };
+    assert_eq!(deserialize(&[]).unwrap(), None);
+    assert_eq!(
+        deserialize(&[3, 2]).unwrap(),
+        Some(Polynomial::new(&[term(3, 1.0, 0.0), term(2, 1.0, 0.0)]).unwrap())
+    );
+
+    // Rejected with the validation message, not rendered as `z^power + c`.
+    let error = deserialize(&[1]).unwrap_err();
+    assert!(error.to_string().contains("power 2 or more"), "{error}");
+}
+
+#[test]
+fn formula_layers_and_channels_reuse_the_base_render() {
+    let mut options = tile(
+        ("0", "0"),
+        FULL_SET_VIEW,
+        2,
+        0,
+        vec![term(3, 1.0, 0.0), term(2, 0.5, 0.0)],
+    );
+    options.layers = vec![crate::layers::ColoringLayer {
+        coloring: crate::ColoringOptions {
+            distance_estimate: true,
+            ..Default::default()
+        },
+        blend_mode: Default::default(),
+        opacity: Some(0.5),
+    }];
+    options.channels = vec![crate::channels::ValueChannel::Iteration];
+    let rendered = options.render_layered();
+
+    // Formula values are escape values whatever the layer's method, so the
+    // layer recolors the base values, and the channel reads the base
+    // render's escape results.
+    assert_eq!(rendered.layer_values, rendered.values);
+    for (&iteration, &value) in rendered.channels.iter().zip(&rendered.values) {
+        assert_eq!(iteration.is_finite(), value.is_finite());
+    }
+}
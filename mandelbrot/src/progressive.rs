@@ -0,0 +1,707 @@
+//! Progressive (coarse-to-fine) tile rendering.
+//!
+//! A `ProgressiveTile` renders a tile in passes of halving stride: the first
+//! pass computes every `stride`-th pixel of every `stride`-th row, each later
+//! pass only the pixels the previous grids skipped, so the passes together
+//! compute each pixel exactly once. Every pass but the last returns a
+//! snapshot with each uncomputed pixel showing the nearest computed sample
+//! above and to its left (blocks of `stride x stride` pixels), colored by the
+//! base coloring alone; the last returns the finished tile with anti-aliasing,
+//! layers, channels and the requested output format, as `render_tile` would.
+
+use std::ops::Range;
+
+use itertools_num::linspace;
+use num::complex::Complex64;
+use wasm_bindgen::prelude::*;
+
+use crate::polynomial::Polynomial;
+use crate::{
+    antialias_tile, complete_tile_subdivided, direct_sample, escape_points, perturbation,
+    precise_color_fn, recolor_values_opaque, rect_in_set, smoothed_escape_value,
+    tile_pixel_spacing, transformed_border_in_set, uses_perturbation, ColoringOptions, DirectGrid,
+    MandelbrotTile, OutputFormat, RenderTier, RenderedTile, TileIterationStats, TileRenderOptions,
+    ESCAPE_RADIUS, UNCOMPUTED,
+};
+
+#[cfg(test)]
+#[path = "progressive_test.rs"]
+mod progressive_test;
+
+/// Stride of the first pass when the caller does not pick one.
+const DEFAULT_INITIAL_STRIDE: usize = 8;
+
+/// Computes the value of any pixel of one tile by the path its tier takes:
+/// what `render_tile_precise` renders whole tiles through, and progressive
+/// passes, render sessions and renders seeded from a parent tile render any
+/// subset of pixels through.
+pub(crate) struct PixelSampler {
+    pub(crate) method: SampleMethod,
+    pub(crate) image_width: usize,
+    pub(crate) image_height: usize,
+    pub(crate) max_iterations: u32,
+    pub(crate) power: u32,
+    pub(crate) smooth_coloring: bool,
+    /// The distance-estimate and atom-domain value methods (see
+    /// `ColoringOptions`), honored by `SampleMethod::Direct` only.
+    pub(crate) distance_estimate: bool,
+    pub(crate) atom_domain: bool,
+}
+
+/// How a `PixelSampler` computes its tile.
+pub(crate) enum SampleMethod {
+    /// A shallow tile, computed directly in f64.
+    Direct {
+        grid: DirectGrid,
+        pixel_spacing: f64,
+        /// The real parts of the pixel columns and imaginary parts of the
+        /// rows when the grid is untransformed: the axes the streaming and
+        /// subdivided kernels walk.
+        axes: Option<(Vec<f64>, Vec<f64>)>,
+    },
+    /// A shallow formula tile (see `TileRenderOptions::formula`), computed
+    /// directly in f64.
+    DirectFormula {
+        grid: DirectGrid,
+        formula: Polynomial,
+    },
+    /// A deep tile, computed by perturbation: of a formula too.
+    Perturbed(perturbation::PerturbedFrame),
+    /// A tile rendered as interior without computing it: a shallow one
+    /// whose border lies in the set (outside the atom-domain mode, which
+    /// colors the interior), or a deep one whose frame failed or whose
+    /// border lies in the set.
+    Interior(RenderTier),
+}
+
+impl PixelSampler {
+    /// The sampler of `options`' tile with its base coloring's value method.
+    pub(crate) fn new(options: &TileRenderOptions) -> PixelSampler {
+        PixelSampler::with_coloring(options, &options.coloring)
+    }
+
+    /// The sampler of `options`' tile with `coloring`'s value method (the
+    /// base coloring's or a layer's).
+    pub(crate) fn with_coloring(
+        options: &TileRenderOptions,
+        coloring: &ColoringOptions,
+    ) -> PixelSampler {
+        PixelSampler {
+            method: SampleMethod::new(options, coloring.atom_domain),
+            ..PixelSampler::interior_with_coloring(options, coloring)
+        }
+    }
+
+    /// A sampler for a tile already known to lie in the set, reporting the
+    /// tier `new` would have rendered it by.
+    pub(crate) fn interior(options: &TileRenderOptions) -> PixelSampler {
+        PixelSampler::interior_with_coloring(options, &options.coloring)
+    }
+
+    fn interior_with_coloring(
+        options: &TileRenderOptions,
+        coloring: &ColoringOptions,
+    ) -> PixelSampler {
+        PixelSampler {
+            method: SampleMethod::Interior(SampleMethod::planned_tier(options).0),
+            image_width: options.image_width,
+            image_height: options.image_height,
+            max_iterations: options.max_iterations,
+            power: options.power,
+            smooth_coloring: options.smooth_coloring,
+            distance_estimate: coloring.distance_estimate,
+            atom_domain: coloring.atom_domain,
+        }
+    }
+
+    pub(crate) fn tier(&self) -> RenderTier {
+        match &self.method {
+            SampleMethod::Direct { .. } | SampleMethod::DirectFormula { .. } => RenderTier::Direct,
+            SampleMethod::Perturbed(frame) if frame.uses_float_exp() => RenderTier::FloatExp,
+            SampleMethod::Perturbed(_) => RenderTier::Perturbation,
+            SampleMethod::Interior(tier) => *tier,
+        }
+    }
+
+    /// Whether the sampler's values are escape values of its escape results
+    /// (see `escape_value`): every tile but the direct distance-estimate
+    /// and atom-domain modes, whose values come from their own kernels and
+    /// map over a fixed palette window.
+    pub(crate) fn escape_valued(&self) -> bool {
+        !matches!(self.method, SampleMethod::Direct { .. })
+            || !(self.distance_estimate || self.atom_domain)
+    }
+
+    /// The smoothed escape value of an escape result.
+    pub(crate) fn escape_value(&self, (escape_iterations, z): (u32, Complex64)) -> f64 {
+        let exponent = match &self.method {
+            SampleMethod::DirectFormula { formula, .. } => formula.degree(),
+            SampleMethod::Perturbed(frame) => frame.exponent(),
+            _ => self.power,
+        };
+        smoothed_escape_value(
+            escape_iterations,
+            z,
+            self.max_iterations,
+            exponent,
+            self.smooth_coloring,
+        )
+    }
+
+    /// Escape results for the listed pixels (row-major indices), in list
+    /// order, through the batched kernels but without the Mariani–Silver
+    /// fill, which needs whole rectangles (see `complete`).
+    fn escape_results_of(&self, pixels: &[usize]) -> Vec<(u32, Complex64)> {
+        let width = self.image_width;
+        match &self.method {
+            SampleMethod::Perturbed(frame) => frame.compute_pixels(pixels, width),
+            SampleMethod::Direct {
+                axes: Some((re_values, im_values)),
+                ..
+            } => {
+                let points: Vec<(f64, f64)> = pixels
+                    .iter()
+                    .map(|&pixel| (re_values[pixel % width], im_values[pixel / width]))
+                    .collect();
+                let mut results = vec![(0, Complex64::new(0.0, 0.0)); pixels.len()];
+                escape_points(&points, self.max_iterations, self.power, &mut results);
+                results
+            }
+            _ => pixels
+                .iter()
+                .map(|&pixel| self.escape_at((pixel % width) as f64, (pixel / width) as f64))
+                .collect(),
+        }
+    }
+
+    /// Fills the `UNCOMPUTED` slots of `results`, the escape results of the
+    /// tile's rows from `first_row` on, by Mariani–Silver subdivision over
+    /// row bands where the method subdivides. Slots already filled are
+    /// kept, and the ring tests read them like computed ones.
+    fn complete(&self, first_row: usize, results: &mut [(u32, Complex64)]) {
+        let width = self.image_width;
+        match &self.method {
+            SampleMethod::Direct {
+                axes: Some((re_values, im_values)),
+                ..
+            } => complete_tile_subdivided(
+                re_values,
+                &im_values[first_row..first_row + results.len() / width.max(1)],
+                self.max_iterations,
+                self.power,
+                results,
+            ),
+            SampleMethod::Perturbed(frame) => frame.complete(width, first_row, results),
+            _ => {
+                for (position, result) in results.iter_mut().enumerate() {
+                    if result.0 == UNCOMPUTED {
+                        let pixel = first_row * width + position;
+                        *result = self.escape_at((pixel % width) as f64, (pixel / width) as f64);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Every pixel's escape result, row-major.
+    pub(crate) fn escape_results(&self) -> Vec<(u32, Complex64)> {
+        if let SampleMethod::Perturbed(frame) = &self.method {
+            return frame.compute_all(self.image_width, self.image_height);
+        }
+        let pixel_count = self.image_width * self.image_height;
+        let mut results = vec![(UNCOMPUTED, Complex64::new(0.0, 0.0)); pixel_count];
+        self.complete(0, &mut results);
+        results
+    }
+
+    /// The escape result at a possibly fractional pixel position.
+    fn escape_at(&self, column: f64, row: f64) -> (u32, Complex64) {
+        match &self.method {
+            SampleMethod::Direct { grid, .. } => {
+                let c = grid.point_at(column, row);
+                crate::calculate_escape_iterations(c.re, c.im, self.max_iterations, self.power)
+            }
+            SampleMethod::DirectFormula { grid, formula } => {
+                formula.escape_iterations(grid.point_at(column, row), self.max_iterations)
+            }
+            SampleMethod::Perturbed(frame) => frame.escape_iterations_at(column, row),
+            SampleMethod::Interior(_) => (self.max_iterations, Complex64::new(0.0, 0.0)),
+        }
+    }
+
+    /// The cached value at a possibly fractional pixel position, plus the
+    /// escape iterations the stats record. The distance-estimate and
+    /// atom-domain modes are direct-only; formula tiles have neither.
+    pub(crate) fn sample(&self, column: f64, row: f64) -> (f64, u32) {
+        match &self.method {
+            SampleMethod::Direct {
+                grid,
+                pixel_spacing,
+                ..
+            } => direct_sample(
+                grid.point_at(column, row),
+                self.max_iterations,
+                self.power,
+                *pixel_spacing,
+                self.smooth_coloring,
+                self.distance_estimate,
+                self.atom_domain,
+            ),
+            SampleMethod::Interior(_) => (f64::INFINITY, self.max_iterations),
+            _ => {
+                let escape = self.escape_at(column, row);
+                (self.escape_value(escape), escape.0)
+            }
+        }
+    }
+}
+
+impl SampleMethod {
+    fn new(options: &TileRenderOptions, atom_domain: bool) -> SampleMethod {
+        let bounds = &options.bounds;
+        let view_transform = options.view_transform();
+        let formula = options.polynomial();
+        let (width, height) = (options.image_width, options.image_height);
+        let (tier, pixel_spacing) = SampleMethod::planned_tier(options);
+        if let RenderTier::Direct = tier {
+            let grid = DirectGrid::new(
+                &options.origin_re,
+                &options.origin_im,
+                bounds.x_min,
+                bounds.x_max,
+                bounds.y_min,
+                bounds.y_max,
+                bounds.zoom,
+                options.zoom_offset,
+                view_transform,
+                width,
+                height,
+            );
+            if let Some(formula) = formula {
+                return SampleMethod::DirectFormula {
+                    grid,
+                    formula: formula.clone(),
+                };
+            }
+
+            let axis_ranges = view_transform.is_identity().then(|| {
+                let (re_min, re_max, im_min, im_max) = grid.axis_aligned_bounds();
+                (
+                    linspace(re_min, re_max, width),
+                    linspace(im_max, im_min, height),
+                )
+            });
+            // Every pixel but in atom-domain mode is black in the interior
+            // (see `generate_atom_domain_image`), so only those may shortcut.
+            let in_set = width == 0
+                || height == 0
+                || !atom_domain
+                    && match &axis_ranges {
+                        Some((re_range, im_range)) => rect_in_set(
+                            re_range.clone(),
+                            im_range.clone(),
+                            options.max_iterations,
+                            options.power,
+                        ),
+                        None => transformed_border_in_set(
+                            width,
+                            height,
+                            options.max_iterations,
+                            options.power,
+                            |column, row| grid.point_at(column, row),
+                        ),
+                    };
+            if in_set {
+                return SampleMethod::Interior(RenderTier::Direct);
+            }
+            return SampleMethod::Direct {
+                grid,
+                pixel_spacing,
+                axes: axis_ranges
+                    .map(|(re_range, im_range)| (re_range.collect(), im_range.collect())),
+            };
+        }
+
+        let frame = match formula {
+            Some(formula) => perturbation::PerturbedFrame::new_polynomial(
+                &options.origin_re,
+                &options.origin_im,
+                bounds.x_min,
+                bounds.x_max,
+                bounds.y_min,
+                bounds.y_max,
+                bounds.zoom,
+                options.zoom_offset,
+                view_transform,
+                width,
+                height,
+                options.max_iterations,
+                formula,
+            ),
+            None => perturbation::PerturbedFrame::new(
+                &options.origin_re,
+                &options.origin_im,
+                bounds.x_min,
+                bounds.x_max,
+                bounds.y_min,
+                bounds.y_max,
+                bounds.zoom,
+                options.zoom_offset,
+                view_transform,
+                width,
+                height,
+                options.max_iterations,
+                options.power,
+                ESCAPE_RADIUS,
+            ),
+        };
+        match frame {
+            Ok(frame) if !frame.border_in_set(width, height) => SampleMethod::Perturbed(frame),
+            _ => SampleMethod::Interior(tier),
+        }
+    }
+
+    /// The tier `new` picks for `options` (before any frame check), and the
+    /// tile's pixel spacing.
+    fn planned_tier(options: &TileRenderOptions) -> (RenderTier, f64) {
+        let bounds = &options.bounds;
+        let pixel_spacing = tile_pixel_spacing(
+            bounds.x_min,
+            bounds.x_max,
+            bounds.y_min,
+            bounds.y_max,
+            bounds.zoom,
+            options.zoom_offset,
+            options.view_transform(),
+            options.image_width,
+            options.image_height,
+        );
+        let effective_zoom = bounds.zoom as i64 + options.zoom_offset as i64;
+        let (exponent, float_exp) = match options.polynomial() {
+            Some(formula) => (
+                formula.degree(),
+                perturbation::formula_uses_float_exp(effective_zoom),
+            ),
+            None => (
+                options.power,
+                perturbation::uses_float_exp(effective_zoom, options.power),
+            ),
+        };
+        let tier = if !uses_perturbation(pixel_spacing, exponent) {
+            RenderTier::Direct
+        } else if float_exp {
+            RenderTier::FloatExp
+        } else {
+            RenderTier::Perturbation
+        };
+        (tier, pixel_spacing)
+    }
+}
+
+/// A tile's pixels as they are computed, in any order and over any number
+/// of calls: the state progressive passes, render sessions and renders
+/// seeded from a parent tile build a tile in.
+pub(crate) struct PartialTile {
+    /// Per-pixel values; uncomputed pixels hold `NaN`.
+    values: Vec<f32>,
+    /// Per-pixel escape results when the sampler is escape-valued (see
+    /// `PixelSampler::escape_valued`), `UNCOMPUTED` where not yet known;
+    /// empty otherwise.
+    escape_results: Vec<(u32, Complex64)>,
+    stats: TileIterationStats,
+    /// The pixels whose values were seeded (see `seed`), leaving stand-ins
+    /// in their escape results.
+    seeded: Vec<usize>,
+}
+
+impl PartialTile {
+    pub(crate) fn new(sampler: &PixelSampler) -> PartialTile {
+        let pixel_count = sampler.image_width * sampler.image_height;
+        PartialTile {
+            values: vec![f32::NAN; pixel_count],
+            escape_results: if sampler.escape_valued() {
+                vec![(UNCOMPUTED, Complex64::new(0.0, 0.0)); pixel_count]
+            } else {
+                Vec::new()
+            },
+            stats: TileIterationStats::default(),
+            seeded: Vec::new(),
+        }
+    }
+
+    /// Computes the listed pixels (row-major indices) through the batched
+    /// kernels, without subdivision: sparse grids like the coarse passes of
+    /// a progressive render.
+    pub(crate) fn compute_pixels(&mut self, sampler: &PixelSampler, pixels: &[usize]) {
+        if self.escape_results.is_empty() {
+            let width = sampler.image_width;
+            for &pixel in pixels {
+                let (value, escape_iterations) =
+                    sampler.sample((pixel % width) as f64, (pixel / width) as f64);
+                self.stats.record(escape_iterations, sampler.max_iterations);
+                self.values[pixel] = value as f32;
+            }
+            return;
+        }
+        for (&pixel, escape) in pixels.iter().zip(sampler.escape_results_of(pixels)) {
+            self.record(sampler, pixel, escape);
+        }
+    }
+
+    /// Takes `value` for `pixel` without computing it, as a render from a
+    /// parent tile reuses the parent's samples. Its escape result becomes a
+    /// stand-in that only tells the subdivision's ring tests whether the
+    /// pixel is interior.
+    pub(crate) fn seed(&mut self, sampler: &PixelSampler, pixel: usize, value: f32) {
+        let escape_iterations = escape_iterations_of(value, sampler.max_iterations);
+        self.stats.record(escape_iterations, sampler.max_iterations);
+        self.values[pixel] = value;
+        if let Some(escape) = self.escape_results.get_mut(pixel) {
+            *escape = (escape_iterations, Complex64::new(0.0, 0.0));
+            self.seeded.push(pixel);
+        }
+    }
+
+    /// Computes every uncomputed pixel of the rows `rows` as a whole-tile
+    /// render would (see `PixelSampler::complete`), with the pixels already
+    /// known seeding the subdivision.
+    pub(crate) fn complete_rows(&mut self, sampler: &PixelSampler, rows: Range<usize>) {
+        let width = sampler.image_width;
+        let pixels = rows.start * width..rows.end * width;
+        if self.escape_results.is_empty() {
+            let uncomputed: Vec<usize> = pixels
+                .filter(|&pixel| self.values[pixel].is_nan())
+                .collect();
+            self.compute_pixels(sampler, &uncomputed);
+            return;
+        }
+        sampler.complete(rows.start, &mut self.escape_results[pixels.clone()]);
+        for pixel in pixels {
+            if self.values[pixel].is_nan() {
+                self.record(sampler, pixel, self.escape_results[pixel]);
+            }
+        }
+    }
+
+    fn record(&mut self, sampler: &PixelSampler, pixel: usize, escape: (u32, Complex64)) {
+        self.escape_results[pixel] = escape;
+        self.stats.record(escape.0, sampler.max_iterations);
+        self.values[pixel] = sampler.escape_value(escape) as f32;
+    }
+
+    /// Whether the tile was taken by `finish`.
+    pub(crate) fn is_finished(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The finished tile, anti-aliased and assembled like a `render_tile`
+    /// result, once every pixel is computed. Takes the pixels, leaving the
+    /// tile empty. The seeded pixels' escape results are computed here if
+    /// the tile's channels read them.
+    pub(crate) fn finish(
+        &mut self,
+        sampler: &PixelSampler,
+        options: &TileRenderOptions,
+    ) -> MandelbrotTile {
+        let values = std::mem::take(&mut self.values);
+        let mut escape_results = std::mem::take(&mut self.escape_results);
+        if !self.seeded.is_empty() {
+            if options.reads_channels() {
+                for (&pixel, escape) in self
+                    .seeded
+                    .iter()
+                    .zip(sampler.escape_results_of(&self.seeded))
+                {
+                    escape_results[pixel] = escape;
+                }
+            } else {
+                escape_results = Vec::new();
+            }
+        }
+        options.layered_over(RenderedTile {
+            escape_results,
+            ..colored_tile(options, sampler, values, self.stats)
+        })
+    }
+}
+
+/// The escape iterations a smoothed value was computed from, for the tile's
+/// stats: exact without smoothing, and the ceiling with it, as smoothing
+/// subtracts a fraction in `[0, 1)`.
+fn escape_iterations_of(value: f32, max_iterations: u32) -> u32 {
+    if value.is_finite() {
+        (value.ceil().max(0.0) as u32).min(max_iterations.saturating_sub(1))
+    } else {
+        max_iterations
+    }
+}
+
+/// A tile rendered pass by pass, coarse to fine (see the module docs). The
+/// caller pulls one pass at a time with `next_pass` and can show each
+/// snapshot while the next computes.
+#[wasm_bindgen]
+pub struct ProgressiveTile {
+    options: TileRenderOptions,
+    sampler: PixelSampler,
+    tile: PartialTile,
+    initial_stride: usize,
+    completed_passes: usize,
+}
+
+#[wasm_bindgen]
+impl ProgressiveTile {
+    /// Prepares a progressive render of a `TileRenderOptions` payload (the
+    /// payload `render_tile` takes). `initial_stride` is the first pass'
+    /// stride, rounded down to a power of two; it defaults to 8, and 1
+    /// renders the whole tile in one pass. Deep tiles compute their
+    /// reference orbit here.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        options: JsValue,
+        initial_stride: Option<usize>,
+    ) -> Result<ProgressiveTile, JsValue> {
+        let options: TileRenderOptions =
+            serde_wasm_bindgen::from_value(options).map_err(JsValue::from)?;
+        Ok(ProgressiveTile::from_options(
+            options,
+            initial_stride.unwrap_or(DEFAULT_INITIAL_STRIDE),
+        ))
+    }
+
+    /// Total number of passes.
+    pub fn pass_count(&self) -> usize {
+        self.initial_stride.trailing_zeros() as usize + 1
+    }
+
+    /// Number of passes `next_pass` has returned; equals `pass_count` once
+    /// the tile is done.
+    pub fn completed_passes(&self) -> usize {
+        self.completed_passes
+    }
+
+    /// Stride of pass `index`: the distance, in pixels, between the samples
+    /// computed so far once it is done.
+    pub fn pass_stride(&self, index: usize) -> usize {
+        self.initial_stride >> index.min(self.pass_count() - 1)
+    }
+
+    /// Computes the next pass and returns its snapshot (the finished tile
+    /// for the last pass), or `None` once every pass is done.
+    pub fn next_pass(&mut self) -> Option<MandelbrotTile> {
+        if self.completed_passes >= self.pass_count() {
+            return None;
+        }
+
+        let stride = self.pass_stride(self.completed_passes);
+        let (width, height) = (self.options.image_width, self.options.image_height);
+        self.completed_passes += 1;
+        if stride == 1 {
+            // The last pass renders like `render_tile`, seeded with the
+            // coarse grids.
+            self.tile.complete_rows(&self.sampler, 0..height);
+            return Some(self.tile.finish(&self.sampler, &self.options));
+        }
+
+        // Pixels an earlier, coarser pass computed are skipped.
+        let coarser = stride * 2;
+        let first_pass = self.completed_passes == 1;
+        let pixels: Vec<usize> = (0..height)
+            .step_by(stride)
+            .flat_map(|row| (0..width).step_by(stride).map(move |column| (column, row)))
+            .filter(|&(column, row)| first_pass || row % coarser != 0 || column % coarser != 0)
+            .map(|(column, row)| row * width + column)
+            .collect();
+        self.tile.compute_pixels(&self.sampler, &pixels);
+        Some(self.snapshot(stride))
+    }
+}
+
+impl ProgressiveTile {
+    fn from_options(options: TileRenderOptions, initial_stride: usize) -> ProgressiveTile {
+        let sampler = PixelSampler::new(&options);
+        let tile = PartialTile::new(&sampler);
+        ProgressiveTile {
+            options,
+            sampler,
+            tile,
+            initial_stride: 1 << initial_stride.max(1).ilog2(),
+            completed_passes: 0,
+        }
+    }
+
+    /// The tile after a pass of `stride`: each pixel shows the sample at the
+    /// top-left corner of its `stride x stride` block.
+    fn snapshot(&self, stride: usize) -> MandelbrotTile {
+        let width = self.options.image_width;
+        let computed = &self.tile.values;
+        let values: Vec<f32> = (0..computed.len())
+            .map(|pixel| {
+                let (column, row) = (pixel % width, pixel / width);
+                computed[(row - row % stride) * width + column - column % stride]
+            })
+            .collect();
+        let rendered = RenderedTile {
+            image: recolor_values_opaque(&values, &sampled_coloring(&self.options)),
+            values,
+            stats: self.tile.stats,
+            tier: self.sampler.tier(),
+            escape_results: Vec::new(),
+            antialiased: Vec::new(),
+        };
+        MandelbrotTile::from_rendered_with_output(
+            rendered,
+            width,
+            self.options.include_values,
+            OutputFormat::Rgba8,
+            &self.options.coloring,
+            &[],
+            Vec::new(),
+        )
+    }
+}
+
+/// The tile's coloring as it applies to sampled values: without
+/// dithering, which tile assembly applies (see
+/// `MandelbrotTile::from_rendered_with_output`), and for a formula tile
+/// without the distance-estimate and atom-domain modes, as formula values
+/// are always escape values.
+fn sampled_coloring(options: &TileRenderOptions) -> ColoringOptions {
+    let escape_values_only = options.polynomial().is_some();
+    let coloring = &options.coloring;
+    ColoringOptions {
+        dither: None,
+        distance_estimate: coloring.distance_estimate && !escape_values_only,
+        atom_domain: coloring.atom_domain && !escape_values_only,
+        ..coloring.clone()
+    }
+}
+
+/// `values` colored with the tile's coloring and anti-aliased by resampling
+/// through `sampler`.
+fn colored_tile(
+    options: &TileRenderOptions,
+    sampler: &PixelSampler,
+    values: Vec<f32>,
+    stats: TileIterationStats,
+) -> RenderedTile {
+    let coloring = sampled_coloring(options);
+    let rendered = RenderedTile {
+        image: recolor_values_opaque(&values, &coloring),
+        values,
+        stats,
+        tier: sampler.tier(),
+        escape_results: Vec::new(),
+        antialiased: Vec::new(),
+    };
+    antialias_tile(
+        rendered,
+        options.image_width,
+        options.image_height,
+        options.supersampling,
+        options.adaptive_antialiasing,
+        coloring.palette_thresholds(),
+        coloring.color_density.max(1),
+        |column, row| sampler.sample(column, row).0,
+        precise_color_fn(&coloring),
+    )
+}
@@ -0,0 +1,108 @@
+use super::*;
+use crate::test_fixtures::{assert_same_tile, deep_tile, full_set_tile, tile};
+
+/// Every pass of a progressive render of `options`.
+fn passes(options: TileRenderOptions, initial_stride: usize) -> Vec<MandelbrotTile> {
+    let mut progressive = ProgressiveTile::from_options(options, initial_stride);
+    std::iter::from_fn(|| progressive.next_pass()).collect()
+}
+
+#[test]
+fn strides_halve_down_to_one_pixel() {
+    let mut progressive = ProgressiveTile::from_options(full_set_tile(), 8);
+    assert_eq!(progressive.pass_count(), 4);
+    let strides: Vec<usize> = (0..4).map(|pass| progressive.pass_stride(pass)).collect();
+    assert_eq!(strides, [8, 4, 2, 1]);
+    for pass in 0..4 {
+        assert_eq!(progressive.completed_passes(), pass);
+        assert!(progressive.next_pass().is_some());
+    }
+    assert!(progressive.next_pass().is_none());
+    assert_eq!(progressive.completed_passes(), 4);
+
+    // Strides round down to a power of two; 1 is a single full pass.
+    assert_eq!(
+        ProgressiveTile::from_options(full_set_tile(), 6).pass_count(),
+        3
+    );
+    assert_eq!(
+        ProgressiveTile::from_options(full_set_tile(), 0).pass_count(),
+        1
+    );
+}
+
+#[test]
+fn snapshots_fill_blocks_from_their_corner_sample() {
+    let passes = passes(full_set_tile(), 8);
+    let finished = passes.last().unwrap();
+    for (pass, snapshot) in passes.iter().enumerate() {
+        let stride = 8 >> pass;
+        for (pixel, &value) in snapshot.values.iter().enumerate() {
+            let (column, row) = (pixel % 24, pixel / 24);
+            let corner = (row - row % stride) * 24 + column - column % stride;
+            // Bit patterns, so interior (infinite) values compare too.
+            assert_eq!(value.to_bits(), finished.values[corner].to_bits());
+        }
+        assert_eq!(
+            snapshot.image,
+            crate::recolor_values(&snapshot.values, &ColoringOptions::default())
+        );
+    }
+    // The coarse snapshot already shows interior and escaping pixels.
+    assert!(passes[0].values.iter().any(|value| value.is_finite()));
+    assert!(passes[0].values.iter().any(|value| value.is_infinite()));
+}
+
+#[test]
+fn the_last_pass_matches_a_full_render() {
+    let mut options = full_set_tile();
+    options.output_format = OutputFormat::Rgba16;
+    let rendered = options.render_layered();
+    assert_same_tile(passes(options, 8).last().unwrap(), &rendered);
+
+    let mut distance_estimate = full_set_tile();
+    distance_estimate.coloring.distance_estimate = true;
+    let rendered = distance_estimate.render_layered();
+    assert_same_tile(passes(distance_estimate, 4).last().unwrap(), &rendered);
+
+    let mut antialiased = full_set_tile();
+    antialiased.supersampling = 2;
+    antialiased.adaptive_antialiasing = true;
+    let rendered = antialiased.render_layered();
+    assert_same_tile(passes(antialiased, 8).last().unwrap(), &rendered);
+}
+
+#[test]
+fn deep_tiles_render_progressively_by_perturbation() {
+    let rendered = deep_tile().render_layered();
+    assert_eq!(rendered.tier, RenderTier::Perturbation as u8);
+    let passes = passes(deep_tile(), 4);
+    assert_eq!(passes.len(), 3);
+    assert!(passes
+        .iter()
+        .all(|pass| pass.tier == RenderTier::Perturbation as u8));
+    assert_same_tile(passes.last().unwrap(), &rendered);
+}
+
+#[test]
+fn tiles_inside_the_set_finish_without_computing() {
+    // A small square around -0.2, well inside the main cardioid.
+    let center = perturbation::origin_tile_coordinate(2);
+    let half = perturbation::origin_tile_coordinate(2) / 40.0;
+    let inside = || {
+        tile(
+            ("-0.2", "0"),
+            (center - half, center + half, center - half, center + half),
+            2,
+            0,
+            100,
+        )
+    };
+    assert!(matches!(
+        PixelSampler::new(&inside()).method,
+        SampleMethod::Interior(RenderTier::Direct)
+    ));
+    let rendered = inside().render_layered();
+    assert!(rendered.values.iter().all(|value| value.is_infinite()));
+    assert_same_tile(passes(inside(), 4).last().unwrap(), &rendered);
+}
@@ -0,0 +1,114 @@
+//! Cancellable, sliced tile renders.
+//!
+//! `render_tile` runs a tile to completion in one call, so the worker can
+//! neither report progress nor stop a deep tile the user has panned away
+//! from, short of terminating itself. A `RenderSession` instead computes the
+//! tile a bounded band of rows per `advance` call, reporting
+//! `percent_complete` after each; the worker yields to its message loop
+//! between bands and calls `cancel` (or simply stops advancing and frees
+//! the session) when the tile is no longer wanted. Each band is computed as
+//! `render_tile` computes a whole tile, Mariani–Silver subdivision included,
+//! so slicing costs little beyond the rectangles cut at band edges. Once
+//! every row is done, `finish` assembles the same tile `render_tile`
+//! returns.
+//!
+//! Setup — parsing the origin and, for deep tiles, computing the reference
+//! orbit — and the final assembly (anti-aliasing, layers, channels) run
+//! outside the bands.
+
+use wasm_bindgen::prelude::*;
+
+use crate::progressive::{PartialTile, PixelSampler};
+use crate::{MandelbrotTile, TileRenderOptions};
+
+#[cfg(test)]
+#[path = "session_test.rs"]
+mod session_test;
+
+/// A tile render advanced slice by slice (see the module docs).
+#[wasm_bindgen]
+pub struct RenderSession {
+    options: TileRenderOptions,
+    sampler: PixelSampler,
+    tile: PartialTile,
+    /// Rows computed so far, from the top.
+    completed_rows: usize,
+    cancelled: bool,
+}
+
+#[wasm_bindgen]
+impl RenderSession {
+    /// Prepares a render of a `TileRenderOptions` payload (the payload
+    /// `render_tile` takes).
+    #[wasm_bindgen(constructor)]
+    pub fn new(options: JsValue) -> Result<RenderSession, JsValue> {
+        let options: TileRenderOptions =
+            serde_wasm_bindgen::from_value(options).map_err(JsValue::from)?;
+        Ok(RenderSession::from_options(options))
+    }
+
+    /// Computes up to `row_budget` more rows (at least one). Returns whether
+    /// rows remain, so `while (session.advance(n)) { ... }` runs the render
+    /// out; a cancelled session computes nothing and returns false.
+    pub fn advance(&mut self, row_budget: usize) -> bool {
+        if self.cancelled {
+            return false;
+        }
+        let end_row = (self.completed_rows + row_budget.max(1)).min(self.options.image_height);
+        self.tile
+            .complete_rows(&self.sampler, self.completed_rows..end_row);
+        self.completed_rows = end_row;
+        !self.is_done()
+    }
+
+    /// Share of the tile's rows computed so far, from 0 to 100. Only the
+    /// rows count: the setup in the constructor (the reference orbit of a
+    /// deep tile) is done before the first `advance`, and `finish` still
+    /// anti-aliases and adds layers and channels after this reaches 100.
+    pub fn percent_complete(&self) -> f64 {
+        match self.options.image_height {
+            0 => 100.0,
+            height => self.completed_rows as f64 / height as f64 * 100.0,
+        }
+    }
+
+    /// Whether every row is computed.
+    pub fn is_done(&self) -> bool {
+        self.completed_rows >= self.options.image_height
+    }
+
+    /// Stops the render: later `advance` calls compute nothing and `finish`
+    /// returns `None`.
+    pub fn cancel(&mut self) {
+        self.cancelled = true;
+    }
+
+    /// Whether `cancel` was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// The finished tile, as `render_tile` would return it, once every row
+    /// is computed; `None` before then, after `cancel`, or if the tile was
+    /// already taken.
+    pub fn finish(&mut self) -> Option<MandelbrotTile> {
+        if self.cancelled || !self.is_done() || self.tile.is_finished() {
+            return None;
+        }
+        Some(self.tile.finish(&self.sampler, &self.options))
+    }
+}
+
+impl RenderSession {
+    fn from_options(options: TileRenderOptions) -> RenderSession {
+        let sampler = PixelSampler::new(&options);
+        let tile = PartialTile::new(&sampler);
+        RenderSession {
+            options,
+            sampler,
+            tile,
+            completed_rows: 0,
+            cancelled: false,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use super::*;
+use crate::test_fixtures::{self, deep_tile};
+
+/// The full-set tile, adaptively anti-aliased so `finish` has work left.
+fn full_set_tile() -> TileRenderOptions {
+    TileRenderOptions {
+        supersampling: 2,
+        adaptive_antialiasing: true,
+        ..test_fixtures::full_set_tile()
+    }
+}
+
+#[test]
+fn slices_run_out_to_the_full_render() {
+    let rendered = full_set_tile().render_layered();
+    let mut session = RenderSession::from_options(full_set_tile());
+    assert_eq!(session.percent_complete(), 0.0);
+    assert!(session.finish().is_none());
+
+    let mut progress = Vec::new();
+    while session.advance(6) {
+        progress.push(session.percent_complete());
+    }
+    assert_eq!(progress, [30.0, 60.0, 90.0]);
+    assert!(session.is_done());
+    assert_eq!(session.percent_complete(), 100.0);
+
+    let tile = session.finish().unwrap();
+    assert_eq!(tile.values, rendered.values);
+    assert_eq!(tile.image, rendered.image);
+    assert_eq!(
+        (tile.min_iter, tile.max_iter),
+        (rendered.min_iter, rendered.max_iter)
+    );
+    // The tile is handed out once.
+    assert!(session.finish().is_none());
+}
+
+#[test]
+fn cancelled_sessions_stop_between_slices() {
+    let mut session = RenderSession::from_options(full_set_tile());
+    assert!(session.advance(5));
+    session.cancel();
+    assert!(session.is_cancelled());
+    assert!(!session.advance(5));
+    assert_eq!(session.percent_complete(), 25.0);
+    assert!(!session.is_done());
+    assert!(session.finish().is_none());
+}
+
+#[test]
+fn deep_tiles_slice_by_perturbation() {
+    let rendered = deep_tile().render_layered();
+    let mut session = RenderSession::from_options(deep_tile());
+    while session.advance(7) {}
+    let tile = session.finish().unwrap();
+    assert_eq!(tile.tier, rendered.tier);
+    assert_eq!(tile.values, rendered.values);
+    assert_eq!(tile.image, rendered.image);
+}